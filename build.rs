@@ -0,0 +1,56 @@
+use std::env;
+use std::process::Command;
+use std::str;
+
+// Modules that need a newer compiler than the rest of the crate are left out
+// on older compilers instead of failing the build.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let compiler = match rustc_minor_version() {
+        Some(compiler) => compiler,
+        None => return,
+    };
+
+    if compiler >= 80 {
+        println!("cargo:rustc-check-cfg=cfg(no_const_control_flow)");
+        println!("cargo:rustc-check-cfg=cfg(no_const_panic)");
+        println!("cargo:rustc-check-cfg=cfg(no_diagnostic_namespace)");
+        println!("cargo:rustc-check-cfg=cfg(no_hint_spin_loop)");
+    }
+
+    if compiler < 46 {
+        // `if`, `match` and loops in const fn.
+        // https://blog.rust-lang.org/2020/08/27/Rust-1.46.0.html#const-fn-improvements
+        println!("cargo:rustc-cfg=no_const_control_flow");
+    }
+
+    if compiler < 49 {
+        // core::hint::spin_loop, which replaces the deprecated
+        // core::sync::atomic::spin_loop_hint.
+        println!("cargo:rustc-cfg=no_hint_spin_loop");
+    }
+
+    if compiler < 57 {
+        // `panic!` and `assert!` in const contexts.
+        // https://blog.rust-lang.org/2021/12/02/Rust-1.57.0.html#panic-in-const-contexts
+        println!("cargo:rustc-cfg=no_const_panic");
+    }
+
+    if compiler < 78 {
+        // #[diagnostic::on_unimplemented].
+        // https://blog.rust-lang.org/2024/05/02/Rust-1.78.0.html#diagnostic-attributes
+        println!("cargo:rustc-cfg=no_diagnostic_namespace");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
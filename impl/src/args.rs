@@ -1,10 +1,12 @@
 use syn::parse::{Error, Parse, ParseStream, Result};
-use syn::{LitInt, Path, Token};
+use syn::{Ident, LitInt, Path, Token};
 
 pub enum Args {
     None,
     Path(Path),
     PathPos(Path, usize),
+    PathIndex(Path, usize),
+    PathOrder(Path, i32),
 }
 
 impl Parse for Args {
@@ -17,6 +19,32 @@ impl Parse for Args {
             return Ok(Args::Path(path));
         }
         input.parse::<Token![,]>()?;
+        if input.peek(Ident) {
+            let key: Ident = input.parse()?;
+            if key == "order" {
+                input.parse::<Token![=]>()?;
+                let neg: Option<Token![-]> = input.parse()?;
+                let lit: LitInt = input.parse()?;
+                let order: i32 = if neg.is_some() {
+                    format!("-{}", lit.base10_digits())
+                        .parse()
+                        .map_err(|err| Error::new(lit.span(), err))?
+                } else {
+                    lit.base10_parse()?
+                };
+                return Ok(Args::PathOrder(path, order));
+            }
+            if key != "index" {
+                return Err(Error::new(
+                    key.span(),
+                    "expected `index = N` or `order = N`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let lit: LitInt = input.parse()?;
+            let index: usize = lit.base10_parse()?;
+            return Ok(Args::PathIndex(path, index));
+        }
         let lit: LitInt = input.parse()?;
         let pos: usize = lit.base10_parse()?;
         if pos > 9999 {
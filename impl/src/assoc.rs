@@ -0,0 +1,269 @@
+use crate::args::Args;
+use crate::element::{self, Element};
+use proc_macro2::{Delimiter, Ident, Spacing, TokenStream, TokenTree};
+use quote::quote;
+use std::mem;
+use syn::parse::{Error, Parse, ParseStream, Parser, Result};
+use syn::{braced, Attribute, Generics, Token, Type, WhereClause};
+
+struct ImplBlock {
+    attrs: Vec<Attribute>,
+    unsafety: Option<Token![unsafe]>,
+    generics: Generics,
+    trait_: Option<Type>,
+    self_ty: Type,
+    where_clause: Option<WhereClause>,
+    inner_attrs: Vec<Attribute>,
+    items: Vec<Item>,
+}
+
+struct Item {
+    attrs: Vec<Attribute>,
+    tokens: TokenStream,
+}
+
+// What an item in an impl block is, as far as its leading keywords tell.
+#[derive(Copy, Clone, PartialEq)]
+enum Kind {
+    Unknown,
+    Const,
+    // A const, static or type item, which may have an initializer.
+    Value,
+    Fn,
+}
+
+impl Kind {
+    fn next(self, ident: &Ident) -> Self {
+        match self {
+            Kind::Unknown if ident == "const" => Kind::Const,
+            Kind::Unknown if ident == "static" || ident == "type" => Kind::Value,
+            Kind::Unknown if ident == "fn" => Kind::Fn,
+            Kind::Const
+                if ident == "fn" || ident == "unsafe" || ident == "async" || ident == "extern" =>
+            {
+                Kind::Fn
+            }
+            Kind::Const => Kind::Value,
+            kind => kind,
+        }
+    }
+}
+
+// #[distributed_slice]
+// impl Plugin for MyType {
+//     #[distributed_slice(PLUGINS)]
+//     const DESCRIPTOR: Descriptor = ...;
+// }
+pub fn is_impl(input: &TokenStream) -> bool {
+    let peek = |input: ParseStream| {
+        input.call(Attribute::parse_outer)?;
+        input.parse::<Option<Token![unsafe]>>()?;
+        let is_impl = input.peek(Token![impl]);
+        input.parse::<TokenStream>()?;
+        Ok(is_impl)
+    };
+    peek.parse2(input.clone()).unwrap_or(false)
+}
+
+impl Parse for ImplBlock {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let unsafety: Option<Token![unsafe]> = input.parse()?;
+        input.parse::<Token![impl]>()?;
+        let generics: Generics = input.parse()?;
+        let first: Type = input.parse()?;
+        let (trait_, self_ty) = if input.parse::<Option<Token![for]>>()?.is_some() {
+            (Some(first), input.parse()?)
+        } else {
+            (None, first)
+        };
+        let where_clause: Option<WhereClause> = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let inner_attrs = content.call(Attribute::parse_inner)?;
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            let attrs = content.call(Attribute::parse_outer)?;
+            // An item ends at a semicolon, or at its braced body unless the
+            // braces are part of the initializer of a const, static or type
+            // item. The initializer starts at the first `=` outside of angle
+            // brackets, so that `Iterator<Item = u8>` in a signature or where
+            // clause is not mistaken for one.
+            let mut tokens = TokenStream::new();
+            let mut kind = Kind::Unknown;
+            let mut depth = 0usize;
+            let mut arrow = false;
+            let mut initializer = false;
+            loop {
+                let tt: TokenTree = content.parse()?;
+                let end = match &tt {
+                    TokenTree::Ident(ident) => {
+                        kind = kind.next(ident);
+                        false
+                    }
+                    TokenTree::Punct(punct) if initializer => punct.as_char() == ';',
+                    TokenTree::Punct(punct) => match punct.as_char() {
+                        ';' => true,
+                        '<' => {
+                            depth += 1;
+                            false
+                        }
+                        '>' if !arrow => {
+                            depth = depth.saturating_sub(1);
+                            false
+                        }
+                        '=' => {
+                            initializer = kind == Kind::Value
+                                && depth == 0
+                                && punct.spacing() == Spacing::Alone;
+                            false
+                        }
+                        _ => false,
+                    },
+                    TokenTree::Group(group) => {
+                        group.delimiter() == Delimiter::Brace && !initializer
+                    }
+                    TokenTree::Literal(_) => false,
+                };
+                arrow = match &tt {
+                    TokenTree::Punct(punct) => {
+                        punct.as_char() == '-' && punct.spacing() == Spacing::Joint
+                    }
+                    _ => false,
+                };
+                tokens.extend(Some(tt));
+                if end {
+                    break;
+                }
+            }
+            items.push(Item { attrs, tokens });
+        }
+
+        Ok(ImplBlock {
+            attrs,
+            unsafety,
+            generics,
+            trait_,
+            self_ty,
+            where_clause,
+            inner_attrs,
+            items,
+        })
+    }
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let mut input: ImplBlock = match syn::parse2(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let input_items = mem::replace(&mut input.items, Vec::new());
+    let self_ty = &input.self_ty;
+    let qself = if let Some(trait_) = &input.trait_ {
+        element::replace_self(quote!(<#self_ty as #trait_>), self_ty)
+    } else {
+        quote!(<#self_ty>)
+    };
+
+    let mut items = Vec::new();
+    let mut registrations = Vec::new();
+    let mut errors: Option<Error> = None;
+    for item in input_items {
+        let mut slices = Vec::new();
+        let mut linkme_attrs = Vec::new();
+        let mut attrs = Vec::new();
+        for attr in item.attrs {
+            let is_slice = attr
+                .path
+                .segments
+                .last()
+                .map_or(false, |segment| segment.ident == "distributed_slice");
+            if is_slice {
+                slices.push(attr);
+            } else if attr.path.is_ident("linkme") {
+                linkme_attrs.push(attr);
+            } else {
+                attrs.push(attr);
+            }
+        }
+
+        for attr in &slices {
+            match register(
+                attr,
+                &item.tokens,
+                &linkme_attrs,
+                &qself,
+                &input.generics,
+                self_ty,
+            ) {
+                Ok(registration) => registrations.push(registration),
+                Err(err) => match &mut errors {
+                    None => errors = Some(err),
+                    Some(errors) => errors.combine(err),
+                },
+            }
+        }
+        if slices.is_empty() {
+            attrs.extend(linkme_attrs);
+        }
+
+        let tokens = item.tokens;
+        items.push(quote!(#(#attrs)* #tokens));
+    }
+
+    let attrs = &input.attrs;
+    let unsafety = input.unsafety;
+    let generics = &input.generics;
+    let trait_ = input.trait_.as_ref().map(|trait_| quote!(#trait_ for));
+    let where_clause = &input.where_clause;
+    let inner_attrs = &input.inner_attrs;
+    let errors = errors.map(|errors| errors.to_compile_error());
+
+    quote! {
+        #(#attrs)*
+        #unsafety impl #generics #trait_ #self_ty #where_clause {
+            #(#inner_attrs)*
+            #(#items)*
+        }
+
+        #(
+            const _: () = {
+                #registrations
+            };
+        )*
+
+        #errors
+    }
+}
+
+fn register(
+    attr: &Attribute,
+    tokens: &TokenStream,
+    linkme_attrs: &[Attribute],
+    qself: &TokenStream,
+    generics: &Generics,
+    self_ty: &Type,
+) -> Result<TokenStream> {
+    if !generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            generics,
+            "cannot register items of a generic impl in a distributed slice",
+        ));
+    }
+    let args: Args = attr.parse_args()?;
+    let element = Element::parse_associated(quote!(#(#linkme_attrs)* #tokens), qself, self_ty)?;
+    Ok(match args {
+        Args::None => {
+            return Err(Error::new_spanned(
+                attr,
+                "expected the path of a distributed slice",
+            ))
+        }
+        Args::Path(path) => element::expand(path, None, None, None, element),
+        Args::PathPos(path, pos) => element::expand(path, pos, None, None, element),
+        Args::PathIndex(path, index) => element::expand(path, None, index, None, element),
+        Args::PathOrder(path, order) => element::expand(path, None, None, order, element),
+    })
+}
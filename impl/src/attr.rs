@@ -1,9 +1,28 @@
 use syn::parse::{Error, ParseStream, Result};
-use syn::{parse_quote, Attribute, Path, Token};
+use syn::{parse_quote, Attribute, Ident, LitStr, Path, Token};
+
+pub(crate) struct Options {
+    pub linkme_path: Path,
+    pub export_c: Option<LitStr>,
+    pub mirror: Option<Path>,
+    pub abi: Option<LitStr>,
+    pub ordered: Option<Ident>,
+    pub dylib: Option<LitStr>,
+}
 
 // #[linkme(crate = path::to::linkme)]
-pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
+// #[linkme(export_c = "name")]
+// #[linkme(mirror = path::to::SLICE)]
+// #[linkme(abi = "C")]
+// #[linkme(ordered)]
+// #[linkme(dylib = "name")]
+pub(crate) fn options(attrs: &mut Vec<Attribute>) -> Result<Options> {
     let mut linkme_path = None;
+    let mut export_c = None;
+    let mut mirror = None;
+    let mut abi = None;
+    let mut ordered = None;
+    let mut dylib = None;
     let mut errors: Option<Error> = None;
 
     attrs.retain(|attr| {
@@ -11,11 +30,40 @@ pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
             return true;
         }
         match attr.parse_args_with(|input: ParseStream| {
-            input.parse::<Token![crate]>()?;
-            input.parse::<Token![=]>()?;
-            input.call(Path::parse_mod_style)
+            if input.peek(Token![crate]) {
+                input.parse::<Token![crate]>()?;
+                input.parse::<Token![=]>()?;
+                linkme_path = Some(input.call(Path::parse_mod_style)?);
+                return Ok(());
+            }
+            let key: Ident = input.parse()?;
+            if key == "export_c" {
+                input.parse::<Token![=]>()?;
+                export_c = Some(input.parse()?);
+                Ok(())
+            } else if key == "mirror" {
+                input.parse::<Token![=]>()?;
+                mirror = Some(input.parse()?);
+                Ok(())
+            } else if key == "abi" {
+                input.parse::<Token![=]>()?;
+                abi = Some(input.parse()?);
+                Ok(())
+            } else if key == "ordered" {
+                ordered = Some(key);
+                Ok(())
+            } else if key == "dylib" {
+                input.parse::<Token![=]>()?;
+                dylib = Some(input.parse()?);
+                Ok(())
+            } else {
+                Err(Error::new(
+                    key.span(),
+                    "expected `crate = ...`, `export_c = \"...\"`, `mirror = ...`, `abi = \"...\"`, `ordered` or `dylib = \"...\"`",
+                ))
+            }
         }) {
-            Ok(path) => linkme_path = Some(path),
+            Ok(()) => {}
             Err(err) => match &mut errors {
                 None => errors = Some(err),
                 Some(errors) => errors.combine(err),
@@ -25,7 +73,49 @@ pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
     });
 
     match errors {
-        None => Ok(linkme_path.unwrap_or_else(|| parse_quote!(::linkme))),
+        None => Ok(Options {
+            linkme_path: linkme_path.unwrap_or_else(|| parse_quote!(::linkme)),
+            export_c,
+            mirror,
+            abi,
+            ordered,
+            dylib,
+        }),
         Some(errors) => Err(errors),
     }
 }
+
+pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
+    let options = options(attrs)?;
+    if let Some(export_c) = options.export_c {
+        return Err(Error::new(
+            export_c.span(),
+            "export_c is only supported on a distributed slice declaration",
+        ));
+    }
+    if let Some(mirror) = options.mirror {
+        return Err(Error::new_spanned(
+            mirror,
+            "mirror is only supported on a distributed slice declaration",
+        ));
+    }
+    if let Some(abi) = options.abi {
+        return Err(Error::new(
+            abi.span(),
+            "abi is only supported on a distributed slice declaration",
+        ));
+    }
+    if let Some(ordered) = options.ordered {
+        return Err(Error::new(
+            ordered.span(),
+            "ordered is only supported on a distributed slice declaration",
+        ));
+    }
+    if let Some(dylib) = options.dylib {
+        return Err(Error::new(
+            dylib.span(),
+            "dylib is only supported on a distributed slice declaration",
+        ));
+    }
+    Ok(options.linkme_path)
+}
@@ -1,8 +1,9 @@
 use crate::{attr, linker};
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{bracketed, Attribute, Error, Ident, Token, Type, Visibility};
+use syn::spanned::Spanned;
+use syn::{bracketed, Abi, Attribute, Error, Ident, LitStr, Path, Token, Type, Visibility};
 
 struct Declaration {
     attrs: Vec<Attribute>,
@@ -37,6 +38,16 @@ impl Parse for Declaration {
 }
 
 pub fn expand(input: TokenStream) -> TokenStream {
+    do_expand(input, false)
+}
+
+// A distributed map is a distributed slice of `(K, V)` pairs behind a
+// `DistributedMap<K, V>`, which builds its index on first access.
+pub fn expand_map(input: TokenStream) -> TokenStream {
+    do_expand(input, true)
+}
+
+fn do_expand(input: TokenStream, map: bool) -> TokenStream {
     let msg = "distributed_slice is not implemented for this platform";
     let error = Error::new_spanned(&input, msg);
     let unsupported_platform = error.to_compile_error();
@@ -51,31 +62,231 @@ pub fn expand(input: TokenStream) -> TokenStream {
     let ident = decl.ident;
     let ty = decl.ty;
 
-    let linkme_path = match attr::linkme_path(&mut attrs) {
-        Ok(path) => path,
+    let options = match attr::options(&mut attrs) {
+        Ok(options) => options,
         Err(err) => return err.to_compile_error(),
     };
+    let linkme_path = options.linkme_path;
+
+    let static_ty = if map {
+        if let Some(mirror) = &options.mirror {
+            let msg = "mirror is not supported on a distributed map";
+            return Error::new_spanned(mirror, msg).to_compile_error();
+        }
+        if let Some(ordered) = &options.ordered {
+            let msg = "ordered is not supported on a distributed map";
+            return Error::new(ordered.span(), msg).to_compile_error();
+        }
+        if let Some(dylib) = &options.dylib {
+            let msg = "dylib is not supported on a distributed map";
+            return Error::new(dylib.span(), msg).to_compile_error();
+        }
+        match map_types(&ty) {
+            Ok((key, value)) => quote!(#linkme_path::DistributedMap<#key, #value>),
+            Err(err) => return err.to_compile_error(),
+        }
+    } else {
+        quote!(#linkme_path::DistributedSlice<#ty>)
+    };
+
+    let abi_check = match &options.abi {
+        Some(abi) => match check_abi(&ty, abi, &linkme_path) {
+            Ok(abi_check) => abi_check,
+            Err(err) => return err.to_compile_error(),
+        },
+        None => TokenStream::new(),
+    };
+
+    // Ordered elements are sorted on their first access from Rust, which C
+    // code reading the exported bounds would get ahead of.
+    if let (Some(ordered), Some(_)) = (&options.ordered, &options.export_c) {
+        let msg = "ordered is not supported together with export_c";
+        return Error::new(ordered.span(), msg).to_compile_error();
+    }
+
+    let export_c = options.export_c.map(|name| {
+        let data = format!("{}_data", name.value());
+        let end = format!("{}_end", name.value());
+        quote! {
+            #[cfg(not(target_family = "wasm"))]
+            #[export_name = #data]
+            static LINKME_EXPORT_DATA: #linkme_path::private::ExportPtr<<#ty as #linkme_path::private::Slice>::Element> = unsafe {
+                #linkme_path::private::ExportPtr(&LINKME_START as *const _ as *const _)
+            };
+
+            #[cfg(not(target_family = "wasm"))]
+            #[export_name = #end]
+            static LINKME_EXPORT_END: #linkme_path::private::ExportPtr<<#ty as #linkme_path::private::Slice>::Element> = unsafe {
+                #linkme_path::private::ExportPtr(&LINKME_STOP as *const _ as *const _)
+            };
+        }
+    });
+
+    // Descriptor of this copy of the slice, for a host that loads the binary
+    // as a dynamic library. Reading the elements goes through the slice
+    // itself so that they are sorted first if the slice is ordered.
+    let dylib = options.dylib.map(|name| {
+        quote! {
+            #[cfg(not(target_family = "wasm"))]
+            fn linkme_dylib_slice() -> &'static [<#ty as #linkme_path::private::Slice>::Element] {
+                #ident.static_slice()
+            }
+
+            #[cfg(not(target_family = "wasm"))]
+            #[export_name = #name]
+            static LINKME_DYLIB: #linkme_path::private::DylibExport<<#ty as #linkme_path::private::Slice>::Element> = #linkme_path::private::DylibExport {
+                version: #linkme_path::private::DYLIB_VERSION,
+                size: ::core::mem::size_of::<<#ty as #linkme_path::private::Slice>::Element>(),
+                align: ::core::mem::align_of::<<#ty as #linkme_path::private::Slice>::Element>(),
+                type_id: #linkme_path::private::TypeId::of::<<#ty as #linkme_path::private::Slice>::Element>,
+                slice: linkme_dylib_slice,
+            };
+        }
+    });
+
+    // A mirror takes its section identity from the slice it mirrors, and
+    // checks that the element types agree, so that both declarations see the
+    // same elements.
+    let section_ident = match &options.mirror {
+        Some(mirror) => match mirror.segments.last() {
+            Some(segment) => segment.ident.clone(),
+            None => return Error::new_spanned(mirror, "expected path").to_compile_error(),
+        },
+        None => ident.clone(),
+    };
+    let wasm_registry = if let Some(mirror) = &options.mirror {
+        quote! {
+            #[cfg(target_family = "wasm")]
+            fn linkme_mirror() -> *const #linkme_path::private::wasm::Registry<<#ty as #linkme_path::private::Slice>::Element> {
+                #mirror.private_registry()
+            }
+
+            #[cfg(target_family = "wasm")]
+            static LINKME_START: #linkme_path::private::wasm::Registry<<#ty as #linkme_path::private::Slice>::Element> = #linkme_path::private::wasm::Registry::mirror(linkme_mirror);
+        }
+    } else {
+        let new = if options.ordered.is_some() {
+            quote!(ordered)
+        } else {
+            quote!(new)
+        };
+        quote! {
+            #[cfg(target_family = "wasm")]
+            static LINKME_START: #linkme_path::private::wasm::Registry<<#ty as #linkme_path::private::Slice>::Element> = #linkme_path::private::wasm::Registry::#new();
+        }
+    };
+    if let (Some(ordered), Some(_)) = (&options.ordered, &options.mirror) {
+        let msg = "ordered is not supported on a mirror";
+        return Error::new(ordered.span(), msg).to_compile_error();
+    }
+    // The elements of an ordered slice are sorted through its own
+    // declaration, which a mirror has no access to, so its macro rejects
+    // being mirrored.
+    let mirror_check = options.mirror.map(|mirror| {
+        quote_spanned! {mirror.span()=>
+            const _: fn() = || {
+                let _: &#linkme_path::DistributedSlice<#ty> = &#mirror;
+            };
+
+            #mirror ! {
+                #![linkme_mirror]
+            }
+        }
+    });
+
+    let linux_section = linker::linux::section(&section_ident);
+    let linux_section_start = linker::linux::section_start(&section_ident);
+    let linux_section_stop = linker::linux::section_stop(&section_ident);
+
+    let macos_section_start = linker::macos::section_start(&section_ident);
+    let macos_section_stop = linker::macos::section_stop(&section_ident);
+
+    let windows_section_start = linker::windows::section_start(&section_ident);
+    let windows_section_stop = linker::windows::section_stop(&section_ident);
+
+    let illumos_section = linker::illumos::section(&section_ident);
+    let illumos_section_start = linker::illumos::section_start(&section_ident);
+    let illumos_section_stop = linker::illumos::section_stop(&section_ident);
+
+    let freebsd_section = linker::freebsd::section(&section_ident);
+    let freebsd_section_start = linker::freebsd::section_start(&section_ident);
+    let freebsd_section_stop = linker::freebsd::section_stop(&section_ident);
+
+    // Ordered slices sort their elements on first access using the keys that
+    // each element places in a companion section, except where the elements
+    // are already sorted by section name or by the wasm registry.
+    let sort = options.ordered.as_ref().map(|_| {
+        let keys_ident = format_ident!("{}_k", section_ident);
+
+        let linux_keys_section = linker::linux::section(&keys_ident);
+        let linux_keys_start = linker::linux::section_start(&keys_ident);
+        let linux_keys_stop = linker::linux::section_stop(&keys_ident);
 
-    let linux_section = linker::linux::section(&ident);
-    let linux_section_start = linker::linux::section_start(&ident);
-    let linux_section_stop = linker::linux::section_stop(&ident);
+        let macos_keys_start = linker::macos::section_start(&keys_ident);
+        let macos_keys_stop = linker::macos::section_stop(&keys_ident);
 
-    let macos_section_start = linker::macos::section_start(&ident);
-    let macos_section_stop = linker::macos::section_stop(&ident);
+        let illumos_keys_section = linker::illumos::section(&keys_ident);
+        let illumos_keys_start = linker::illumos::section_start(&keys_ident);
+        let illumos_keys_stop = linker::illumos::section_stop(&keys_ident);
 
-    let windows_section_start = linker::windows::section_start(&ident);
-    let windows_section_stop = linker::windows::section_stop(&ident);
+        let freebsd_keys_section = linker::freebsd::section(&keys_ident);
+        let freebsd_keys_start = linker::freebsd::section_start(&keys_ident);
+        let freebsd_keys_stop = linker::freebsd::section_stop(&keys_ident);
 
-    let illumos_section = linker::illumos::section(&ident);
-    let illumos_section_start = linker::illumos::section_start(&ident);
-    let illumos_section_stop = linker::illumos::section_stop(&ident);
+        quote! {
+            #[cfg(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "illumos", target_os = "freebsd"))]
+            extern "C" {
+                #[cfg_attr(any(target_os = "none", target_os = "linux"), link_name = #linux_keys_start)]
+                #[cfg_attr(target_os = "macos", link_name = #macos_keys_start)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_keys_start)]
+                #[cfg_attr(target_os = "freebsd", link_name = #freebsd_keys_start)]
+                static mut LINKME_KEYS_START: #linkme_path::private::OrderKey;
+
+                #[cfg_attr(any(target_os = "none", target_os = "linux"), link_name = #linux_keys_stop)]
+                #[cfg_attr(target_os = "macos", link_name = #macos_keys_stop)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_keys_stop)]
+                #[cfg_attr(target_os = "freebsd", link_name = #freebsd_keys_stop)]
+                static mut LINKME_KEYS_STOP: #linkme_path::private::OrderKey;
+            }
+
+            #[cfg(any(target_os = "none", target_os = "linux", target_os = "illumos", target_os = "freebsd"))]
+            #[cfg_attr(any(target_os = "none", target_os = "linux"), link_section = #linux_keys_section)]
+            #[cfg_attr(target_os = "illumos", link_section = #illumos_keys_section)]
+            #[cfg_attr(target_os = "freebsd", link_section = #freebsd_keys_section)]
+            #[used]
+            static mut LINKME_KEYS_PLEASE: [#linkme_path::private::OrderKey; 0] = [];
+
+            #[cfg(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "illumos", target_os = "freebsd"))]
+            static LINKME_SORT: #linkme_path::private::Sort = unsafe {
+                #linkme_path::private::Sort::new(
+                    ::core::ptr::addr_of_mut!(LINKME_KEYS_START),
+                    ::core::ptr::addr_of_mut!(LINKME_KEYS_STOP),
+                )
+            };
+
+            #[cfg(not(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "illumos", target_os = "freebsd")))]
+            static LINKME_SORT: #linkme_path::private::Sort = #linkme_path::private::Sort::sorted();
+        }
+    });
+    let ordered = sort
+        .as_ref()
+        .map(|_| quote!(.private_ordered(&LINKME_SORT)));
+    let ordered_variant = options.ordered.as_ref().map(|_| {
+        let linkme_path_str = quote!(#linkme_path).to_string();
+        quote!(_Ordered = (#linkme_path_str, 2).1,)
+    });
 
-    let freebsd_section = linker::freebsd::section(&ident);
-    let freebsd_section_start = linker::freebsd::section_start(&ident);
-    let freebsd_section_stop = linker::freebsd::section_stop(&ident);
+    let mut slice = quote! {
+        unsafe {
+            #linkme_path::DistributedSlice::private_new(&LINKME_START, &LINKME_STOP) #ordered
+        }
+    };
+    if map {
+        slice = quote!(#linkme_path::DistributedMap::private_new(#slice));
+    }
 
     let call_site = Span::call_site();
-    let ident_str = ident.to_string();
+    let ident_str = section_ident.to_string();
     let link_section_macro_dummy_str = format!("_linkme_macro_{}", ident);
     let link_section_macro_dummy = Ident::new(&link_section_macro_dummy_str, call_site);
     let link_section_enum_dummy_str = format!("_linkme_generate_{}", ident);
@@ -83,7 +294,7 @@ pub fn expand(input: TokenStream) -> TokenStream {
 
     quote! {
         #(#attrs)*
-        #vis static #ident: #linkme_path::DistributedSlice<#ty> = {
+        #vis static #ident: #static_ty = {
             #[cfg(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "illumos", target_os = "freebsd"))]
             extern "C" {
                 #[cfg_attr(any(target_os = "none", target_os = "linux"), link_name = #linux_section_start)]
@@ -99,13 +310,20 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 static LINKME_STOP: <#ty as #linkme_path::private::Slice>::Element;
             }
 
+            // Zero-length arrays of the element type, rather than (), so that
+            // the start and stop markers carry the element alignment and the
+            // linker cannot leave padding between them and the elements. Like
+            // the elements they are #[used], which rustc passes on to the
+            // linker as an /INCLUDE directive so that /OPT:REF keeps them.
             #[cfg(target_os = "windows")]
             #[link_section = #windows_section_start]
-            static LINKME_START: () = ();
+            #[used]
+            static LINKME_START: [<#ty as #linkme_path::private::Slice>::Element; 0] = [];
 
             #[cfg(target_os = "windows")]
             #[link_section = #windows_section_stop]
-            static LINKME_STOP: () = ();
+            #[used]
+            static LINKME_STOP: [<#ty as #linkme_path::private::Slice>::Element; 0] = [];
 
             #[cfg(any(target_os = "none", target_os = "linux", target_os = "illumos", target_os = "freebsd"))]
             #[cfg_attr(any(target_os = "none", target_os = "linux"), link_section = #linux_section)]
@@ -114,12 +332,27 @@ pub fn expand(input: TokenStream) -> TokenStream {
             #[used]
             static mut LINKME_PLEASE: [<#ty as #linkme_path::private::Slice>::Element; 0] = [];
 
-            #[cfg(not(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "windows", target_os = "illumos", target_os = "freebsd")))]
+            // On wasm the elements register themselves at startup, and the
+            // start marker is the list they register into.
+            #wasm_registry
+
+            #[cfg(target_family = "wasm")]
+            static LINKME_STOP: () = ();
+
+            #[cfg(not(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "windows", target_os = "illumos", target_os = "freebsd", target_family = "wasm")))]
             #unsupported_platform
 
-            unsafe {
-                #linkme_path::DistributedSlice::private_new(&LINKME_START, &LINKME_STOP)
-            }
+            #export_c
+
+            #dylib
+
+            #mirror_check
+
+            #abi_check
+
+            #sort
+
+            #slice
         };
 
         #[doc(hidden)]
@@ -131,9 +364,82 @@ pub fn expand(input: TokenStream) -> TokenStream {
         enum #link_section_enum_dummy {
             _Ident = (#ident_str, 0).1,
             _Macro = (#link_section_macro_dummy_str, 1).1,
+            #ordered_variant
         }
 
         #[doc(hidden)]
         #vis use #link_section_macro_dummy as #ident;
     }
 }
+
+fn map_types(ty: &Type) -> Result<(&Type, &Type)> {
+    if let Type::Slice(slice) = ty {
+        if let Type::Tuple(tuple) = &*slice.elem {
+            if tuple.elems.len() == 2 {
+                return Ok((&tuple.elems[0], &tuple.elems[1]));
+            }
+        }
+    }
+    Err(Error::new_spanned(
+        ty,
+        "distributed map must have a type of the form `[(K, V)]`",
+    ))
+}
+
+// The element type must be a function pointer with exactly the given ABI, so
+// that a slice meant for C cannot silently hold Rust-ABI functions.
+// Registrations are then held to the same type by the usual element
+// typecheck. A function pointer type written out is checked here, which works
+// for every ABI. Any other element type, such as a type alias, is left to the
+// trait system, which knows the "C" and "system" function pointers of up to
+// 12 arguments.
+fn check_abi(ty: &Type, abi: &LitStr, linkme_path: &Path) -> Result<TokenStream> {
+    let element = if let Type::Slice(slice) = ty {
+        &*slice.elem
+    } else {
+        let msg = format!(
+            "abi = {:?} requires a slice of `extern {:?} fn` pointers",
+            abi.value(),
+            abi.value(),
+        );
+        return Err(Error::new_spanned(ty, msg));
+    };
+    let bare_fn = if let Type::BareFn(bare_fn) = element {
+        bare_fn
+    } else {
+        let check = match abi.value().as_str() {
+            "C" => quote!(abi_c),
+            "system" => quote!(abi_system),
+            _ => {
+                let msg = format!(
+                    "abi = {:?} can only be checked on an element type written as `extern {:?} fn`",
+                    abi.value(),
+                    abi.value(),
+                );
+                return Err(Error::new_spanned(element, msg));
+            }
+        };
+        return Ok(quote_spanned! {element.span()=>
+            const _: fn() = #linkme_path::private::#check::<#element>;
+        });
+    };
+    let actual = match &bare_fn.abi {
+        Some(Abi {
+            name: Some(name), ..
+        }) => name.value(),
+        Some(Abi { name: None, .. }) => "C".to_owned(),
+        None => "Rust".to_owned(),
+    };
+    if actual == abi.value() {
+        return Ok(TokenStream::new());
+    }
+    let msg = format!(
+        "expected `extern {:?} fn` element type, found `extern {:?} fn`",
+        abi.value(),
+        actual,
+    );
+    match &bare_fn.abi {
+        Some(actual) => Err(Error::new_spanned(actual, msg)),
+        None => Err(Error::new_spanned(bare_fn.fn_token, msg)),
+    }
+}
@@ -1,12 +1,13 @@
 use crate::linker;
 use proc_macro2::{TokenStream, TokenTree};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{braced, parenthesized, token, Ident, LitStr};
+use syn::{braced, parenthesized, token, Ident, LitStr, Path};
 
 pub struct Enum {
     linkme_ident: Ident,
     linkme_macro: Ident,
+    ordered: Option<Path>,
 }
 
 impl Parse for Enum {
@@ -38,11 +39,25 @@ impl Parse for Enum {
         parenthesized!(discriminant in variants);
         let linkme_macro = discriminant.parse::<LitStr>()?.parse::<Ident>()?;
         discriminant.parse::<TokenStream>()?;
-        variants.parse::<TokenStream>()?;
+
+        // Only present for an ordered slice:
+        //     _Ordered = (#linkme_path, 2).1,
+        let mut ordered = None;
+        while !variants.is_empty() && !variants.peek(token::Paren) {
+            variants.parse::<TokenTree>()?;
+        }
+        if !variants.is_empty() {
+            let discriminant;
+            parenthesized!(discriminant in variants);
+            ordered = Some(discriminant.parse::<LitStr>()?.parse::<Path>()?);
+            discriminant.parse::<TokenStream>()?;
+            variants.parse::<TokenStream>()?;
+        }
 
         Ok(Enum {
             linkme_ident,
             linkme_macro,
+            ordered,
         })
     }
 }
@@ -50,17 +65,25 @@ impl Parse for Enum {
 pub fn expand(input: Enum) -> TokenStream {
     let ident = input.linkme_ident;
     let ident_macro = input.linkme_macro;
+    let ident_str = ident.to_string();
+
+    if let Some(linkme_path) = input.ordered {
+        return expand_ordered(&ident, &ident_macro, &linkme_path);
+    }
 
     let linux_section = linker::linux::section(&ident);
     let macos_section = linker::macos::section(&ident);
     let windows_section = linker::windows::section(&ident);
     let illumos_section = linker::illumos::section(&ident);
     let freebsd_section = linker::freebsd::section(&ident);
+    let unique = unique(&ident_str);
 
     quote! {
         #[doc(hidden)]
         #[macro_export]
         macro_rules! #ident_macro {
+            (#![linkme_mirror]) => {};
+            #unique
             (
                 #![linkme_macro = $macro:path]
                 #![linkme_sort_key = $key:tt]
@@ -75,6 +98,35 @@ pub fn expand(input: Enum) -> TokenStream {
                     $item
                 }
             };
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_index = $index:tt]
+                $item:item
+            ) => {
+                $macro ! {
+                    $item
+                }
+
+                const _: () = {
+                    #[export_name = concat!("linkme.index.", #ident_str, ".", $index)]
+                    static LINKME_INDEX: u8 = 0;
+                };
+            };
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_order = $order:tt]
+                #![linkme_order_key = $key:tt]
+                $item:item
+            ) => {
+                compile_error!(concat!(
+                    "distributed slice `", #ident_str, "` is not ordered; ",
+                    "declare it with #[linkme(ordered)] to register elements with `order = N`",
+                ));
+
+                $macro ! {
+                    $item
+                }
+            };
             (
                 #![linkme_linux_section = $linux_section:expr]
                 #![linkme_macos_section = $macos_section:expr]
@@ -103,3 +155,136 @@ pub fn expand(input: Enum) -> TokenStream {
         }
     }
 }
+
+// Elements of an ordered slice are sorted in place on first access, so they
+// are emitted as `static mut` where no code can name them. Alongside each one
+// a key recording its order and address goes into a companion section, except
+// on Windows where the order is encoded into the grouped section name and the
+// linker sorts them, and on wasm where the registry sorts them.
+fn expand_ordered(ident: &Ident, ident_macro: &Ident, linkme_path: &Path) -> TokenStream {
+    let ident_str = ident.to_string();
+    let linkme_path = macro_path(linkme_path);
+
+    let linux_section = linker::linux::section(ident);
+    let macos_section = linker::macos::section(ident);
+    let windows_section = linker::windows::section(ident);
+    let illumos_section = linker::illumos::section(ident);
+    let freebsd_section = linker::freebsd::section(ident);
+
+    let keys_ident = format_ident!("{}_k", ident);
+    let linux_keys_section = linker::linux::section(&keys_ident);
+    let macos_keys_section = linker::macos::section(&keys_ident);
+    let illumos_keys_section = linker::illumos::section(&keys_ident);
+    let freebsd_keys_section = linker::freebsd::section(&keys_ident);
+
+    let element = |order: TokenStream, key: TokenStream| {
+        quote! {
+            const _: () = {
+                #[cfg(not(target_family = "wasm"))]
+                $(#[$attr])*
+                #[used]
+                #[cfg_attr(any(target_os = "none", target_os = "linux"), link_section = #linux_section)]
+                #[cfg_attr(target_os = "macos", link_section = #macos_section)]
+                #[cfg_attr(target_os = "windows", link_section = concat!(#windows_section, #key))]
+                #[cfg_attr(target_os = "illumos", link_section = #illumos_section)]
+                #[cfg_attr(target_os = "freebsd", link_section = #freebsd_section)]
+                static mut $ident: $ty = $expr;
+
+                #[cfg(target_family = "wasm")]
+                $(#[$attr])*
+                static $ident: $ty = $expr;
+
+                #[cfg(any(target_os = "none", target_os = "linux", target_os = "macos", target_os = "illumos", target_os = "freebsd"))]
+                #[used]
+                #[cfg_attr(any(target_os = "none", target_os = "linux"), link_section = #linux_keys_section)]
+                #[cfg_attr(target_os = "macos", link_section = #macos_keys_section)]
+                #[cfg_attr(target_os = "illumos", link_section = #illumos_keys_section)]
+                #[cfg_attr(target_os = "freebsd", link_section = #freebsd_keys_section)]
+                static mut LINKME_ORDER: #linkme_path::private::OrderKey = #linkme_path::private::OrderKey::new(
+                    #order,
+                    ::core::ptr::addr_of!($ident) as *const (),
+                );
+            };
+        }
+    };
+    let ordered = element(quote!($order), quote!($key));
+    let unordered = element(quote!(2147483648), quote!("80000000"));
+
+    let unsupported = format!(
+        "distributed slice `{}` is ordered; register elements with `order = N`",
+        ident_str,
+    );
+    let unsupported_mirror = format!(
+        "distributed slice `{}` is ordered, which is not supported on a mirror",
+        ident_str,
+    );
+
+    let unique = unique(&ident_str);
+
+    quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #ident_macro {
+            (#![linkme_mirror]) => {
+                compile_error!(#unsupported_mirror);
+            };
+            #unique
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_sort_key = $key:tt]
+                $item:item
+            ) => {
+                compile_error!(#unsupported);
+            };
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_index = $index:tt]
+                $item:item
+            ) => {
+                compile_error!(#unsupported);
+            };
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_order = $order:tt]
+                #![linkme_order_key = $key:tt]
+                $(#[$attr:meta])*
+                $vis:vis static $ident:ident : $ty:ty = $expr:expr;
+            ) => {
+                #ordered
+            };
+            (
+                $(#[$attr:meta])*
+                $vis:vis static $ident:ident : $ty:ty = $expr:expr;
+            ) => {
+                #unordered
+            };
+        }
+    }
+}
+
+// Marker symbol whose name is unique to one key of one kind within this slice,
+// such as `linkme.irq.VECTORS.3`, so that the linker reports two elements
+// claiming the same key as a duplicate symbol. Keys of slices with different
+// names do not collide.
+fn unique(ident_str: &str) -> TokenStream {
+    quote! {
+        (#![linkme_unique = $kind:tt, $key:tt]) => {
+            const _: () = {
+                #[export_name = concat!("linkme.", $kind, ".", #ident_str, ".", $key)]
+                static LINKME_UNIQUE: u8 = 0;
+            };
+        };
+    }
+}
+
+// The linkme path as seen from wherever the exported macro is invoked.
+fn macro_path(path: &Path) -> TokenStream {
+    let mut segments = path.segments.iter();
+    match segments.next() {
+        Some(first) if path.leading_colon.is_none() && first.ident == "crate" => {
+            let rest = segments.map(|segment| quote!(:: #segment));
+            quote!($crate #(#rest)*)
+        }
+        _ => quote!(#path),
+    }
+}
@@ -1,8 +1,8 @@
 use crate::attr;
-use proc_macro2::{Span, TokenStream, TokenTree};
+use proc_macro2::{Group, Literal, Span, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
 use std::iter::FromIterator;
-use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::parse::{Error, Parse, ParseStream, Parser, Result};
 use syn::punctuated::Punctuated;
 use syn::{
     braced, parenthesized, parse_quote, Abi, Attribute, BareFnArg, BoundLifetimes, GenericParam,
@@ -54,10 +54,12 @@ impl Parse for Element {
             let unsafety: Option<Token![unsafe]> = input.parse()?;
             let abi: Option<Abi> = input.parse()?;
             let fn_token: Token![fn] = input.parse().map_err(|_| {
-                Error::new_spanned(
-                    item.token_stream(),
-                    "distributed element must be either static or function item",
-                )
+                let msg = if constness.is_some() {
+                    "distributed element must be either static or function item; to register an associated const, also put #[distributed_slice] on its impl block"
+                } else {
+                    "distributed element must be either static or function item"
+                };
+                Error::new_spanned(item.token_stream(), msg)
             })?;
             let ident: Ident = input.parse()?;
             let generics: Generics = input.parse()?;
@@ -192,17 +194,99 @@ impl Parse for Element {
     }
 }
 
-pub fn expand(path: Path, pos: impl Into<Option<usize>>, input: Element) -> TokenStream {
+impl Element {
+    // Element for an associated const or fn of an impl block, referring to it
+    // through `qself` such as `<MyType as Plugin>`. The item itself stays in
+    // the impl block.
+    pub fn parse_associated(
+        item: TokenStream,
+        qself: &TokenStream,
+        self_ty: &Type,
+    ) -> Result<Self> {
+        let parse = |input: ParseStream| {
+            let attrs = input.call(Attribute::parse_outer)?;
+            input.parse::<Visibility>()?;
+            if !input.peek(Token![const]) || input.peek2(Token![fn]) {
+                let mut element: Element = input.parse()?;
+                element.orig_item = None;
+                return Ok(element);
+            }
+            input.parse::<Token![const]>()?;
+            let ident: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            let start_span = input.span();
+            let ty: Type = input.parse()?;
+            let end_span = quote!(#ty).into_iter().last().unwrap().span();
+            input.parse::<Token![=]>()?;
+            input.parse::<TokenStream>()?;
+            Ok(Element {
+                attrs,
+                vis: Visibility::Inherited,
+                ident: format_ident!("_LINKME_ELEMENT_{}", ident),
+                ty,
+                expr: quote!(#ident),
+                orig_item: None,
+                start_span,
+                end_span,
+            })
+        };
+        let mut element = parse.parse2(item)?;
+        let expr = element.expr;
+        element.expr = quote!(#qself::#expr);
+        let ty = &element.ty;
+        element.ty = syn::parse2(replace_self(quote!(#ty), self_ty))?;
+        Ok(element)
+    }
+}
+
+// The element is a static outside of the impl block, where `Self` means
+// nothing.
+pub fn replace_self(tokens: TokenStream, self_ty: &Type) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) if ident == "Self" => quote!(#self_ty),
+            TokenTree::Group(group) => {
+                let stream = replace_self(group.stream(), self_ty);
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                TokenStream::from(TokenTree::Group(replaced))
+            }
+            tt => TokenStream::from(tt),
+        })
+        .collect()
+}
+
+pub fn expand(
+    path: Path,
+    pos: impl Into<Option<usize>>,
+    index: impl Into<Option<usize>>,
+    order: impl Into<Option<i32>>,
+    input: Element,
+) -> TokenStream {
     let pos = pos.into();
-    do_expand(path, pos, input)
+    let index = index.into();
+    let order = order.into();
+    do_expand(path, pos, index, order, false, input)
 }
 
-fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
+pub fn expand_map(path: Path, input: Element) -> TokenStream {
+    do_expand(path, None, None, None, true, input)
+}
+
+fn do_expand(
+    path: Path,
+    pos: Option<usize>,
+    index: Option<usize>,
+    order: Option<i32>,
+    map: bool,
+    input: Element,
+) -> TokenStream {
     let mut attrs = input.attrs;
     let vis = input.vis;
     let ident = input.ident;
-    let ty = input.ty;
-    let expr = input.expr;
+    let mut ty = input.ty;
+    let mut expr = input.expr;
     let orig_item = input.orig_item;
 
     let linkme_path = match attr::linkme_path(&mut attrs) {
@@ -212,6 +296,41 @@ fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
 
     let sort_key = pos.into_iter().map(|pos| format!("{:04}", pos));
 
+    // Orders are biased so that an unsigned comparison of keys, or string
+    // comparison of their hex form as in Windows section names, agrees with
+    // the signed order.
+    #[allow(clippy::cast_sign_loss)]
+    let order_key = order.map(|order| (order as u32) ^ 0x8000_0000);
+    let wasm_sort_key = match (pos, order_key) {
+        (Some(pos), _) => format!("{:04}", pos),
+        (None, Some(key)) => format!("{:08X}", key),
+        (None, None) => String::new(),
+    };
+    let order_hex = order_key.map(|key| format!("{:08X}", key)).into_iter();
+    let order_key = order_key.map(Literal::u32_unsuffixed).into_iter();
+
+    // Elements of a dense table are wrapped in a Slot carrying their index,
+    // and the slice's macro adds a marker symbol that collides when two
+    // elements claim the same index.
+    let index = index.map(|index| {
+        let index = Literal::usize_unsuffixed(index);
+        ty = parse_quote!(#linkme_path::dense::Slot<#ty>);
+        expr = quote! {
+            #linkme_path::dense::Slot {
+                index: #index,
+                value: { #expr },
+            }
+        };
+        index
+    });
+    let index = index.into_iter();
+
+    let (container, container_ref) = if map {
+        (quote!(#linkme_path::DistributedMap), quote!(&#path))
+    } else {
+        (quote!(#linkme_path::DistributedSlice), quote!(#path))
+    };
+
     let new = quote_spanned!(input.start_span=> __new);
     let uninit = quote_spanned!(input.end_span=> #new());
 
@@ -221,13 +340,36 @@ fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
                 #![linkme_macro = #path]
                 #![linkme_sort_key = #sort_key]
             )*
+            #(
+                #![linkme_macro = #path]
+                #![linkme_index = #index]
+            )*
+            #(
+                #![linkme_macro = #path]
+                #![linkme_order = #order_key]
+                #![linkme_order_key = #order_hex]
+            )*
             #(#attrs)*
             #vis static #ident : #ty = {
                 unsafe fn __typecheck(_: #linkme_path::private::Void) {
                     let #new = #linkme_path::private::value::<#ty>;
-                    #linkme_path::DistributedSlice::private_typecheck(#path, #uninit)
+                    #container::private_typecheck(#container_ref, #uninit)
                 }
 
+                #[cfg(target_family = "wasm")]
+                static LINKME_NODE: #linkme_path::private::wasm::Node<#ty> =
+                    #linkme_path::private::wasm::Node::new(&#ident, #wasm_sort_key);
+
+                #[cfg(target_family = "wasm")]
+                extern "C" fn linkme_register() {
+                    unsafe { #container::private_register(#container_ref, &LINKME_NODE) }
+                }
+
+                #[cfg(target_family = "wasm")]
+                #[used]
+                #[link_section = ".init_array"]
+                static LINKME_REGISTER: extern "C" fn() = linkme_register;
+
                 #expr
             };
         }
@@ -0,0 +1,81 @@
+use crate::{attr, declaration};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::{Attribute, Ident, Token, Visibility};
+
+pub struct Event {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    item: TokenStream,
+}
+
+impl Parse for Event {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let item = input.cursor();
+        input.parse::<Visibility>()?;
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![struct]) {
+            input.parse::<Token![struct]>()?;
+        } else if lookahead.peek(Token![enum]) {
+            input.parse::<Token![enum]>()?;
+        } else {
+            return Err(Error::new_spanned(
+                item.token_stream(),
+                "event must be a struct or enum",
+            ));
+        }
+        let ident: Ident = input.parse()?;
+        if let Some(lt_token) = input.parse::<Option<Token![<]>>()? {
+            return Err(Error::new_spanned(
+                lt_token,
+                "generic event type is not supported",
+            ));
+        }
+        input.parse::<TokenStream>()?;
+
+        Ok(Event {
+            attrs,
+            ident,
+            item: item.token_stream(),
+        })
+    }
+}
+
+// Name of the distributed slice holding the subscribers of an event type,
+// which is also the name of its linker section.
+pub fn subscribers(event: &Ident) -> Ident {
+    format_ident!("_LINKME_EVENT_{}", event)
+}
+
+pub fn expand(input: Event) -> TokenStream {
+    let mut attrs = input.attrs;
+    let ident = input.ident;
+    let item = input.item;
+
+    let linkme_path = match attr::linkme_path(&mut attrs) {
+        Ok(path) => path,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let subscribers = subscribers(&ident);
+    let declaration = declaration::expand(quote! {
+        #[linkme(crate = #linkme_path)]
+        #[allow(non_upper_case_globals)]
+        static #subscribers: [#linkme_path::event::Subscriber<#ident>] = [..];
+    });
+
+    quote! {
+        #(#attrs)*
+        #item
+
+        #declaration
+
+        impl #linkme_path::event::Event for #ident {
+            fn private_subscribers() -> #linkme_path::DistributedSlice<[#linkme_path::event::Subscriber<Self>]> {
+                #subscribers
+            }
+        }
+    }
+}
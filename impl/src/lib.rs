@@ -3,14 +3,17 @@
 extern crate proc_macro;
 
 mod args;
+mod assoc;
 mod attr;
 mod declaration;
 mod derive;
 mod element;
+mod event;
 mod linker;
+mod subscribe;
 
 use proc_macro::TokenStream;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, Path};
 
 use crate::args::Args;
 
@@ -19,14 +22,59 @@ pub fn distributed_slice(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as Args);
 
     let expanded = match args {
-        Args::None => declaration::expand(parse_macro_input!(input)),
-        Args::Path(path) => element::expand(path, None, parse_macro_input!(input)),
-        Args::PathPos(path, pos) => element::expand(path, pos, parse_macro_input!(input)),
+        Args::None => {
+            let input = proc_macro2::TokenStream::from(input);
+            if assoc::is_impl(&input) {
+                assoc::expand(input)
+            } else {
+                declaration::expand(input)
+            }
+        }
+        Args::Path(path) => element::expand(path, None, None, None, parse_macro_input!(input)),
+        Args::PathPos(path, pos) => {
+            element::expand(path, pos, None, None, parse_macro_input!(input))
+        }
+        Args::PathIndex(path, index) => {
+            element::expand(path, None, index, None, parse_macro_input!(input))
+        }
+        Args::PathOrder(path, order) => {
+            element::expand(path, None, None, order, parse_macro_input!(input))
+        }
     };
 
     TokenStream::from(expanded)
 }
 
+#[proc_macro_attribute]
+pub fn distributed_map(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as Args);
+
+    let expanded = match args {
+        Args::None => declaration::expand_map(proc_macro2::TokenStream::from(input)),
+        Args::Path(path) => element::expand_map(path, parse_macro_input!(input)),
+        Args::PathPos(path, _) | Args::PathIndex(path, _) | Args::PathOrder(path, _) => {
+            let msg = "expected `#[distributed_map(path::to::MAP)]`";
+            syn::Error::new_spanned(path, msg).to_compile_error()
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_attribute]
+pub fn event(args: TokenStream, input: TokenStream) -> TokenStream {
+    parse_macro_input!(args as syn::parse::Nothing);
+    let expanded = event::expand(parse_macro_input!(input));
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_attribute]
+pub fn subscribe(args: TokenStream, input: TokenStream) -> TokenStream {
+    let event = parse_macro_input!(args as Path);
+    let expanded = subscribe::expand(event, parse_macro_input!(input));
+    TokenStream::from(expanded)
+}
+
 #[doc(hidden)]
 #[proc_macro_derive(link_section_macro, attributes(linkme_ident, linkme_macro))]
 pub fn link_section_macro(input: TokenStream) -> TokenStream {
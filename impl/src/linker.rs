@@ -34,15 +34,43 @@ pub mod macos {
     use syn::Ident;
 
     pub fn section(ident: &Ident) -> String {
-        format!("__DATA,__{}", ident)
+        format!("__DATA,{}", name(ident))
     }
 
     pub fn section_start(ident: &Ident) -> String {
-        format!("\x01section$start$__DATA$__{}", ident)
+        format!("\x01section$start$__DATA${}", name(ident))
     }
 
     pub fn section_stop(ident: &Ident) -> String {
-        format!("\x01section$end$__DATA$__{}", ident)
+        format!("\x01section$end$__DATA${}", name(ident))
+    }
+
+    // Mach-O section names are at most 16 bytes. A longer name is replaced by
+    // one derived from a hash of the identifier, which comes out the same in
+    // every crate that refers to the slice.
+    fn name(ident: &Ident) -> String {
+        let name = format!("__{}", ident);
+        if name.len() <= 16 {
+            return name;
+        }
+        format!("__linkme_{}", hash(&ident.to_string()))
+    }
+
+    // FNV-1a, in 7 base-36 digits to fill out the 16 bytes.
+    fn hash(string: &str) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in string.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        let mut digits = String::new();
+        for _ in 0..7 {
+            digits.push(char::from(
+                b"0123456789abcdefghijklmnopqrstuvwxyz"[(hash % 36) as usize],
+            ));
+            hash /= 36;
+        }
+        digits
     }
 }
 
@@ -0,0 +1,113 @@
+use crate::{attr, event, linker};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::{Attribute, Ident, Path, Token, Visibility};
+
+pub struct Subscriber {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    item: TokenStream,
+}
+
+impl Parse for Subscriber {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let item = input.cursor();
+        input.parse::<Visibility>()?;
+        input.parse::<Option<Token![const]>>()?;
+        if let Some(asyncness) = input.parse::<Option<Token![async]>>()? {
+            return Err(Error::new_spanned(
+                asyncness,
+                "async fn subscriber is not supported",
+            ));
+        }
+        if let Some(unsafety) = input.parse::<Option<Token![unsafe]>>()? {
+            return Err(Error::new_spanned(
+                unsafety,
+                "unsafe fn subscriber is not supported",
+            ));
+        }
+        input.parse::<Token![fn]>().map_err(|_| {
+            Error::new_spanned(item.token_stream(), "subscriber must be a function item")
+        })?;
+        let ident: Ident = input.parse()?;
+        input.parse::<TokenStream>()?;
+
+        Ok(Subscriber {
+            attrs,
+            ident,
+            item: item.token_stream(),
+        })
+    }
+}
+
+pub fn expand(event: Path, input: Subscriber) -> TokenStream {
+    let mut attrs = input.attrs;
+    let ident = input.ident;
+    let item = input.item;
+
+    let linkme_path = match attr::linkme_path(&mut attrs) {
+        Ok(path) => path,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    // The subscriber goes straight into the linker section of the event's
+    // slice, whose name follows from the name of the event type, so that
+    // the event type may be named by any path that reaches it.
+    let event_ident = match event.segments.last() {
+        Some(segment) if segment.arguments.is_empty() => &segment.ident,
+        _ => {
+            let msg = "expected the path of an event type declared with #[linkme::event]";
+            return Error::new_spanned(event, msg).to_compile_error();
+        }
+    };
+    let section = event::subscribers(event_ident);
+    let linux_section = linker::linux::section(&section);
+    let macos_section = linker::macos::section(&section);
+    let windows_section = linker::windows::section(&section);
+    let illumos_section = linker::illumos::section(&section);
+    let freebsd_section = linker::freebsd::section(&section);
+
+    let subscriber = format_ident!("_LINKME_SUBSCRIBER_{}", ident);
+
+    quote! {
+        #(#attrs)*
+        #item
+
+        #[used]
+        #[cfg_attr(any(target_os = "none", target_os = "linux"), link_section = #linux_section)]
+        #[cfg_attr(target_os = "macos", link_section = #macos_section)]
+        #[cfg_attr(target_os = "windows", link_section = #windows_section)]
+        #[cfg_attr(target_os = "illumos", link_section = #illumos_section)]
+        #[cfg_attr(target_os = "freebsd", link_section = #freebsd_section)]
+        #[allow(non_upper_case_globals)]
+        static #subscriber: #linkme_path::event::Subscriber<#event> = {
+            #[cfg(target_family = "wasm")]
+            static LINKME_NODE: #linkme_path::private::wasm::Node<#linkme_path::event::Subscriber<#event>> =
+                #linkme_path::private::wasm::Node::new(&#subscriber, "");
+
+            #[cfg(target_family = "wasm")]
+            extern "C" fn linkme_register() {
+                let subscribers = <#event as #linkme_path::event::Event>::private_subscribers();
+                unsafe { subscribers.private_register(&LINKME_NODE) }
+            }
+
+            #[cfg(target_family = "wasm")]
+            #[used]
+            #[link_section = ".init_array"]
+            static LINKME_REGISTER: extern "C" fn() = linkme_register;
+
+            // Only wasm names the Event impl otherwise.
+            const _: fn() = || {
+                fn assert_event<E: #linkme_path::event::Event>() {}
+                assert_event::<#event>();
+            };
+
+            #linkme_path::event::Subscriber {
+                event: #linkme_path::private::TypeId::of::<#event>,
+                handler: #ident,
+            }
+        };
+    }
+}
@@ -0,0 +1,91 @@
+//! Named binary assets embedded next to the code that uses them.
+//!
+//! Any crate may register an [`Asset`] into a distributed slice owned by the
+//! application, typically built with the [`asset!`] macro around
+//! `include_bytes!`. Icons, templates and shaders can then live in the crate
+//! that needs them rather than in one central asset directory.
+//!
+//! ```
+//! use linkme::asset::{self, Asset};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static ASSETS: [Asset] = [..];
+//!
+//! #[distributed_slice(ASSETS)]
+//! static LICENSE: Asset = linkme::asset!("license.txt", "../LICENSE-MIT");
+//!
+//! fn main() {
+//!     duplicate::check(&ASSETS, |asset| asset.name).unwrap();
+//!
+//!     let license = asset::get(&ASSETS, "license.txt").unwrap();
+//!     assert!(license.starts_with(b"Permission is hereby granted"));
+//! }
+//! ```
+//!
+//! [`asset!`]: crate::asset!
+
+/// Named blob of bytes.
+pub struct Asset {
+    pub name: &'static str,
+    pub bytes: &'static [u8],
+}
+
+/// Builds an [`Asset`] from a name and the path of a file to embed.
+///
+/// The path is resolved relative to the file containing the macro call, like
+/// `include_bytes!`.
+///
+/// ```
+/// # use linkme::asset::Asset;
+/// # use linkme::distributed_slice;
+/// #
+/// # #[distributed_slice]
+/// # pub static ASSETS: [Asset] = [..];
+/// #
+/// #[distributed_slice(ASSETS)]
+/// static LICENSE: Asset = linkme::asset!("license.txt", "../LICENSE-MIT");
+/// ```
+///
+/// When the name is a string literal, appending `unique(SLICE)` with the slice
+/// the asset is registered into additionally makes it a build error for two
+/// assets of that slice built this way to share a name anywhere in the final
+/// binary. Duplicates within one crate are rejected by the compiler, and
+/// duplicates across crates by the linker as a duplicate symbol named
+/// `linkme.asset.<SLICE>.<name>`. Assets of different slices may share names.
+/// This requires rustc 1.54 or newer.
+///
+/// ```
+/// # use linkme::asset::Asset;
+/// # use linkme::distributed_slice;
+/// #
+/// # #[distributed_slice]
+/// # pub static ASSETS: [Asset] = [..];
+/// #
+/// #[distributed_slice(ASSETS)]
+/// static LICENSE: Asset = linkme::asset!("license.txt", "../LICENSE-MIT", unique(ASSETS));
+/// ```
+#[macro_export]
+macro_rules! asset {
+    ($name:expr, $path:expr, unique($slice:path)) => {{
+        $slice! {
+            #![linkme_unique = "asset", $name]
+        }
+
+        $crate::asset!($name, $path)
+    }};
+    ($name:expr, $path:expr) => {
+        $crate::asset::Asset {
+            name: $name,
+            bytes: include_bytes!($path),
+        }
+    };
+}
+
+/// Finds the contents of the asset with the given name.
+pub fn get(assets: &[Asset], name: &str) -> Option<&'static [u8]> {
+    assets
+        .iter()
+        .find(|asset| asset.name == name)
+        .map(|asset| asset.bytes)
+}
@@ -0,0 +1,112 @@
+//! Byte strings from many crates viewed as one concatenated blob.
+//!
+//! Each registration contributes a named [`Blob`] of bytes. The collection is
+//! read as their logical concatenation, in lexicographic order of name so that
+//! offsets do not depend on link order, which suits firmware configuration
+//! images, small embedded filesystems and string pools. The linker places
+//! each contributor's bytes wherever it likes, so [`read`] assembles the
+//! concatenated view on demand, and [`locate`] gives each contributor's offset
+//! within it.
+//!
+//! ```
+//! use linkme::blob::{self, Blob};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static CONFIG: [Blob] = [..];
+//!
+//! #[distributed_slice(CONFIG)]
+//! static NET: Blob = Blob {
+//!     name: "net",
+//!     bytes: b"mtu=1500\n",
+//! };
+//!
+//! #[distributed_slice(CONFIG)]
+//! static CLOCK: Blob = Blob {
+//!     name: "clock",
+//!     bytes: b"hz=1000\n",
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&CONFIG, |blob| blob.name).unwrap();
+//!
+//!     let mut image = [0; 32];
+//!     let n = blob::read(&CONFIG, 0, &mut image);
+//!     assert_eq!(&image[..n], b"hz=1000\nmtu=1500\n");
+//!     assert_eq!(blob::locate(&CONFIG, "net"), Some(8..17));
+//! }
+//! ```
+
+use crate::order::{self, ByKey};
+use core::cmp;
+use core::ops::Range;
+
+/// Named contribution to the concatenated blob.
+pub struct Blob {
+    pub name: &'static str,
+    pub bytes: &'static [u8],
+}
+
+/// Total length of the concatenation.
+pub fn len(blobs: &[Blob]) -> usize {
+    blobs.iter().map(|blob| blob.bytes.len()).sum()
+}
+
+/// Contributions in name order, each with its offset in the concatenation.
+/// Contributions with the same name keep slice order.
+pub fn parts(blobs: &[Blob]) -> Parts<'_> {
+    Parts {
+        blobs: order::by_key(blobs, name),
+        offset: 0,
+    }
+}
+
+/// Byte range occupied by the named contribution.
+pub fn locate(blobs: &[Blob], name: &str) -> Option<Range<usize>> {
+    parts(blobs)
+        .find(|(_, blob)| blob.name == name)
+        .map(|(offset, blob)| offset..offset + blob.bytes.len())
+}
+
+/// Copies bytes of the concatenation starting at `offset` into `buf`,
+/// returning how many were copied. This is less than `buf.len()` only when
+/// the end of the concatenation is reached.
+pub fn read(blobs: &[Blob], offset: usize, buf: &mut [u8]) -> usize {
+    let mut copied = 0;
+    for (start, blob) in parts(blobs) {
+        let end = start + blob.bytes.len();
+        let from = offset + copied;
+        if end <= from {
+            continue;
+        }
+        let bytes = &blob.bytes[from - start..];
+        let n = cmp::min(bytes.len(), buf.len() - copied);
+        buf[copied..copied + n].copy_from_slice(&bytes[..n]);
+        copied += n;
+        if copied == buf.len() {
+            break;
+        }
+    }
+    copied
+}
+
+fn name(blob: &Blob) -> &'static str {
+    blob.name
+}
+
+/// Iterator returned by [`parts`].
+pub struct Parts<'a> {
+    blobs: ByKey<'a, Blob, &'static str, fn(&Blob) -> &'static str>,
+    offset: usize,
+}
+
+impl<'a> Iterator for Parts<'a> {
+    type Item = (usize, &'a Blob);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let blob = self.blobs.next()?;
+        let offset = self.offset;
+        self.offset += blob.bytes.len();
+        Some((offset, blob))
+    }
+}
@@ -0,0 +1,105 @@
+//! Bootloader stage hooks injected by board-support crates.
+//!
+//! A bootloader exposes three hook points, each backed by its own link
+//! section: [`PRE_RAM_INIT`] runs before memory is trained, [`POST_RAM_INIT`]
+//! once RAM is usable, and [`PRE_JUMP`] immediately before control is handed to
+//! the next stage. Board-support crates register [`Hook`]s at the points they
+//! need, and the bootloader calls [`run`] as it reaches each stage.
+//!
+//! Hooks within a stage run in a strict order: ascending `order`, then by
+//! name, never depending on link order. Nothing here allocates, so hooks can
+//! run before a heap exists.
+//!
+//! ```
+//! use linkme::boot::{self, Hook, Stage, POST_RAM_INIT, PRE_RAM_INIT};
+//! use linkme::distributed_slice;
+//!
+//! // In the board-support crate.
+//! #[distributed_slice(PRE_RAM_INIT)]
+//! static CLOCKS: Hook = Hook {
+//!     name: "clocks",
+//!     order: 0,
+//!     run: || { /* configure PLLs */ },
+//! };
+//!
+//! #[distributed_slice(POST_RAM_INIT)]
+//! static RELOCATE: Hook = Hook {
+//!     name: "relocate",
+//!     order: 10,
+//!     run: || { /* copy .data into RAM */ },
+//! };
+//!
+//! fn main() {
+//!     boot::run(Stage::PreRamInit);
+//!     /* train DRAM */
+//!     boot::run(Stage::PostRamInit);
+//!     /* load the kernel */
+//!     boot::run(Stage::PreJump);
+//! }
+//! ```
+
+use crate::{distributed_slice, order};
+
+/// Hooks run before RAM is initialized, with only on-chip memory available.
+///
+/// These have the type `DistributedSlice<[Hook]>`.
+pub use self::LINKME_PRERAM as PRE_RAM_INIT;
+
+/// Hooks run once RAM is initialized.
+///
+/// These have the type `DistributedSlice<[Hook]>`.
+pub use self::LINKME_POSTRAM as POST_RAM_INIT;
+
+/// Hooks run immediately before jumping to the next stage.
+///
+/// These have the type `DistributedSlice<[Hook]>`.
+pub use self::LINKME_PREJUMP as PRE_JUMP;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_PRERAM: [Hook] = [..];
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_POSTRAM: [Hook] = [..];
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_PREJUMP: [Hook] = [..];
+
+/// Code injected at one stage of the boot.
+pub struct Hook {
+    pub name: &'static str,
+    /// Position within the stage; lower runs first.
+    pub order: i32,
+    pub run: fn(),
+}
+
+/// Stage of the boot at which hooks run, in the order the stages happen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    PreRamInit,
+    PostRamInit,
+    PreJump,
+}
+
+impl Stage {
+    /// Hooks registered at this stage, in slice order.
+    pub fn hooks(self) -> &'static [Hook] {
+        match self {
+            Stage::PreRamInit => &LINKME_PRERAM,
+            Stage::PostRamInit => &LINKME_POSTRAM,
+            Stage::PreJump => &LINKME_PREJUMP,
+        }
+    }
+}
+
+/// Runs every hook registered at the given stage, in order.
+pub fn run(stage: Stage) {
+    for hook in order::by_key(stage.hooks(), |hook| (hook.order, hook.name)) {
+        (hook.run)();
+    }
+}
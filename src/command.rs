@@ -0,0 +1,147 @@
+//! Named commands dispatched through a lazily built index.
+//!
+//! Registering `(name, handler)` pairs into a distributed slice and looking
+//! them up by name is the most common use of linkme, and every such program
+//! ends up writing the same wrapper. [`CommandRegistry`] is that wrapper: it
+//! sits in a static next to the slice, sorts the commands into an index the
+//! first time it is used, and dispatches by name with errors for unknown and
+//! duplicated commands.
+//!
+//! Requires the `std` feature.
+//!
+//! ```
+//! use linkme::command::{Command, CommandRegistry};
+//! use linkme::distributed_slice;
+//!
+//! #[distributed_slice]
+//! pub static COMMANDS: [Command<i32>] = [..];
+//!
+//! pub static REGISTRY: CommandRegistry<i32> = CommandRegistry::new(&COMMANDS);
+//!
+//! // In any crate.
+//! #[distributed_slice(COMMANDS)]
+//! static ECHO: Command<i32> = Command {
+//!     name: "echo",
+//!     handler: |args| {
+//!         println!("{}", args.join(" "));
+//!         0
+//!     },
+//! };
+//!
+//! fn main() {
+//!     let status = REGISTRY.dispatch("echo", &["hello", "world"]).unwrap();
+//!     assert_eq!(status, 0);
+//!     assert!(REGISTRY.dispatch("ehco", &[]).is_err());
+//! }
+//! ```
+
+use crate::DistributedSlice;
+use std::cell::UnsafeCell;
+use std::fmt::{self, Display};
+use std::sync::Once;
+use std::vec::Vec;
+
+/// Command registered under a name, whose handler returns `T`.
+pub struct Command<T: 'static = ()> {
+    pub name: &'static str,
+    /// Runs the command with the arguments following its name.
+    pub handler: fn(&[&str]) -> T,
+}
+
+/// Dispatcher over a distributed slice of [`Command`]s.
+pub struct CommandRegistry<T: 'static = ()> {
+    commands: &'static DistributedSlice<[Command<T>]>,
+    once: Once,
+    index: UnsafeCell<Option<Result<Vec<usize>, &'static str>>>,
+}
+
+// The index is written once inside `Once::call_once` and only read after.
+unsafe impl<T> Sync for CommandRegistry<T> {}
+
+impl<T> CommandRegistry<T> {
+    /// Creates a registry over the given slice, for use as a static.
+    pub const fn new(commands: &'static DistributedSlice<[Command<T>]>) -> Self {
+        CommandRegistry {
+            commands,
+            once: Once::new(),
+            index: UnsafeCell::new(None),
+        }
+    }
+
+    /// Runs the command registered under `name`.
+    pub fn dispatch<'a>(&self, name: &'a str, args: &[&str]) -> Result<T, DispatchError<'a>> {
+        match self.get(name)? {
+            Some(command) => Ok((command.handler)(args)),
+            None => Err(DispatchError::Unknown { name }),
+        }
+    }
+
+    /// Finds the command registered under `name`.
+    ///
+    /// Fails only if some command name, not necessarily this one, is
+    /// registered twice.
+    pub fn get(&self, name: &str) -> Result<Option<&'static Command<T>>, DispatchError<'static>> {
+        let commands = self.commands.static_slice();
+        let index = self.index()?;
+        Ok(index
+            .binary_search_by(|&i| commands[i].name.cmp(name))
+            .ok()
+            .map(|found| &commands[index[found]]))
+    }
+
+    /// Names of every command, in sorted order.
+    pub fn names(&self) -> Result<Vec<&'static str>, DispatchError<'static>> {
+        let commands = self.commands.static_slice();
+        Ok(self.index()?.iter().map(|&i| commands[i].name).collect())
+    }
+
+    /// Builds the index now rather than on first dispatch, reporting whether
+    /// any command name is registered twice.
+    pub fn validate(&self) -> Result<(), DispatchError<'static>> {
+        self.index().map(drop)
+    }
+
+    fn index(&self) -> Result<&[usize], DispatchError<'static>> {
+        self.once.call_once(|| {
+            let commands = self.commands.static_slice();
+            let mut index: Vec<usize> = (0..commands.len()).collect();
+            index.sort_by_key(|&i| commands[i].name);
+            let duplicate = index
+                .windows(2)
+                .find(|pair| commands[pair[0]].name == commands[pair[1]].name);
+            let index = match duplicate {
+                Some(pair) => Err(commands[pair[0]].name),
+                None => Ok(index),
+            };
+            unsafe { *self.index.get() = Some(index) };
+        });
+        match unsafe { &*self.index.get() } {
+            Some(Ok(index)) => Ok(index),
+            Some(Err(name)) => Err(DispatchError::Duplicate { name }),
+            None => unreachable!(),
+        }
+    }
+}
+
+/// Error returned by [`CommandRegistry::dispatch`].
+#[derive(Debug, PartialEq)]
+pub enum DispatchError<'a> {
+    /// No command is registered under the name.
+    Unknown { name: &'a str },
+    /// Two commands are registered under this name, so the registry cannot
+    /// dispatch any command reliably.
+    Duplicate { name: &'static str },
+}
+
+impl Display for DispatchError<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DispatchError::Unknown { name } => write!(formatter, "unknown command {name:?}"),
+            DispatchError::Duplicate { name } => {
+                write!(formatter, "command {name:?} is registered twice")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DispatchError<'_> {}
@@ -0,0 +1,71 @@
+//! Shell completion providers contributed by any crate in the binary.
+//!
+//! In a CLI whose subcommands live in separate crates, no single crate knows
+//! the full command tree, so no single crate can write the completion script.
+//! Instead each subcommand registers a [`Completion`] into a distributed slice
+//! owned by the application, and the application stitches the fragments
+//! together with [`merge`].
+//!
+//! ```
+//! use core::fmt::{self, Write};
+//! use linkme::completion::{self, Completion, Shell};
+//! use linkme::distributed_slice;
+//!
+//! #[distributed_slice]
+//! pub static COMPLETIONS: [Completion] = [..];
+//!
+//! #[distributed_slice(COMPLETIONS)]
+//! static REMOTE_ADD: Completion = Completion {
+//!     path: &["remote", "add"],
+//!     generate: remote_add,
+//! };
+//!
+//! fn remote_add(shell: Shell, out: &mut dyn Write) -> fmt::Result {
+//!     match shell {
+//!         Shell::Fish => writeln!(out, "complete -c app -n '__fish_seen_subcommand_from add' -l fetch"),
+//!         _ => Ok(()),
+//!     }
+//! }
+//!
+//! fn main() -> fmt::Result {
+//!     let mut script = String::new();
+//!     completion::merge(&COMPLETIONS, Shell::Fish, &mut script)?;
+//!     print!("{}", script);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::order;
+use core::fmt::{self, Write};
+
+/// Shell for which a completion script is being generated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+    Elvish,
+}
+
+/// Completion generator for one command in the command tree.
+pub struct Completion {
+    /// Words leading to the command, not including the binary name. The
+    /// top-level command is the empty path.
+    pub path: &'static [&'static str],
+    /// Writes this command's fragment of the completion script.
+    pub generate: fn(Shell, &mut dyn Write) -> fmt::Result,
+}
+
+/// Writes the fragments of every registered command into one script.
+///
+/// Fragments are emitted in lexicographic order of their command path, so a
+/// parent command always comes before its subcommands and the output is the
+/// same regardless of link order. Registrations with identical paths are
+/// emitted in the order they appear in the slice.
+pub fn merge(completions: &[Completion], shell: Shell, out: &mut dyn Write) -> fmt::Result {
+    for completion in order::by_key(completions, |completion| completion.path) {
+        (completion.generate)(shell, out)?;
+    }
+    Ok(())
+}
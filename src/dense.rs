@@ -0,0 +1,179 @@
+//! Dense tables whose elements claim a fixed index, such as syscall tables.
+//!
+//! A distributed slice of [`Slot<T>`] holds elements registered with an
+//! explicit index:
+//!
+//! ```
+//! # type SyscallFn = fn(usize) -> isize;
+//! #
+//! # #[linkme::distributed_slice]
+//! # pub static SYSCALLS: [linkme::dense::Slot<SyscallFn>] = [..];
+//! #
+//! #[linkme::distributed_slice(SYSCALLS, index = 17)]
+//! fn sys_getpid(_arg: usize) -> isize {
+//!     /* ... */
+//! #   1
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! The element is wrapped in a `Slot` recording its index, and two elements
+//! claiming the same index of the same slice anywhere in the final binary
+//! fail to build: within one crate the compiler rejects them, and across
+//! crates the linker reports a duplicate symbol named
+//! `linkme.index.<SLICE>.<index>`. Registering with an index requires rustc
+//! 1.54 or newer.
+//!
+//! Because of the wrapping, a static registered with an index has type
+//! `Slot<T>` rather than the `T` written in its declaration, and its value is
+//! read through the `value` field. A function registered with an index stays
+//! an ordinary function, with its slot in a hidden static.
+//!
+//! ```
+//! # use linkme::dense::Slot;
+//! # use linkme::distributed_slice;
+//! #
+//! # type SyscallFn = fn(usize) -> isize;
+//! #
+//! # #[distributed_slice]
+//! # pub static SYSCALLS: [Slot<SyscallFn>] = [..];
+//! #
+//! #[distributed_slice(SYSCALLS, index = 24)]
+//! static SYS_YIELD: SyscallFn = |_arg| 0;
+//!
+//! fn main() {
+//!     let slot: &Slot<SyscallFn> = &SYS_YIELD;
+//!     assert_eq!(slot.index, 24);
+//!     assert_eq!((SYS_YIELD.value)(0), 0);
+//! }
+//! ```
+//!
+//! Linkers have no way to leave a hole in a section, so the slots are not laid
+//! out by index in the binary. Instead [`populate`] writes each element into
+//! its slot of a table the caller has filled with a default handler, giving
+//! a table that can be indexed directly by syscall number.
+//!
+//! ```
+//! use linkme::dense::{self, Slot};
+//! use linkme::distributed_slice;
+//!
+//! type SyscallFn = fn(usize) -> isize;
+//!
+//! #[distributed_slice]
+//! pub static SYSCALLS: [Slot<SyscallFn>] = [..];
+//!
+//! #[distributed_slice(SYSCALLS, index = 1)]
+//! fn sys_exit(code: usize) -> isize {
+//!     /* ... */
+//! #   code as isize
+//! }
+//!
+//! fn sys_enosys(_arg: usize) -> isize {
+//!     -38
+//! }
+//!
+//! fn main() {
+//!     let mut table: [SyscallFn; 64] = [sys_enosys; 64];
+//!     dense::populate(&SYSCALLS, &mut table).unwrap();
+//!     assert_eq!(table[0](0), -38);
+//!     assert_eq!(table[1](7), 7);
+//! }
+//! ```
+
+use crate::duplicate;
+use core::fmt::{self, Display};
+
+/// Element of a dense table together with the index it claims.
+///
+/// Usually created by `#[distributed_slice(SLICE, index = N)]` rather than
+/// written out. A static registered that way has type `Slot<T>` even though
+/// its declaration says `T`.
+///
+/// ```compile_fail
+/// # use linkme::dense::Slot;
+/// # use linkme::distributed_slice;
+/// #
+/// # #[distributed_slice]
+/// # pub static SYSCALLS: [Slot<fn()>] = [..];
+/// #
+/// #[distributed_slice(SYSCALLS, index = 7)]
+/// fn sys_fork() {}
+///
+/// // error: symbol `linkme.index.SYSCALLS.7` is already defined
+/// #[distributed_slice(SYSCALLS, index = 7)]
+/// fn sys_vfork() {}
+/// #
+/// # fn main() {}
+/// ```
+pub struct Slot<T> {
+    pub index: usize,
+    pub value: T,
+}
+
+/// Copies every element into its slot of `table`.
+///
+/// Slots that no element claims keep their previous contents, usually a
+/// default handler. Two elements claiming the same index are an error, even
+/// though registering with `index = N` already rejects that at build time, to
+/// cover slots written out as struct literals. The table is left partially
+/// populated if an error is returned.
+pub fn populate<T: Copy>(slots: &[Slot<T>], table: &mut [T]) -> Result<(), Invalid> {
+    if let Some((slot, _)) = duplicate::find(slots.iter(), |a, b| a.index == b.index) {
+        return Err(Invalid::Duplicate { index: slot.index });
+    }
+    let len = table.len();
+    for slot in slots {
+        match table.get_mut(slot.index) {
+            Some(entry) => *entry = slot.value,
+            None => {
+                return Err(Invalid::OutOfRange {
+                    index: slot.index,
+                    len,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Finds the element claiming the given index.
+pub fn get<T>(slots: &[Slot<T>], index: usize) -> Option<&T> {
+    slots
+        .iter()
+        .find(|slot| slot.index == index)
+        .map(|slot| &slot.value)
+}
+
+/// Length of the smallest table that fits every element: one past the
+/// highest claimed index.
+pub fn len<T>(slots: &[Slot<T>]) -> usize {
+    slots.iter().map(|slot| slot.index + 1).max().unwrap_or(0)
+}
+
+/// Error returned by [`populate`].
+#[derive(Debug)]
+pub enum Invalid {
+    /// Two elements claim the same index.
+    Duplicate { index: usize },
+    /// An index does not fit in the table.
+    OutOfRange { index: usize, len: usize },
+}
+
+impl Display for Invalid {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Invalid::Duplicate { index } => {
+                write!(formatter, "index {} is claimed more than once", index)
+            }
+            Invalid::OutOfRange { index, len } => write!(
+                formatter,
+                "index {} is out of range for a table of {} entries",
+                index, len,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Invalid {}
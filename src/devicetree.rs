@@ -0,0 +1,100 @@
+//! Device-tree `compatible` match tables for decentralized driver matching.
+//!
+//! Like Linux's `of_device_id` tables, each driver registers a
+//! [`DriverMatch`] for every `compatible` string it supports into a
+//! distributed slice owned by the kernel. When the kernel walks the device
+//! tree it picks a driver for each node with [`match_node`] and calls its
+//! probe function, so adding a driver crate is enough for its devices to be
+//! found.
+//!
+//! ```
+//! use linkme::devicetree::{self, DriverMatch};
+//! use linkme::distributed_slice;
+//!
+//! pub struct Node {
+//!     pub compatible: &'static [&'static str],
+//!     pub reg: usize,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static DRIVERS: [DriverMatch<Node>] = [..];
+//!
+//! // In the PL011 driver crate.
+//! #[distributed_slice(DRIVERS)]
+//! static PL011: DriverMatch<Node> = DriverMatch {
+//!     compatible: "arm,pl011",
+//!     probe: |node| { /* map node.reg and register a console */ },
+//! };
+//!
+//! fn main() {
+//!     let node = Node {
+//!         compatible: &["brcm,bcm2835-pl011", "arm,pl011", "arm,primecell"],
+//!         reg: 0x7e20_1000,
+//!     };
+//!     assert!(devicetree::probe(&DRIVERS, node.compatible, &node));
+//! }
+//! ```
+
+/// Driver entry for one `compatible` string, probing devices of type `D`.
+pub struct DriverMatch<D: 'static> {
+    /// Compatible string in the device tree's `"vendor,device"` form.
+    pub compatible: &'static str,
+    pub probe: fn(&D),
+}
+
+/// Finds the entry whose compatible string is exactly `compatible`.
+pub fn find<'a, D>(table: &'a [DriverMatch<D>], compatible: &str) -> Option<&'a DriverMatch<D>> {
+    table.iter().find(|entry| entry.compatible == compatible)
+}
+
+/// Finds the entry whose compatible string is the longest prefix of
+/// `compatible`.
+///
+/// This lets one entry such as `"vendor,uart"` serve a family of devices like
+/// `"vendor,uart-v2"` and `"vendor,uart-v3"`, while a more specific entry
+/// still wins for the devices it names.
+pub fn find_prefix<'a, D>(
+    table: &'a [DriverMatch<D>],
+    compatible: &str,
+) -> Option<&'a DriverMatch<D>> {
+    let mut best: Option<&DriverMatch<D>> = None;
+    for entry in table {
+        if compatible.starts_with(entry.compatible)
+            && best.map_or(true, |best| entry.compatible.len() > best.compatible.len())
+        {
+            best = Some(entry);
+        }
+    }
+    best
+}
+
+/// Finds the driver for a node given its `compatible` property.
+///
+/// The property lists strings from most to least specific, and the first one
+/// with an exactly matching entry wins, as in Linux. Prefix matches are only
+/// considered if no string matches exactly.
+pub fn match_node<'a, D>(
+    table: &'a [DriverMatch<D>],
+    compatibles: &[&str],
+) -> Option<&'a DriverMatch<D>> {
+    compatibles
+        .iter()
+        .find_map(|compatible| find(table, compatible))
+        .or_else(|| {
+            compatibles
+                .iter()
+                .find_map(|compatible| find_prefix(table, compatible))
+        })
+}
+
+/// Probes `device` with the driver matching its `compatible` property,
+/// returning whether a driver was found.
+pub fn probe<D>(table: &[DriverMatch<D>], compatibles: &[&str], device: &D) -> bool {
+    match match_node(table, compatibles) {
+        Some(entry) => {
+            (entry.probe)(device);
+            true
+        }
+        None => false,
+    }
+}
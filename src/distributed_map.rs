@@ -0,0 +1,269 @@
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Display};
+use core::slice;
+
+use crate::once::Once;
+#[cfg(target_family = "wasm")]
+use crate::private::wasm::Node;
+use crate::DistributedSlice;
+
+/// Collection of static key-value pairs gathered by the linker, looked up by
+/// key.
+///
+/// A distributed map is a [`DistributedSlice`] of `(K, V)` pairs together with
+/// an index of the pairs sorted by key, built on first access, so that
+/// lookups take logarithmic time however many pairs are registered.
+///
+/// Requires the `alloc` feature.
+///
+/// ## Declaration
+///
+/// A distributed map is declared by writing `#[distributed_map]` on a static
+/// item whose type is `[(K, V)]`. As with a distributed slice, the initializer
+/// expression must be `[..]`, and the attribute rewrites the type of the
+/// static into `DistributedMap<K, V>`.
+///
+/// ```
+/// # pub struct Codec;
+/// #
+/// use linkme::distributed_map;
+///
+/// #[distributed_map]
+/// pub static CODECS: [(&'static str, Codec)] = [..];
+/// ```
+///
+/// ## Entries
+///
+/// Entries are registered from anywhere in the dependency graph by a
+/// `#[distributed_map(...)]` attribute naming the map, on a static whose type
+/// is the `(K, V)` pair.
+///
+/// ```
+/// use linkme::distributed_map;
+///
+/// pub struct Codec {
+///     pub lossless: bool,
+/// }
+///
+/// #[distributed_map]
+/// pub static CODECS: [(&'static str, Codec)] = [..];
+///
+/// #[distributed_map(CODECS)]
+/// static FLAC: (&'static str, Codec) = ("flac", Codec { lossless: true });
+///
+/// #[distributed_map(CODECS)]
+/// static OPUS: (&'static str, Codec) = ("opus", Codec { lossless: false });
+///
+/// fn main() {
+///     assert!(CODECS.get("flac").unwrap().lossless);
+///     assert!(CODECS.get("mp3").is_none());
+///     assert_eq!(CODECS.len(), 2);
+/// }
+/// ```
+///
+/// An entry whose type is not the map's `(K, V)` pair does not compile, and
+/// neither does a declaration whose type is not a slice of pairs.
+///
+/// ```compile_fail
+/// use linkme::distributed_map;
+///
+/// #[distributed_map]
+/// pub static CODECS: [(&'static str, u32)] = [..];
+///
+/// // error: expected `(&str, u32)`, found `(u32, &str)`
+/// #[distributed_map(CODECS)]
+/// static FLAC: (u32, &'static str) = (1, "flac");
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ## Duplicate keys
+///
+/// Nothing stops two crates from registering the same key. Lookups with
+/// [`get`] find the entry that comes first in the underlying slice,
+/// [`get_all`] finds every entry with the key, and [`validate`] reports the
+/// first key registered more than once, which is worth asserting in a test.
+///
+/// [`get`]: DistributedMap::get
+/// [`get_all`]: DistributedMap::get_all
+/// [`validate`]: DistributedMap::validate
+pub struct DistributedMap<K: 'static, V: 'static> {
+    slice: DistributedSlice<[(K, V)]>,
+    // Positions in the slice, sorted by key and then by position.
+    index: Once<Vec<usize>>,
+}
+
+impl<K, V> DistributedMap<K, V> {
+    #[doc(hidden)]
+    pub const fn private_new(slice: DistributedSlice<[(K, V)]>) -> Self {
+        DistributedMap {
+            slice,
+            index: Once::new(),
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(target_family = "wasm")]
+    pub unsafe fn private_register(&self, node: &'static Node<(K, V)>) {
+        self.slice.private_register(node);
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub unsafe fn private_typecheck(&self, element: (K, V)) {
+        self.slice.private_typecheck(element);
+    }
+
+    /// Number of entries, counting entries with a repeated key.
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    /// The entries in the order the linker placed them.
+    pub fn entries(&self) -> &'static [(K, V)] {
+        self.slice.static_slice()
+    }
+}
+
+impl<K: Ord, V> DistributedMap<K, V> {
+    /// Finds the value for `key`.
+    ///
+    /// If several entries share the key, the one first in the slice is found.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_all(key).next()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Every value registered for `key`, in slice order.
+    pub fn get_all<Q>(&self, key: &Q) -> Values<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let entries = self.entries();
+        let index = self.index();
+        let start = index.partition_point(|&i| entries[i].0.borrow() < key);
+        let len = index[start..].partition_point(|&i| entries[i].0.borrow() == key);
+        Values {
+            iter: Iter {
+                entries,
+                index: index[start..start + len].iter(),
+            },
+        }
+    }
+
+    /// Every entry in order of key, with equal keys in slice order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: self.entries(),
+            index: self.index().iter(),
+        }
+    }
+
+    /// Builds the index now rather than on first lookup, reporting the first
+    /// key that more than one entry registers.
+    pub fn validate(&self) -> Result<(), DuplicateKey<'static, K>> {
+        let entries = self.entries();
+        let index = self.index();
+        match index
+            .windows(2)
+            .find(|pair| entries[pair[0]].0 == entries[pair[1]].0)
+        {
+            None => Ok(()),
+            Some(pair) => Err(DuplicateKey {
+                key: &entries[pair[1]].0,
+            }),
+        }
+    }
+
+    fn index(&self) -> &[usize] {
+        self.index.get_or_init(|| {
+            let entries = self.entries();
+            let mut index: Vec<usize> = (0..entries.len()).collect();
+            // Stable, so equal keys stay in slice order.
+            index.sort_by(|&a, &b| entries[a].0.cmp(&entries[b].0));
+            index
+        })
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a DistributedMap<K, V> {
+    type Item = (&'static K, &'static V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`DistributedMap`] in key order.
+pub struct Iter<'a, K: 'static, V: 'static> {
+    entries: &'static [(K, V)],
+    index: slice::Iter<'a, usize>,
+}
+
+impl<K, V> Iterator for Iter<'_, K, V> {
+    type Item = (&'static K, &'static V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = &self.entries[*self.index.next()?];
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.index.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// Iterator over the values registered for one key of a [`DistributedMap`].
+pub struct Values<'a, K: 'static, V: 'static> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<K, V> Iterator for Values<'_, K, V> {
+    type Item = &'static V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+/// Error returned by [`DistributedMap::validate`] when two entries share a
+/// key.
+#[derive(Debug)]
+pub struct DuplicateKey<'a, K> {
+    pub key: &'a K,
+}
+
+impl<K: Debug> Display for DuplicateKey<'_, K> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "key {:?} is registered twice", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Debug> std::error::Error for DuplicateKey<'_, K> {}
@@ -1,16 +1,22 @@
 use core::mem;
 use core::ops::Deref;
+#[cfg(not(target_family = "wasm"))]
+use core::ptr;
 use core::slice;
 
-use crate::private::Slice;
+use crate::padding::{self, SkipNull};
+#[cfg(target_family = "wasm")]
+use crate::private::wasm::{Node, Registry};
+use crate::private::{Slice, Sort};
 
 /// Collection of static elements that are gathered into a contiguous section of
 /// the binary by the linker.
 ///
 /// The implementation is based on `link_section` attributes and
 /// platform-specific linker support. It does not involve life-before-main or
-/// any other runtime initialization on any platform. This is a zero-cost safe
-/// abstraction that operates entirely during compilation and linking.
+/// any other runtime initialization on any platform other than WebAssembly
+/// (see [below](#webassembly)). This is a zero-cost safe abstraction that
+/// operates entirely during compilation and linking.
 ///
 /// ## Declaration
 ///
@@ -97,6 +103,235 @@ use crate::private::Slice;
 ///                     found type `usize`
 /// ```
 ///
+/// ## Exporting to C
+///
+/// Adding `#[linkme(export_c = "name")]` to a declaration additionally
+/// exports two pointer-sized symbols, `name_data` and `name_end`, holding
+/// the address of the first element and the address one past the last. C and
+/// C++ code linked into the same binary can then read the slice without
+/// calling into Rust. The element type should be `#[repr(C)]`.
+///
+/// ```
+/// use linkme::distributed_slice;
+///
+/// #[repr(C)]
+/// pub struct Driver {
+///     pub id: u32,
+///     pub probe: extern "C" fn() -> i32,
+/// }
+///
+/// #[distributed_slice]
+/// #[linkme(export_c = "drivers")]
+/// pub static DRIVERS: [Driver] = [..];
+/// ```
+///
+/// ```c
+/// struct driver {
+///     uint32_t id;
+///     int (*probe)(void);
+/// };
+///
+/// extern const struct driver *const drivers_data;
+/// extern const struct driver *const drivers_end;
+///
+/// for (const struct driver *d = drivers_data; d != drivers_end; d++) {
+///     d->probe();
+/// }
+/// ```
+///
+/// The length is exported implicitly as `drivers_end - drivers_data`, since
+/// it is only known once the binary is linked.
+///
+/// A slice of function pointers that C will call can additionally assert
+/// their ABI with `#[linkme(abi = "C")]`. The declaration then fails to compile
+/// unless its element type is an `extern "C" fn` pointer, rather than for
+/// example a Rust-ABI `fn` written by mistake, and as with any slice, a
+/// registration whose signature or ABI differs from the element type is a
+/// compile error.
+///
+/// The element type may also be a type alias such as `type Callback = extern
+/// "C" fn(i32) -> i32`, which is then checked through the type system. This
+/// covers `abi = "C"` and `abi = "system"` with up to 12 arguments; other ABIs,
+/// and higher-ranked function pointers like `extern "C" fn(&u8)`, need the
+/// pointer type written out in the declaration.
+///
+/// ```compile_fail
+/// use linkme::distributed_slice;
+///
+/// #[distributed_slice]
+/// #[linkme(abi = "C")]
+/// pub static CALLBACKS: [extern "C" fn(i32) -> i32] = [..];
+///
+/// // error: expected "C" fn, found "Rust" fn
+/// #[distributed_slice(CALLBACKS)]
+/// fn double(x: i32) -> i32 {
+///     x * 2
+/// }
+/// ```
+///
+/// ## Mirrors
+///
+/// A crate that cannot modify the crate declaring a distributed slice may
+/// declare a mirror of it with `#[linkme(mirror = path::to::SLICE)]`. The
+/// mirror refers to the same section as the original, so both see every
+/// element, including those registered through the mirror. The element type
+/// of the mirror must match that of the original; a mismatch is a compile
+/// error.
+///
+/// ```
+/// # mod vendored {
+/// #     use linkme::distributed_slice;
+/// #
+/// #     pub struct Plugin {
+/// #         pub name: &'static str,
+/// #     }
+/// #
+/// #     #[distributed_slice]
+/// #     pub static PLUGINS: [Plugin] = [..];
+/// # }
+/// #
+/// use linkme::distributed_slice;
+/// use vendored::Plugin;
+///
+/// #[distributed_slice]
+/// #[linkme(mirror = vendored::PLUGINS)]
+/// pub static ALL_PLUGINS: [Plugin] = [..];
+///
+/// #[distributed_slice(ALL_PLUGINS)]
+/// static AUDIT: Plugin = Plugin { name: "audit" };
+///
+/// fn main() {
+///     assert_eq!(vendored::PLUGINS.len(), 1);
+///     assert_eq!(ALL_PLUGINS[0].name, "audit");
+/// }
+/// ```
+///
+/// Within the crate that declares the original, the mirror needs a name of
+/// its own.
+///
+/// ## Ordering
+///
+/// Elements otherwise appear in whatever order the linker placed them, which
+/// differs between platforms and builds. A slice declared with
+/// `#[linkme(ordered)]` instead yields its elements sorted by the `order = N`
+/// given at registration, an `i32` that defaults to 0. Elements with equal
+/// order appear in an unspecified order relative to each other.
+///
+/// ```
+/// use linkme::distributed_slice;
+///
+/// pub struct Layer {
+///     pub name: &'static str,
+/// }
+///
+/// #[distributed_slice]
+/// #[linkme(ordered)]
+/// pub static LAYERS: [Layer] = [..];
+///
+/// #[distributed_slice(LAYERS, order = 10)]
+/// static LOGGING: Layer = Layer { name: "logging" };
+///
+/// #[distributed_slice(LAYERS, order = -10)]
+/// static AUTH: Layer = Layer { name: "auth" };
+///
+/// #[distributed_slice(LAYERS)]
+/// static ROUTING: Layer = Layer { name: "routing" };
+///
+/// fn main() {
+///     let names: Vec<_> = LAYERS.iter().map(|layer| layer.name).collect();
+///     assert_eq!(names, ["auth", "routing", "logging"]);
+/// }
+/// ```
+///
+/// On Windows the order is encoded into the grouped section name and the
+/// linker sorts the elements, and on WebAssembly the registry sorts them. On
+/// the other targets each element places a key recording its order in a
+/// companion section, and the first access to the slice sorts the elements in
+/// place. For that reason the element statics of an ordered slice cannot be
+/// referred to by name, and on bare-metal targets the slice's section must be
+/// in writable memory. Registering with an order requires rustc 1.51 or
+/// newer. Ordered slices cannot be mirrored, and do not accept the positional
+/// or `index = N` forms of registration.
+///
+/// ## Registrations in tests
+///
+/// An element is present in a slice exactly when the code registering it is
+/// compiled into the binary being run, whichever crate declared the slice.
+/// For tests this means:
+///
+/// - Elements inside `#[cfg(test)]` in a crate are compiled only into that
+///   crate's unit-test binary, where they appear alongside the elements of
+///   every crate it depends on.
+/// - Each integration test under `tests/` is a separate binary. It sees its
+///   own elements and those registered by the library built normally, never
+///   the library's `#[cfg(test)]` elements and never those of another
+///   integration test.
+/// - Each doctest is a separate binary too, linked against the library built
+///   normally. It sees the library's own elements and those in the doctest,
+///   but not the library's `#[cfg(test)]` elements.
+/// - A dependency that is used only for the elements it registers, such as a
+///   crate of shared test fixtures, must still be named somewhere, for
+///   example with `use fixtures as _;`, because the compiler does not link
+///   crates nothing refers to.
+///
+/// [`linkme::fixture`](crate::fixture) builds on this to let test fixtures
+/// register themselves.
+///
+/// ## Dynamic libraries
+///
+/// The same holds for a shared library loaded at runtime: it has its own copy
+/// of the slice with the elements linked into it, which the host's slice does
+/// not see. The `linkme::dylib` module, which requires the `alloc` feature,
+/// lets the host add those elements to its own.
+///
+/// ## Identical code folding
+///
+/// Every element keeps its own address even when two elements are byte for
+/// byte identical and the linker folds identical sections. Rust statics are
+/// never marked as having an insignificant address, on ELF the slice's
+/// sections are named as C identifiers, which `ld.lld --icf=all` excludes
+/// from folding because `__start_`/`__stop_` symbols refer to them, and on
+/// macOS the elements live in `__DATA`, which is not folded.
+///
+/// ## Static linking
+///
+/// On Linux the slice bounds are plain references to the linker-defined
+/// `__start_`/`__stop_` symbols, relocated like any other address, so
+/// slices work the same in dynamically linked, fully static and static-PIE
+/// executables, with glibc or musl. CI runs the test suite on
+/// `x86_64-unknown-linux-musl` with `-C relocation-model=static` and with
+/// static-PIE, in debug and release.
+///
+/// ## Linker options on Windows
+///
+/// With MSVC, elements are gathered through grouped sections (`$a`, `$b`,
+/// `$c`) that the linker sorts by suffix. The slice bounds are zero-length
+/// arrays of the element type, so they are aligned the same as the elements
+/// and no padding can appear before the first element or after the last.
+///
+/// `/OPT:REF` only discards COMDAT sections that nothing references, and
+/// `/OPT:ICF` only folds COMDAT sections. The elements and bounds are placed in
+/// explicitly named sections, which are never COMDAT, and every one of them is
+/// marked `#[used]`, for which rustc also emits an `/INCLUDE:` linker directive
+/// naming the symbol. CI runs the test suite under `/OPT:REF`, `/OPT:ICF` and
+/// `/INCREMENTAL` to catch linker behavior that would lose or pad elements.
+///
+/// ## WebAssembly
+///
+/// wasm-ld provides no bounds for a section, and `link_section` on wasm names
+/// a custom section outside of linear memory, so on `wasm32-unknown-unknown`
+/// and WASI each element instead registers itself from a constructor that
+/// runs during `__wasm_call_ctors`. The first access to the slice copies the
+/// registered elements, in the same order as on other targets, into a single
+/// allocation that lives for the rest of the program. This needs a global
+/// allocator, and because the slice holds copies, an element with interior
+/// mutability read through its own static will not observe changes made
+/// through the slice, nor the other way around.
+///
+/// Threads that instantiate the module with shared memory run the
+/// constructors again; registering an element is idempotent, so each element
+/// still appears once. `#[linkme(export_c)]` has no effect on wasm.
+///
 /// ## Function elements
 ///
 /// As a shorthand for the common case of distributed slices containing function
@@ -122,9 +357,50 @@ use crate::private::Slice;
 ///     /* ... */
 /// }
 /// ```
+///
+/// ## Associated items
+///
+/// Associated consts and associated functions are registered by writing the
+/// attribute on the item inside an impl block, and a bare
+/// `#[distributed_slice]` on the impl block itself. The element is a copy of
+/// `<MyType as Plugin>::DESCRIPTOR`, or a pointer to the function, and the
+/// item remains part of the impl. Generic impls are not supported.
+///
+/// ```
+/// use linkme::distributed_slice;
+///
+/// pub struct Descriptor {
+///     pub name: &'static str,
+/// }
+///
+/// pub trait Plugin {
+///     const DESCRIPTOR: Descriptor;
+/// }
+///
+/// #[distributed_slice]
+/// pub static DESCRIPTORS: [Descriptor] = [..];
+///
+/// pub struct Gzip;
+///
+/// #[distributed_slice]
+/// impl Plugin for Gzip {
+///     #[distributed_slice(DESCRIPTORS)]
+///     const DESCRIPTOR: Descriptor = Descriptor { name: "gzip" };
+/// }
+/// #
+/// # fn main() {
+/// #     assert_eq!(DESCRIPTORS[0].name, "gzip");
+/// # }
+/// ```
 pub struct DistributedSlice<T: ?Sized + Slice> {
+    #[cfg(not(target_family = "wasm"))]
     start: StaticPtr<T::Element>,
+    #[cfg(not(target_family = "wasm"))]
     stop: StaticPtr<T::Element>,
+    #[cfg(not(target_family = "wasm"))]
+    sort: StaticPtr<Sort>,
+    #[cfg(target_family = "wasm")]
+    registry: StaticPtr<Registry<T::Element>>,
 }
 
 struct StaticPtr<T> {
@@ -156,12 +432,13 @@ impl<T> DistributedSlice<[T]> {
         DistributedSlice {
             start: StaticPtr { ptr: start },
             stop: StaticPtr { ptr: stop },
+            sort: StaticPtr { ptr: ptr::null() },
         }
     }
 
     #[doc(hidden)]
     #[cfg(target_os = "windows")]
-    pub const unsafe fn private_new(start: *const (), stop: *const ()) -> Self {
+    pub const unsafe fn private_new(start: *const [T; 0], stop: *const [T; 0]) -> Self {
         DistributedSlice {
             start: StaticPtr {
                 ptr: start as *const T,
@@ -169,9 +446,48 @@ impl<T> DistributedSlice<[T]> {
             stop: StaticPtr {
                 ptr: stop as *const T,
             },
+            sort: StaticPtr { ptr: ptr::null() },
         }
     }
 
+    #[doc(hidden)]
+    #[cfg(not(target_family = "wasm"))]
+    #[must_use]
+    pub const fn private_ordered(self, sort: &'static Sort) -> Self {
+        DistributedSlice {
+            start: self.start,
+            stop: self.stop,
+            sort: StaticPtr { ptr: sort },
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(target_family = "wasm")]
+    #[must_use]
+    pub const fn private_ordered(self, _sort: &'static Sort) -> Self {
+        self
+    }
+
+    #[doc(hidden)]
+    #[cfg(target_family = "wasm")]
+    pub const unsafe fn private_new(registry: &'static Registry<T>, _stop: &'static ()) -> Self {
+        DistributedSlice {
+            registry: StaticPtr { ptr: registry },
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(target_family = "wasm")]
+    pub fn private_registry(self) -> *const Registry<T> {
+        self.registry.ptr
+    }
+
+    #[doc(hidden)]
+    #[cfg(target_family = "wasm")]
+    pub unsafe fn private_register(self, node: &'static Node<T>) {
+        (*self.registry.ptr).register(node);
+    }
+
     #[doc(hidden)]
     #[inline]
     pub unsafe fn private_typecheck(self, element: T) {
@@ -214,6 +530,11 @@ impl<T> DistributedSlice<[T]> {
     /// }
     /// ```
     pub fn static_slice(self) -> &'static [T] {
+        self.elements()
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn elements(self) -> &'static [T] {
         let stride = mem::size_of::<T>();
 
         if stride == 0 {
@@ -227,8 +548,89 @@ impl<T> DistributedSlice<[T]> {
         let stop = self.stop.ptr;
         let byte_offset = stop as usize - start as usize;
         let len = byte_offset / stride;
+
+        // A misaligned section start or padding between elements would make
+        // every access below misbehave, and fault outright on strict-alignment
+        // architectures such as SPARC and ARMv5.
+        debug_assert!(
+            start as usize % mem::align_of::<T>() == 0,
+            "distributed slice starts at misaligned address {:p}",
+            start,
+        );
+        debug_assert!(
+            byte_offset % stride == 0,
+            "distributed slice spans {} bytes, not a multiple of the element size {}",
+            byte_offset,
+            stride,
+        );
+        if let Some(sort) = unsafe { self.sort.ptr.as_ref() } {
+            unsafe { sort.run(start as *mut T, len) };
+        }
         unsafe { slice::from_raw_parts(start, len) }
     }
+
+    #[cfg(target_family = "wasm")]
+    fn elements(self) -> &'static [T] {
+        unsafe { (*self.registry.ptr).slice() }
+    }
+}
+
+impl<T> DistributedSlice<[T]> {
+    /// Whether the linker for the current target may have inserted zero
+    /// padding between elements of this slice.
+    ///
+    /// Where this is true, [`static_slice`] may include padding and
+    /// [`iter_skip_null`] should be used to read the slice instead. See the
+    /// [`padding`] module for details.
+    ///
+    /// [`static_slice`]: DistributedSlice::static_slice
+    /// [`iter_skip_null`]: DistributedSlice::iter_skip_null
+    pub fn may_contain_padding(self) -> bool {
+        padding::MAY_CONTAIN_PADDING
+    }
+
+    /// Iterates over the elements, skipping element-sized runs of zero bytes
+    /// that the linker may have inserted as padding.
+    ///
+    /// A slice declared with `#[linkme(ordered)]` is sorted first, exactly
+    /// once across all threads, as on any other access.
+    ///
+    /// # Safety
+    ///
+    /// `T` must have no padding bytes of its own, and no real element may
+    /// consist entirely of zero bytes. Function pointers and references meet
+    /// both requirements.
+    pub unsafe fn iter_skip_null(self) -> SkipNull<T> {
+        let (start, stop) = self.bounds();
+        SkipNull::new(start, stop)
+    }
+
+    // The section bounds, without building a slice over any padding between
+    // them. An ordered slice is sorted first, behind the same guard as in
+    // `elements`.
+    #[cfg(not(target_family = "wasm"))]
+    fn bounds(self) -> (*const T, *const T) {
+        let start = self.start.ptr;
+        let stop = self.stop.ptr;
+        let byte_offset = stop as usize - start as usize;
+        if let Some(len) = byte_offset.checked_div(mem::size_of::<T>()) {
+            if let Some(sort) = unsafe { self.sort.ptr.as_ref() } {
+                unsafe { sort.run(start as *mut T, len) };
+            }
+        }
+        (start, stop)
+    }
+
+    // Elements on wasm are copied out of their statics and never padded.
+    #[cfg(target_family = "wasm")]
+    fn bounds(self) -> (*const T, *const T)
+    where
+        T: 'static,
+    {
+        let slice = self.static_slice();
+        let start = slice.as_ptr();
+        (start, unsafe { start.add(slice.len()) })
+    }
 }
 
 impl<T> Copy for DistributedSlice<[T]> {}
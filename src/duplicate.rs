@@ -0,0 +1,92 @@
+//! Checking that the elements of a distributed slice have distinct keys.
+//!
+//! Nothing stops two crates from registering elements that claim the same
+//! name, code or number. Registries looked up by such a key can call
+//! [`check`] from a test or at startup to find out, instead of one of the
+//! elements silently going unused.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::duplicate;
+//!
+//! pub struct Codec {
+//!     pub name: &'static str,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static CODECS: [Codec] = [..];
+//!
+//! #[distributed_slice(CODECS)]
+//! static FLAC: Codec = Codec { name: "flac" };
+//!
+//! fn main() {
+//!     duplicate::check(&CODECS, |codec| codec.name).unwrap();
+//! }
+//! ```
+
+use core::fmt::{self, Debug, Display};
+
+/// Checks that no two elements have the same key.
+///
+/// The search is quadratic, which is fine for the size of a typical registry
+/// and needs no allocator.
+pub fn check<T, K, F>(items: &[T], key: F) -> Result<(), Duplicate<'_, T, K>>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    match find(items.iter(), |a, b| key(a) == key(b)) {
+        None => Ok(()),
+        Some((first, second)) => Err(Duplicate {
+            key: key(first),
+            first,
+            second,
+        }),
+    }
+}
+
+/// Error returned by [`check`] when two elements have the same key.
+pub struct Duplicate<'a, T, K> {
+    pub key: K,
+    /// The element with the key that comes first in the slice.
+    pub first: &'a T,
+    /// The next element with the same key.
+    pub second: &'a T,
+}
+
+impl<T, K: Debug> Debug for Duplicate<'_, T, K> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Duplicate")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl<T, K: Debug> Display for Duplicate<'_, T, K> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{:?} is registered twice", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, K: Debug> std::error::Error for Duplicate<'_, T, K> {}
+
+// Quadratic duplicate search shared by the registries that validate their
+// entries. Registries are small and this needs to work without an allocator.
+pub(crate) fn find<I, F>(items: I, same: F) -> Option<(I::Item, I::Item)>
+where
+    I: Iterator + Clone,
+    I::Item: Copy,
+    F: Fn(&I::Item, &I::Item) -> bool,
+{
+    let mut rest = items;
+    while let Some(first) = rest.next() {
+        for second in rest.clone() {
+            if same(&first, &second) {
+                return Some((first, second));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,261 @@
+//! Elements of distributed slices in dynamically loaded libraries.
+//!
+//! Every shared library gets its own copy of each distributed slice, holding
+//! the elements linked into that library, so a plugin built as a `cdylib` and
+//! loaded at runtime registers elements that the host's slice never sees.
+//!
+//! A slice declared with `#[linkme(dylib = "name")]` exports a descriptor of
+//! its elements under the symbol `name` from every library it is linked into.
+//! After loading a plugin, the host looks the symbol up, for example with
+//! `libloading`, and hands it to a [`DynamicSlice`] that sits in a static next
+//! to the slice. Iterating the `DynamicSlice` yields the host's own elements
+//! followed by those of each loaded library in load order. This works the
+//! same on Linux, macOS and Windows.
+//!
+//! Requires the `alloc` feature. Only the host needs it; exporting the
+//! descriptor from a plugin does not.
+//!
+//! ```
+//! // In the api crate that the host and the plugins depend on.
+//! use linkme::distributed_slice;
+//! use linkme::dylib::DynamicSlice;
+//!
+//! pub struct Command {
+//!     pub name: &'static str,
+//! }
+//!
+//! #[distributed_slice]
+//! #[linkme(dylib = "api_commands")]
+//! pub static LINKED: [Command] = [..];
+//!
+//! pub static COMMANDS: DynamicSlice<Command> = DynamicSlice::new(&LINKED);
+//!
+//! // In the host, after loading each plugin.
+//! # struct Library;
+//! # impl Library {
+//! #     unsafe fn get(&self, _: &[u8]) -> Result<*const (), ()> {
+//! #         Err(())
+//! #     }
+//! # }
+//! # let library = Library;
+//! #
+//! if let Ok(symbol) = unsafe { library.get(b"api_commands\0") } {
+//!     unsafe { COMMANDS.load(symbol) }.unwrap();
+//! }
+//!
+//! for command in &COMMANDS {
+//!     println!("{}", command.name);
+//! }
+//! ```
+//!
+//! The copy of the slice in a library holds every element linked into it,
+//! including those registered by crates that the host links too, such as the
+//! api crate itself. Such elements appear once for the host and once more for
+//! each library, so elements meant to come only from the host are best
+//! registered outside of the crates that plugins depend on.
+//!
+//! The host and the plugins must be built by the same compiler against the
+//! same version of linkme and of the crate declaring the element type.
+//! Loading checks the element type and rejects a mismatch where it can. The
+//! loaded elements are borrowed for `'static` from the library, which
+//! therefore must never be unloaded: with `libloading`, leak the `Library`
+//! once it has been loaded.
+
+use crate::private::{DylibExport, TypeId, DYLIB_VERSION};
+use crate::registry::Registry;
+use crate::DistributedSlice;
+use alloc::boxed::Box;
+use core::fmt::{self, Display};
+use core::mem;
+use core::ptr;
+use core::slice;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// Distributed slice extended with the elements of dynamically loaded
+/// libraries.
+pub struct DynamicSlice<T: 'static> {
+    linked: &'static DistributedSlice<[T]>,
+    // Loaded libraries in load order. Nodes are appended and never removed.
+    libraries: AtomicPtr<Library<T>>,
+}
+
+struct Library<T: 'static> {
+    elements: &'static [T],
+    next: AtomicPtr<Library<T>>,
+}
+
+unsafe impl<T: Sync> Sync for DynamicSlice<T> {}
+
+impl<T> DynamicSlice<T> {
+    /// Creates a dynamic slice extending `linked`, for use as a static.
+    pub const fn new(linked: &'static DistributedSlice<[T]>) -> Self {
+        DynamicSlice {
+            linked,
+            libraries: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Adds the elements exported under `symbol` by a loaded library.
+    ///
+    /// Loading the same library twice, or a symbol that resolves to the
+    /// host's own copy of the slice, has no effect.
+    ///
+    /// # Safety
+    ///
+    /// `symbol` must be the address of a symbol exported by a slice declared
+    /// with `#[linkme(dylib = "...")]`, from a library that stays loaded for
+    /// the rest of the program.
+    pub unsafe fn load(&self, symbol: *const ()) -> Result<(), LoadError> {
+        let version = ptr::read(symbol as *const u32);
+        if version != DYLIB_VERSION {
+            return Err(LoadError::Version { found: version });
+        }
+        let export = &*(symbol as *const DylibExport<T>);
+        if export.size != mem::size_of::<T>()
+            || export.align != mem::align_of::<T>()
+            || (export.type_id)() != TypeId::of::<T>()
+        {
+            return Err(LoadError::ElementType);
+        }
+
+        let elements = (export.slice)();
+        let mut loaded = self.linked.static_slice();
+        let mut link = &self.libraries;
+        let library = Box::into_raw(Box::new(Library {
+            elements,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }));
+        loop {
+            if ptr::eq(loaded, elements) {
+                drop(Box::from_raw(library));
+                return Ok(());
+            }
+            match link.compare_exchange(
+                ptr::null_mut(),
+                library,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(next) => {
+                    loaded = (*next).elements;
+                    link = &(*next).next;
+                }
+            }
+        }
+    }
+
+    /// The host's own elements followed by those of each loaded library.
+    pub fn iter(&self) -> Iter<T> {
+        Iter {
+            elements: self.linked.static_slice().iter(),
+            next: self.libraries.load(Ordering::Acquire),
+        }
+    }
+
+    /// The elements of each loaded library, in load order, not including the
+    /// host's own.
+    pub fn libraries(&self) -> Libraries<T> {
+        Libraries {
+            next: self.libraries.load(Ordering::Acquire),
+        }
+    }
+}
+
+impl<T> Registry<T> for DynamicSlice<T> {
+    fn len(&self) -> usize {
+        let loaded: usize = self.libraries().map(<[T]>::len).sum();
+        self.linked.len() + loaded
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        let mut index = index;
+        let linked = self.linked.static_slice();
+        if index < linked.len() {
+            return Some(&linked[index]);
+        }
+        index -= linked.len();
+        for elements in self.libraries() {
+            if index < elements.len() {
+                return Some(&elements[index]);
+            }
+            index -= elements.len();
+        }
+        None
+    }
+}
+
+impl<T> IntoIterator for &DynamicSlice<T> {
+    type Item = &'static T;
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of a [`DynamicSlice`].
+pub struct Iter<T: 'static> {
+    elements: slice::Iter<'static, T>,
+    next: *const Library<T>,
+}
+
+impl<T> Iterator for Iter<T> {
+    type Item = &'static T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                return Some(element);
+            }
+            let library = unsafe { self.next.as_ref() }?;
+            self.elements = library.elements.iter();
+            self.next = library.next.load(Ordering::Acquire);
+        }
+    }
+}
+
+/// Iterator over the libraries loaded into a [`DynamicSlice`].
+pub struct Libraries<T: 'static> {
+    next: *const Library<T>,
+}
+
+impl<T> Iterator for Libraries<T> {
+    type Item = &'static [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let library = unsafe { self.next.as_ref() }?;
+        self.next = library.next.load(Ordering::Acquire);
+        Some(library.elements)
+    }
+}
+
+/// Error returned by [`DynamicSlice::load`] for a symbol that the host
+/// cannot read.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The library was built against a version of linkme that exports a
+    /// different descriptor.
+    Version { found: u32 },
+    /// The library's slice has a different element type than the host's.
+    ElementType,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Version { found } => write!(
+                formatter,
+                "library exports a version {} slice descriptor, expected version {}",
+                found,
+                crate::private::DYLIB_VERSION,
+            ),
+            LoadError::ElementType => {
+                formatter.write_str("library's slice has a different element type")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LoadError {}
@@ -0,0 +1,172 @@
+//! Embassy tasks that driver crates ask the application to spawn at boot.
+//!
+//! A driver crate that needs a background task registers a [`Task`] whose
+//! spawn function hands the task's token to the executor's spawner. The
+//! application spawns everything registered with [`spawn_all`] as part of its
+//! startup, so it never needs to know which drivers have background work.
+//!
+//! Executors with a statically sized task arena need to know how many task
+//! slots to reserve. Registering through the [`embassy_task!`] macro checks
+//! this at build time: the crate declaring the slice also declares the
+//! capacity, every task claims numbered slots below it, and no slot may be
+//! claimed twice. A binary whose tasks need more slots than the capacity
+//! therefore fails to build. [`task_count`] gives the total at runtime, for
+//! tasks registered without the macro.
+//!
+//! ```
+//! # mod embassy_executor {
+//! #     #[derive(Copy, Clone)]
+//! #     pub struct Spawner;
+//! #     pub struct SpawnError;
+//! #     impl Spawner {
+//! #         pub fn spawn(self, _token: ()) -> Result<(), SpawnError> { Ok(()) }
+//! #     }
+//! # }
+//! # fn blink() {}
+//! use embassy_executor::{SpawnError, Spawner};
+//! use linkme::embassy::{self, Task};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static TASKS: [Task<Spawner, SpawnError>] = [..];
+//!
+//! // Number of task slots the executor reserves.
+//! pub const TASK_CAPACITY: usize = 8;
+//!
+//! // In a driver crate, next to `#[embassy_executor::task] async fn blink()`.
+//! #[distributed_slice(TASKS)]
+//! static BLINK: Task<Spawner, SpawnError> =
+//!     linkme::embassy_task!(TASKS, TASK_CAPACITY, [0], "blink", |spawner| spawner.spawn(blink()));
+//!
+//! // In `#[embassy_executor::main] async fn main(spawner: Spawner)`.
+//! # fn main() {
+//! # let spawner = Spawner;
+//! duplicate::check(&TASKS, |task| task.name).unwrap();
+//! if embassy::spawn_all(&TASKS, spawner).is_err() {
+//!     /* report which task failed to spawn */
+//! }
+//! # }
+//! ```
+//!
+//! [`embassy_task!`]: crate::embassy_task!
+
+use crate::order;
+use core::fmt::{self, Display};
+
+/// Background task to be spawned on the spawner type `S`, whose spawn
+/// operation fails with `E`.
+pub struct Task<S: 'static, E: 'static> {
+    pub name: &'static str,
+    /// Number of instances of the task that may run at once, matching its
+    /// `pool_size`.
+    pub pool_size: usize,
+    /// Spawns the task, typically `|spawner| spawner.spawn(task())`.
+    pub spawn: fn(S) -> Result<(), E>,
+}
+
+/// Builds a [`Task`] that claims the listed slots of the executor's task
+/// arena, rejecting tasks that do not fit at build time.
+///
+/// The arguments are the slice the task is registered into, the capacity of
+/// the arena, the slots the task claims as a list of integer literals, the
+/// task's name and its spawn function. The task's pool size is the number of
+/// slots it claims. A slot number not below the capacity fails to compile, and
+/// two tasks claiming the same slot of the same slice anywhere in the final
+/// binary fail to build: within one crate the compiler rejects them, and
+/// across crates the linker reports a duplicate symbol named
+/// `linkme.task.<SLICE>.<slot>`. Requires rustc 1.57 or newer.
+///
+/// ```compile_fail
+/// # use linkme::distributed_slice;
+/// # use linkme::embassy::Task;
+/// #
+/// # #[distributed_slice]
+/// # pub static TASKS: [Task<(), ()>] = [..];
+/// #
+/// # pub const TASK_CAPACITY: usize = 2;
+/// #
+/// // error: task slot is not below the task capacity
+/// #[distributed_slice(TASKS)]
+/// static RADIO: Task<(), ()> =
+///     linkme::embassy_task!(TASKS, TASK_CAPACITY, [1, 2], "radio", |()| Ok(()));
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// # use linkme::distributed_slice;
+/// # use linkme::embassy::Task;
+/// #
+/// # #[distributed_slice]
+/// # pub static TASKS: [Task<(), ()>] = [..];
+/// #
+/// # pub const TASK_CAPACITY: usize = 4;
+/// #
+/// #[distributed_slice(TASKS)]
+/// static USB: Task<(), ()> = linkme::embassy_task!(TASKS, TASK_CAPACITY, [0], "usb", |()| Ok(()));
+///
+/// // error: symbol `linkme.task.TASKS.0` is already defined
+/// #[distributed_slice(TASKS)]
+/// static BLINK: Task<(), ()> = linkme::embassy_task!(TASKS, TASK_CAPACITY, [0], "blink", |()| Ok(()));
+/// #
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! embassy_task {
+    ($slice:path, $capacity:expr, [$($slot:literal),+ $(,)?], $name:expr, $spawn:expr) => {{
+        $(
+            $slice! {
+                #![linkme_unique = "task", $slot]
+            }
+
+            const _: () = assert!($slot < $capacity, "task slot is not below the task capacity");
+        )+
+
+        $crate::embassy::Task {
+            name: $name,
+            pool_size: [$($slot),+].len(),
+            spawn: $spawn,
+        }
+    }};
+}
+
+/// Spawns every registered task, in order of name.
+///
+/// Stops at the first task that fails to spawn.
+pub fn spawn_all<S: Copy, E>(tasks: &[Task<S, E>], spawner: S) -> Result<(), SpawnFailed<E>> {
+    for task in order::by_key(tasks, |task| task.name) {
+        if let Err(error) = (task.spawn)(spawner) {
+            return Err(SpawnFailed {
+                name: task.name,
+                error,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Total number of task slots the registered tasks require.
+///
+/// For tasks registered through [`embassy_task!`] this never exceeds the
+/// capacity, which the build already checked.
+///
+/// [`embassy_task!`]: crate::embassy_task!
+pub fn task_count<S, E>(tasks: &[Task<S, E>]) -> usize {
+    tasks.iter().map(|task| task.pool_size).sum()
+}
+
+/// Error returned by [`spawn_all`] when a task fails to spawn.
+#[derive(Debug)]
+pub struct SpawnFailed<E> {
+    pub name: &'static str,
+    pub error: E,
+}
+
+impl<E> Display for SpawnFailed<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "failed to spawn task {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug> std::error::Error for SpawnFailed<E> {}
@@ -0,0 +1,95 @@
+//! Catalog of error codes contributed by any crate in the binary.
+//!
+//! Each crate registers the [`ErrorCode`]s it can produce into a distributed
+//! slice owned by the application. The binary then carries a complete catalog
+//! that can be checked for collisions with [`duplicate::check`] and dumped in
+//! a machine-readable form with [`catalog`].
+//!
+//! ```
+//! use linkme::error_code::{self, ErrorCode};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static ERROR_CODES: [ErrorCode] = [..];
+//!
+//! #[distributed_slice(ERROR_CODES)]
+//! static E_QUOTA: ErrorCode = ErrorCode {
+//!     code: "STORAGE-0001",
+//!     message: "storage quota exceeded",
+//!     docs_url: Some("https://example.com/errors/storage-0001"),
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&ERROR_CODES, |error| error.code).unwrap();
+//!
+//!     let error = ERROR_CODES.iter().find(|error| error.code == "STORAGE-0001");
+//!     assert_eq!(error.unwrap().message, "storage quota exceeded");
+//!
+//!     // [{"code":"STORAGE-0001","message":"storage quota exceeded",...}]
+//!     println!("{}", error_code::catalog(&ERROR_CODES));
+//! }
+//! ```
+//!
+//! [`duplicate::check`]: crate::duplicate::check
+
+use crate::order;
+use core::fmt::{self, Display, Write};
+
+/// Registration of one error code.
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub message: &'static str,
+    /// Page documenting the error and how to resolve it.
+    pub docs_url: Option<&'static str>,
+}
+
+/// Formats the registrations as a JSON array sorted by code.
+///
+/// Each element is an object with `code`, `message` and `docs_url` members,
+/// the last being `null` for codes without documentation.
+pub fn catalog(codes: &[ErrorCode]) -> Catalog<'_> {
+    Catalog { codes }
+}
+
+/// JSON catalog returned by [`catalog`].
+pub struct Catalog<'a> {
+    codes: &'a [ErrorCode],
+}
+
+impl Display for Catalog<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_char('[')?;
+        for (i, error) in order::by_key(self.codes, |error| error.code).enumerate() {
+            if i > 0 {
+                formatter.write_char(',')?;
+            }
+            formatter.write_str("{\"code\":")?;
+            write_json_string(formatter, error.code)?;
+            formatter.write_str(",\"message\":")?;
+            write_json_string(formatter, error.message)?;
+            formatter.write_str(",\"docs_url\":")?;
+            match error.docs_url {
+                Some(url) => write_json_string(formatter, url)?,
+                None => formatter.write_str("null")?,
+            }
+            formatter.write_char('}')?;
+        }
+        formatter.write_char(']')
+    }
+}
+
+fn write_json_string(formatter: &mut fmt::Formatter, string: &str) -> fmt::Result {
+    formatter.write_char('"')?;
+    for ch in string.chars() {
+        match ch {
+            '"' => formatter.write_str("\\\"")?,
+            '\\' => formatter.write_str("\\\\")?,
+            '\n' => formatter.write_str("\\n")?,
+            '\r' => formatter.write_str("\\r")?,
+            '\t' => formatter.write_str("\\t")?,
+            '\u{0}'..='\u{1f}' => write!(formatter, "\\u{:04x}", ch as u32)?,
+            _ => formatter.write_char(ch)?,
+        }
+    }
+    formatter.write_char('"')
+}
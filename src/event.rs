@@ -0,0 +1,59 @@
+use crate::private::TypeId;
+use crate::DistributedSlice;
+
+/// Event type declared with `#[linkme::event]`, which gives it its own
+/// distributed slice of subscribers.
+pub trait Event: Sized + 'static {
+    #[doc(hidden)]
+    fn private_subscribers() -> DistributedSlice<[Subscriber<Self>]>;
+}
+
+#[doc(hidden)]
+pub struct Subscriber<E> {
+    // Slices are told apart by name alone, so subscribers of another event
+    // type of the same name from a different crate end up in the same slice.
+    pub event: fn() -> TypeId,
+    pub handler: fn(&E),
+}
+
+/// Delivers an event to every function registered with `#[subscribe]` for the
+/// event's type.
+///
+/// An event type is declared with `#[linkme::event]`, which gives it a
+/// distributed slice of its own. A subscriber is a function taking a
+/// reference to the event. It may be defined in any crate linked into the
+/// final binary; no central list of handlers is maintained by hand.
+///
+/// ```
+/// #[linkme::event]
+/// pub struct UserCreated {
+///     pub name: &'static str,
+/// }
+///
+/// #[linkme::subscribe(UserCreated)]
+/// fn send_welcome_email(event: &UserCreated) {
+///     println!("welcome, {}!", event.name);
+/// }
+///
+/// #[linkme::subscribe(UserCreated)]
+/// fn update_metrics(_: &UserCreated) {
+///     /* ... */
+/// }
+///
+/// fn main() {
+///     linkme::publish(&UserCreated { name: "dtolnay" });
+/// }
+/// ```
+///
+/// Publishing only visits the subscribers of `E`. The order in which they are
+/// called is unspecified. Event types are told apart in the linker by name
+/// only, so two event types with the same name cannot be declared in one
+/// crate.
+pub fn publish<E: Event>(event: &E) {
+    let event_type = TypeId::of::<E>();
+    for subscriber in E::private_subscribers() {
+        if (subscriber.event)() == event_type {
+            (subscriber.handler)(event);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Feature flags declared next to the code they control.
+//!
+//! Any crate may register a [`FeatureFlag`] into a distributed slice owned by
+//! the application. The application then has a complete list of flags for its
+//! config system and its `--help` output, without a central list that has to
+//! be kept in sync by hand.
+//!
+//! ```
+//! use linkme::feature_flag::{self, FeatureFlag};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static FEATURE_FLAGS: [FeatureFlag] = [..];
+//!
+//! #[distributed_slice(FEATURE_FLAGS)]
+//! static PARALLEL_UPLOAD: FeatureFlag = FeatureFlag {
+//!     name: "parallel-upload",
+//!     default: false,
+//!     description: "Upload chunks over multiple connections",
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&FEATURE_FLAGS, |flag| flag.name).unwrap();
+//!
+//!     let flag = FEATURE_FLAGS.iter().find(|flag| flag.name == "parallel-upload");
+//!     assert!(!flag.unwrap().default);
+//!
+//!     print!("{}", feature_flag::help(&FEATURE_FLAGS));
+//! }
+//! ```
+
+use crate::order;
+use core::fmt::{self, Display};
+
+/// Declaration of one feature flag.
+pub struct FeatureFlag {
+    pub name: &'static str,
+    /// Whether the flag is enabled when the config does not mention it.
+    pub default: bool,
+    pub description: &'static str,
+}
+
+/// Formats the flags as an aligned table sorted by name, one flag per line.
+///
+/// ```text
+///   parallel-upload  Upload chunks over multiple connections [default: off]
+/// ```
+pub fn help(flags: &[FeatureFlag]) -> Help<'_> {
+    Help { flags }
+}
+
+/// Table of flags returned by [`help`].
+pub struct Help<'a> {
+    flags: &'a [FeatureFlag],
+}
+
+impl Display for Help<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.flags.iter().map(|flag| flag.name.len()).max();
+        let width = width.unwrap_or(0);
+        for flag in order::by_key(self.flags, |flag| flag.name) {
+            writeln!(
+                formatter,
+                "  {:width$}  {} [default: {}]",
+                flag.name,
+                flag.description,
+                if flag.default { "on" } else { "off" },
+                width = width,
+            )?;
+        }
+        Ok(())
+    }
+}
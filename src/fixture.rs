@@ -0,0 +1,90 @@
+//! Test fixtures that register themselves from test code.
+//!
+//! A [`Fixture`] registered into [`FIXTURES`] from a `#[cfg(test)]` module or
+//! an integration test is visible only to the test binary it is compiled
+//! into, following the rules for [registrations in tests]. Shared setup can
+//! therefore live next to the tests that need it and be found by name or by
+//! module, without a central list of fixtures.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::fixture::{self, Fixture, FIXTURES};
+//!
+//! // In a #[cfg(test)] module, or anywhere in an integration test.
+//! #[distributed_slice(FIXTURES)]
+//! static TEMP_DIR: Fixture = linkme::fixture!("temp_dir", || {
+//!     /* create a scratch directory */
+//! });
+//!
+//! // #[test]
+//! fn test_writes_file() {
+//!     fixture::setup("temp_dir");
+//!     /* ... */
+//! }
+//! #
+//! # fn main() {
+//! #     linkme::duplicate::check(&FIXTURES, |fixture| fixture.name).unwrap();
+//! #     test_writes_file();
+//! # }
+//! ```
+//!
+//! [registrations in tests]: crate::DistributedSlice#registrations-in-tests
+
+use crate::distributed_slice;
+
+/// Fixtures registered by code in the current test binary.
+///
+/// These have the type `DistributedSlice<[Fixture]>`.
+pub use self::LINKME_FIXTURE as FIXTURES;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_FIXTURE: [Fixture] = [..];
+
+/// Setup routine shared between tests.
+pub struct Fixture {
+    pub name: &'static str,
+    /// Module that registered the fixture, as given by `module_path!()`.
+    pub module: &'static str,
+    pub setup: fn(),
+}
+
+/// Builds a [`Fixture`] from a name and setup function, recording the module
+/// the macro is called from.
+#[macro_export]
+macro_rules! fixture {
+    ($name:expr, $setup:expr) => {
+        $crate::fixture::Fixture {
+            name: $name,
+            module: module_path!(),
+            setup: $setup,
+        }
+    };
+}
+
+/// Finds the fixture with the given name.
+pub fn find(name: &str) -> Option<&'static Fixture> {
+    FIXTURES.iter().find(|fixture| fixture.name == name)
+}
+
+/// Runs the setup of the fixture with the given name.
+///
+/// # Panics
+///
+/// Panics if no fixture with that name is registered in this test binary.
+pub fn setup(name: &str) {
+    match find(name) {
+        Some(fixture) => (fixture.setup)(),
+        None => panic!("no fixture named {:?} in this test binary", name),
+    }
+}
+
+/// Fixtures registered by `module` or any module nested in it.
+pub fn in_module(module: &str) -> impl Iterator<Item = &'static Fixture> + '_ {
+    FIXTURES.iter().filter(move |fixture| {
+        fixture.module == module
+            || (fixture.module.starts_with(module)
+                && fixture.module[module.len()..].starts_with("::"))
+    })
+}
@@ -0,0 +1,81 @@
+//! GraphQL fields contributed by any crate to a modular schema.
+//!
+//! Each crate registers the query, mutation and subscription fields it
+//! implements as [`FieldDef`]s into a distributed slice owned by the server.
+//! The server then assembles each root object from all contributed fields with
+//! [`assemble`], instead of a monolithic schema module listing every field.
+//!
+//! Nothing here depends on a particular GraphQL library. The field type `F` is
+//! whatever the library's schema builder accepts, for example
+//! `async_graphql::dynamic::Field`, constructed at startup by the registered
+//! factory function.
+//!
+//! ```
+//! # mod async_graphql {
+//! #     pub mod dynamic {
+//! #         pub struct Object(pub &'static str, pub Vec<Field>);
+//! #         impl Object {
+//! #             pub fn new(name: &'static str) -> Self { Object(name, Vec::new()) }
+//! #             pub fn field(mut self, field: Field) -> Self { self.1.push(field); self }
+//! #         }
+//! #         pub struct Field(pub &'static str);
+//! #     }
+//! # }
+//! use async_graphql::dynamic::{Field, Object};
+//! use linkme::graphql::{self, FieldDef, Root};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static FIELDS: [FieldDef<Field>] = [..];
+//!
+//! // In the billing crate.
+//! #[distributed_slice(FIELDS)]
+//! static INVOICES: FieldDef<Field> = FieldDef {
+//!     root: Root::Query,
+//!     name: "invoices",
+//!     field: || Field("invoices" /* , type, resolver */),
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&FIELDS, |def| (def.root, def.name)).unwrap();
+//!
+//!     let query = graphql::assemble(&FIELDS, Root::Query, Object::new("Query"), Object::field);
+//!     let mutation = graphql::assemble(&FIELDS, Root::Mutation, Object::new("Mutation"), Object::field);
+//!     /* register query and mutation with the schema builder */
+//! }
+//! ```
+
+use crate::order;
+
+/// Root operation type that a field belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Root {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+/// Field contributed to one of the root operation types.
+pub struct FieldDef<F: 'static> {
+    pub root: Root,
+    /// Name of the field within its root object.
+    pub name: &'static str,
+    /// Builds the library-specific field definition, including its resolver.
+    pub field: fn() -> F,
+}
+
+/// Adds every field registered for `root` to `object`.
+///
+/// Fields are added in order of name so the resulting schema does not depend
+/// on link order. The `add` function is the builder method of the GraphQL
+/// library, which takes the object by value and returns it with the field
+/// added.
+pub fn assemble<O, F>(fields: &[FieldDef<F>], root: Root, object: O, add: impl Fn(O, F) -> O) -> O {
+    let mut object = object;
+    for def in order::by_key(fields, |def| def.name) {
+        if def.root == root {
+            object = add(object, (def.field)());
+        }
+    }
+    object
+}
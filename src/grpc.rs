@@ -0,0 +1,64 @@
+//! gRPC services contributed by feature crates to one server.
+//!
+//! A microservice binary assembled from feature crates links in one
+//! [`Service`] per crate that implements a gRPC service. The binary builds its
+//! server by folding every registered service into the server builder with
+//! [`serve`], so enabling a feature crate is enough to expose its service and
+//! there is no hand-maintained list of services to forget to update.
+//!
+//! The builder type `B` is whatever the gRPC library threads through service
+//! registration, for example `tonic::transport::server::Router` or
+//! `tonic::service::Routes`.
+//!
+//! ```
+//! # mod tonic {
+//! #     pub mod service {
+//! #         #[derive(Default)]
+//! #         pub struct Routes(pub Vec<&'static str>);
+//! #         impl Routes {
+//! #             pub fn add_service(mut self, name: &'static str) -> Self { self.0.push(name); self }
+//! #         }
+//! #     }
+//! # }
+//! use linkme::grpc::{self, Service};
+//! use linkme::{distributed_slice, duplicate};
+//! use tonic::service::Routes;
+//!
+//! #[distributed_slice]
+//! pub static SERVICES: [Service<Routes>] = [..];
+//!
+//! // In the accounts crate.
+//! #[distributed_slice(SERVICES)]
+//! static ACCOUNTS: Service<Routes> = Service {
+//!     name: "accounts.v1.Accounts",
+//!     add: |routes| routes.add_service("accounts.v1.Accounts" /* AccountsServer::new(...) */),
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&SERVICES, |service| service.name).unwrap();
+//!     let routes = grpc::serve(&SERVICES, Routes::default());
+//!     /* hand routes to the transport */
+//! }
+//! ```
+
+use crate::order;
+
+/// Service contributed to the server.
+pub struct Service<B: 'static> {
+    /// Fully qualified gRPC service name, such as `"package.v1.Service"`.
+    pub name: &'static str,
+    /// Adds the service to the server builder.
+    pub add: fn(B) -> B,
+}
+
+/// Adds every registered service to `builder`.
+///
+/// Services are added in order of name so the resulting server does not
+/// depend on link order.
+pub fn serve<B>(services: &[Service<B>], builder: B) -> B {
+    let mut builder = builder;
+    for service in order::by_key(services, |service| service.name) {
+        builder = (service.add)(builder);
+    }
+    builder
+}
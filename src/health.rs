@@ -0,0 +1,163 @@
+//! Health checks contributed by every crate that makes up a service.
+//!
+//! Each crate registers a [`HealthCheck`] for the resources it owns into a
+//! distributed slice owned by the service, and the service's health endpoint
+//! evaluates all of them with [`run`]. A service that wants separate liveness
+//! and readiness endpoints declares one slice for each.
+//!
+//! Requires the `std` feature.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::health::{self, CheckResult, HealthCheck};
+//! use std::time::Duration;
+//!
+//! #[distributed_slice]
+//! pub static HEALTH_CHECKS: [HealthCheck] = [..];
+//!
+//! #[distributed_slice(HEALTH_CHECKS)]
+//! static DATABASE: HealthCheck = HealthCheck {
+//!     name: "database",
+//!     check: || {
+//!         /* ping the database */
+//!         CheckResult::Healthy
+//!     },
+//! };
+//!
+//! fn main() {
+//!     let report = health::run(&HEALTH_CHECKS, Some(Duration::from_secs(5)));
+//!     assert!(report.is_healthy());
+//!     print!("{}", report);
+//! }
+//! ```
+
+use std::fmt::{self, Display};
+use std::panic;
+use std::string::String;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::vec;
+use std::vec::Vec;
+
+/// Named check of one dependency or subsystem.
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub check: fn() -> CheckResult,
+}
+
+/// Result reported by a check function.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CheckResult {
+    Healthy,
+    Unhealthy(String),
+}
+
+/// Result of one check within a [`Report`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Healthy,
+    Unhealthy(String),
+    /// The check did not finish before the timeout.
+    TimedOut,
+    /// The check function panicked.
+    Panicked,
+}
+
+/// Outcome of every check, in the order of the slice.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub checks: Vec<(&'static str, Outcome)>,
+}
+
+impl Report {
+    /// Whether every check came back healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|(_, outcome)| *outcome == Outcome::Healthy)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (name, outcome) in &self.checks {
+            match outcome {
+                Outcome::Healthy => writeln!(formatter, "{}: healthy", name)?,
+                Outcome::Unhealthy(message) => {
+                    writeln!(formatter, "{}: unhealthy: {}", name, message)?;
+                }
+                Outcome::TimedOut => writeln!(formatter, "{}: timed out", name)?,
+                Outcome::Panicked => writeln!(formatter, "{}: panicked", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs every check and reports them in the order of the slice.
+///
+/// Without a timeout the checks run one after another on the calling thread.
+///
+/// With a timeout every check runs on a thread of its own, so all of them
+/// start at once and each has the full `timeout` to finish. A check that has
+/// not finished by then is reported as [`Outcome::TimedOut`], without holding
+/// up the report of any other check. Threads cannot be interrupted: a hung
+/// check keeps its thread alive in the background until it returns.
+pub fn run(checks: &[HealthCheck], timeout: Option<Duration>) -> Report {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => {
+            return Report {
+                checks: checks
+                    .iter()
+                    .map(|health_check| (health_check.name, evaluate(health_check.check)))
+                    .collect(),
+            };
+        }
+    };
+
+    let deadline = Instant::now() + timeout;
+    let (sender, receiver) = mpsc::channel();
+    for (i, health_check) in checks.iter().enumerate() {
+        let sender = sender.clone();
+        let check = health_check.check;
+        thread::spawn(move || {
+            // Fails if the report was already made without this check.
+            let _ = sender.send((i, evaluate(check)));
+        });
+    }
+    drop(sender);
+
+    let mut outcomes = vec![Outcome::TimedOut; checks.len()];
+    let mut remaining = checks.len();
+    while remaining > 0 {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        match receiver.recv_timeout(deadline - now) {
+            Ok((i, outcome)) => {
+                outcomes[i] = outcome;
+                remaining -= 1;
+            }
+            Err(_) => break,
+        }
+    }
+
+    Report {
+        checks: checks
+            .iter()
+            .map(|health_check| health_check.name)
+            .zip(outcomes)
+            .collect(),
+    }
+}
+
+fn evaluate(check: fn() -> CheckResult) -> Outcome {
+    match panic::catch_unwind(check) {
+        Ok(CheckResult::Healthy) => Outcome::Healthy,
+        Ok(CheckResult::Unhealthy(message)) => Outcome::Unhealthy(message),
+        Err(_) => Outcome::Panicked,
+    }
+}
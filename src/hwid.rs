@@ -0,0 +1,163 @@
+//! Hardware ID match tables for PCI, USB and ACPI drivers.
+//!
+//! Like Linux's `pci_device_id` and `usb_device_id` tables, each driver crate
+//! registers an [`IdMatch`] for every device or device class it supports into
+//! a distributed slice owned by the kernel or hypervisor. A bus enumerator
+//! then finds the driver for each device it discovers with [`find`].
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::hwid::{self, Bus, HardwareId, IdMatch, IdPattern};
+//!
+//! pub struct PciDevice {
+//!     pub id: HardwareId,
+//!     pub bar0: u64,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static DRIVERS: [IdMatch<PciDevice>] = [..];
+//!
+//! // In the e1000 driver crate.
+//! #[distributed_slice(DRIVERS)]
+//! static E1000: IdMatch<PciDevice> = IdMatch {
+//!     pattern: IdPattern::device(Bus::Pci, 0x8086, 0x100e),
+//!     probe: |dev| { /* map dev.bar0 */ },
+//! };
+//!
+//! // In a generic NVMe driver crate: class 01, subclass 08, any interface.
+//! #[distributed_slice(DRIVERS)]
+//! static NVME: IdMatch<PciDevice> = IdMatch {
+//!     pattern: IdPattern::class(Bus::Pci, 0x01_08_00, 0xff_ff_00),
+//!     probe: |dev| { /* ... */ },
+//! };
+//!
+//! fn main() {
+//!     let dev = PciDevice {
+//!         id: HardwareId {
+//!             bus: Bus::Pci,
+//!             vendor: 0x144d,
+//!             device: 0xa808,
+//!             class: 0x01_08_02,
+//!         },
+//!         bar0: 0xfe00_0000,
+//!     };
+//!     assert!(hwid::probe(&DRIVERS, &dev.id, &dev));
+//! }
+//! ```
+
+/// Wildcard matching any vendor or device ID.
+pub const ANY: u32 = !0;
+
+/// Bus on which a device was enumerated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Bus {
+    Pci,
+    Usb,
+    /// ACPI devices, identified by the compressed EISA form of their `_HID`
+    /// in `vendor` and `device`.
+    Acpi,
+}
+
+/// Identity of a device reported by the bus during enumeration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HardwareId {
+    pub bus: Bus,
+    pub vendor: u32,
+    pub device: u32,
+    /// Class code, such as PCI's 24-bit class, subclass and programming
+    /// interface.
+    pub class: u32,
+}
+
+/// Set of hardware IDs a driver supports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdPattern {
+    pub bus: Bus,
+    /// Vendor ID to match, or [`ANY`].
+    pub vendor: u32,
+    /// Device ID to match, or [`ANY`].
+    pub device: u32,
+    /// Class code to match, in the bits selected by `class_mask`.
+    pub class: u32,
+    /// Bits of the class code that must match. Zero ignores the class.
+    pub class_mask: u32,
+}
+
+impl IdPattern {
+    /// Matches one specific device.
+    pub const fn device(bus: Bus, vendor: u32, device: u32) -> Self {
+        IdPattern {
+            bus,
+            vendor,
+            device,
+            class: 0,
+            class_mask: 0,
+        }
+    }
+
+    /// Matches every device whose class code agrees with `class` in the bits
+    /// selected by `mask`.
+    pub const fn class(bus: Bus, class: u32, mask: u32) -> Self {
+        IdPattern {
+            bus,
+            vendor: ANY,
+            device: ANY,
+            class,
+            class_mask: mask,
+        }
+    }
+
+    /// Whether the device with the given ID is in this set.
+    pub fn matches(&self, id: &HardwareId) -> bool {
+        self.bus == id.bus
+            && (self.vendor == ANY || self.vendor == id.vendor)
+            && (self.device == ANY || self.device == id.device)
+            && (id.class ^ self.class) & self.class_mask == 0
+    }
+
+    fn specificity(&self) -> (bool, bool, u32) {
+        (
+            self.vendor != ANY,
+            self.device != ANY,
+            self.class_mask.count_ones(),
+        )
+    }
+}
+
+/// Driver entry for a set of hardware IDs, probing devices of type `D`.
+pub struct IdMatch<D: 'static> {
+    pub pattern: IdPattern,
+    pub probe: fn(&D),
+}
+
+/// Finds the driver for the device with the given ID.
+///
+/// When several entries match, the most specific one wins: one naming the
+/// vendor beats one that does not, then likewise for the device, then the
+/// entry comparing more class bits. Remaining ties go to the entry that comes
+/// first in the slice.
+pub fn find<'a, D>(table: &'a [IdMatch<D>], id: &HardwareId) -> Option<&'a IdMatch<D>> {
+    let mut best: Option<&IdMatch<D>> = None;
+    for entry in table {
+        if entry.pattern.matches(id)
+            && best.map_or(true, |best| {
+                entry.pattern.specificity() > best.pattern.specificity()
+            })
+        {
+            best = Some(entry);
+        }
+    }
+    best
+}
+
+/// Probes `device` with the driver matching `id`, returning whether a driver
+/// was found.
+pub fn probe<D>(table: &[IdMatch<D>], id: &HardwareId, device: &D) -> bool {
+    match find(table, id) {
+        Some(entry) => {
+            (entry.probe)(device);
+            true
+        }
+        None => false,
+    }
+}
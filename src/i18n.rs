@@ -0,0 +1,107 @@
+//! Translation catalogs contributed by any crate in the binary.
+//!
+//! Each crate ships the translations for its own messages as a [`Catalog`]
+//! registered into a distributed slice owned by the application. Lookups
+//! consult all catalogs at once, so there is no startup step that merges
+//! per-crate tables into a map.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::i18n::{self, Catalog};
+//!
+//! #[distributed_slice]
+//! pub static CATALOGS: [Catalog] = [..];
+//!
+//! #[distributed_slice(CATALOGS)]
+//! static BILLING_DE: Catalog = Catalog {
+//!     locale: "de",
+//!     domain: "billing",
+//!     messages: &[("invoice", "Rechnung"), ("refund", "Erstattung")],
+//! };
+//!
+//! fn main() {
+//!     i18n::validate(&CATALOGS).unwrap();
+//!
+//!     let text = i18n::lookup(&CATALOGS, "de", "billing", "invoice");
+//!     assert_eq!(text, Some("Rechnung"));
+//! }
+//! ```
+
+use crate::duplicate;
+use core::fmt::{self, Display};
+
+/// Translations of the messages of one domain into one locale.
+pub struct Catalog {
+    pub locale: &'static str,
+    pub domain: &'static str,
+    /// Pairs of message key and translated text.
+    pub messages: &'static [(&'static str, &'static str)],
+}
+
+/// Looks up the translation of `key` across all catalogs for the given locale
+/// and domain.
+pub fn lookup(catalogs: &[Catalog], locale: &str, domain: &str, key: &str) -> Option<&'static str> {
+    messages(catalogs)
+        .find(|&(catalog, (k, _))| {
+            catalog.locale == locale && catalog.domain == domain && *k == key
+        })
+        .map(|(_, (_, text))| *text)
+}
+
+/// Checks that no message is translated two different ways within the same
+/// locale and domain.
+///
+/// Registering an identical translation twice is not considered a conflict.
+pub fn validate(catalogs: &[Catalog]) -> Result<(), Conflict> {
+    let conflict = duplicate::find(
+        messages(catalogs),
+        |&(a, (a_key, a_text)), &(b, (b_key, b_text))| {
+            a.locale == b.locale && a.domain == b.domain && a_key == b_key && a_text != b_text
+        },
+    );
+    match conflict {
+        None => Ok(()),
+        Some(((catalog, (key, first)), (_, (_, second)))) => Err(Conflict {
+            locale: catalog.locale,
+            domain: catalog.domain,
+            key,
+            first,
+            second,
+        }),
+    }
+}
+
+fn messages(
+    catalogs: &[Catalog],
+) -> impl Iterator<Item = (&Catalog, &(&'static str, &'static str))> + Clone {
+    catalogs.iter().flat_map(|catalog| {
+        catalog
+            .messages
+            .iter()
+            .map(move |message| (catalog, message))
+    })
+}
+
+/// Error returned by [`validate`] for a message with two different
+/// translations.
+#[derive(Debug)]
+pub struct Conflict {
+    pub locale: &'static str,
+    pub domain: &'static str,
+    pub key: &'static str,
+    pub first: &'static str,
+    pub second: &'static str,
+}
+
+impl Display for Conflict {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "conflicting translations of {}/{}/{}: {:?} and {:?}",
+            self.locale, self.domain, self.key, self.first, self.second,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Conflict {}
@@ -0,0 +1,365 @@
+//! Indices over distributed slices, built once on first access.
+//!
+//! Finding an entry by name in a distributed slice is a linear scan, which is
+//! fine for a handful of entries and too slow for a registry of thousands
+//! consulted on a hot path. Both index types here sit in a static next to the
+//! slice and build their index the first time they are used, without needing
+//! `std` or a `lazy_static`:
+//!
+//! - [`IndexedSlice`] builds a perfect hash index over a string key. Every
+//!   lookup hashes the key once and compares it against a single candidate
+//!   entry.
+//! - [`Indexed`] sorts the entries by any ordered key, supporting lookups of
+//!   every entry sharing a key and iteration in key order. A registry can
+//!   have several of these over the same slice, one per key.
+//!
+//! A registry whose entries are key-value pairs is better declared as a
+//! [`DistributedMap`], which indexes them itself.
+//!
+//! Requires the `alloc` feature.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::indexed::IndexedSlice;
+//!
+//! pub struct Opcode {
+//!     pub mnemonic: &'static str,
+//!     pub encoding: u8,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static OPCODES: [Opcode] = [..];
+//!
+//! pub static BY_MNEMONIC: IndexedSlice<Opcode> = IndexedSlice::new(&OPCODES, |op| op.mnemonic);
+//!
+//! #[distributed_slice(OPCODES)]
+//! static NOP: Opcode = Opcode {
+//!     mnemonic: "nop",
+//!     encoding: 0x90,
+//! };
+//!
+//! fn main() {
+//!     assert_eq!(BY_MNEMONIC.get("nop").unwrap().encoding, 0x90);
+//!     assert!(BY_MNEMONIC.get("hlt").is_none());
+//! }
+//! ```
+//!
+//! [`DistributedMap`]: crate::DistributedMap
+
+use crate::once::Once;
+use crate::DistributedSlice;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{self, Reverse};
+use core::fmt::{self, Display};
+use core::slice;
+
+/// Distributed slice together with a perfect hash index over a string key of
+/// its entries, built on first access.
+pub struct IndexedSlice<T: 'static> {
+    slice: &'static DistributedSlice<[T]>,
+    key: fn(&T) -> &str,
+    index: Once<Index>,
+}
+
+impl<T> IndexedSlice<T> {
+    /// Creates an index over `slice` keyed by `key`, for use as a static.
+    pub const fn new(slice: &'static DistributedSlice<[T]>, key: fn(&T) -> &str) -> Self {
+        IndexedSlice {
+            slice,
+            key,
+            index: Once::new(),
+        }
+    }
+
+    /// Finds the entry with the given key.
+    ///
+    /// If several entries share the key, the one first in the slice is found.
+    pub fn get(&self, key: &str) -> Option<&'static T> {
+        let i = self.index().table.lookup(key)?;
+        let entry = &self.slice.static_slice()[i];
+        if (self.key)(entry) == key {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// Builds the index now rather than on first lookup, reporting whether
+    /// any key is shared by two entries.
+    pub fn validate(&self) -> Result<(), DuplicateKey> {
+        match self.index().duplicate {
+            None => Ok(()),
+            Some(i) => Err(DuplicateKey {
+                key: (self.key)(&self.slice.static_slice()[i]),
+            }),
+        }
+    }
+
+    fn index(&self) -> &Index {
+        self.index
+            .get_or_init(|| Index::build(self.slice.static_slice(), self.key))
+    }
+}
+
+/// Distributed slice of `D` together with an index of its entries sorted by a
+/// key of type `K`, built on first access.
+///
+/// ```
+/// use linkme::distributed_slice;
+/// use linkme::indexed::Indexed;
+///
+/// pub struct Route {
+///     pub method: &'static str,
+///     pub path: &'static str,
+/// }
+///
+/// #[distributed_slice]
+/// pub static ROUTES: [Route] = [..];
+///
+/// pub static BY_METHOD: Indexed<Route, &str> = Indexed::new(&ROUTES, |route| route.method);
+///
+/// #[distributed_slice(ROUTES)]
+/// static LIST_USERS: Route = Route {
+///     method: "GET",
+///     path: "/users",
+/// };
+///
+/// fn main() {
+///     for route in BY_METHOD.get_all(&"GET") {
+///         println!("GET {}", route.path);
+///     }
+/// }
+/// ```
+pub struct Indexed<D: 'static, K> {
+    slice: &'static DistributedSlice<[D]>,
+    key: fn(&D) -> K,
+    index: Once<Vec<(K, usize)>>,
+}
+
+impl<D, K: Ord> Indexed<D, K> {
+    /// Creates an index over `slice` keyed by `key`, for use as a static.
+    pub const fn new(slice: &'static DistributedSlice<[D]>, key: fn(&D) -> K) -> Self {
+        Indexed {
+            slice,
+            key,
+            index: Once::new(),
+        }
+    }
+
+    /// Finds the entry with the given key.
+    ///
+    /// If several entries share the key, the one first in the slice is found.
+    pub fn get(&self, key: &K) -> Option<&'static D> {
+        self.get_all(key).next()
+    }
+
+    /// Every entry with the given key, in slice order.
+    pub fn get_all(&self, key: &K) -> Iter<'_, D, K> {
+        let index = self.index();
+        let start = index.partition_point(|(k, _)| k < key);
+        let len = index[start..].partition_point(|(k, _)| k == key);
+        Iter {
+            entries: self.slice.static_slice(),
+            index: index[start..start + len].iter(),
+        }
+    }
+
+    /// Every entry in order of key, with equal keys in slice order.
+    pub fn iter(&self) -> Iter<'_, D, K> {
+        Iter {
+            entries: self.slice.static_slice(),
+            index: self.index().iter(),
+        }
+    }
+
+    fn index(&self) -> &[(K, usize)] {
+        self.index.get_or_init(|| {
+            let entries = self.slice.static_slice();
+            let mut index: Vec<(K, usize)> = entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| ((self.key)(entry), i))
+                .collect();
+            index.sort_by(|a, b| a.0.cmp(&b.0));
+            index
+        })
+    }
+}
+
+impl<'a, D, K: Ord> IntoIterator for &'a Indexed<D, K> {
+    type Item = &'static D;
+    type IntoIter = Iter<'a, D, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over entries of an [`Indexed`] slice.
+pub struct Iter<'a, D: 'static, K> {
+    entries: &'static [D],
+    index: slice::Iter<'a, (K, usize)>,
+}
+
+impl<D, K> Iterator for Iter<'_, D, K> {
+    type Item = &'static D;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, i) = self.index.next()?;
+        Some(&self.entries[*i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.index.size_hint()
+    }
+}
+
+impl<D, K> ExactSizeIterator for Iter<'_, D, K> {}
+
+struct Index {
+    table: Table,
+    // Position of an entry whose key repeats an earlier entry's.
+    duplicate: Option<usize>,
+}
+
+// Hash and displace: each key hashes to a bucket, and each bucket is assigned
+// the first displacement under which all of its keys land in distinct free
+// slots. A seed under which some bucket finds no such displacement is
+// replaced by the next one, so every lookup stays a single probe.
+struct Table {
+    seed: u64,
+    displacements: Vec<u32>,
+    slots: Vec<usize>,
+}
+
+const EMPTY: usize = usize::MAX;
+const MAX_DISPLACEMENT: u32 = 1 << 16;
+
+impl Index {
+    fn build<T>(entries: &[T], key: fn(&T) -> &str) -> Self {
+        let mut duplicate = None;
+        let mut keys: Vec<(usize, &str)> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (i, key(entry)))
+            .collect();
+        keys.sort_by(|a, b| a.1.cmp(b.1).then(a.0.cmp(&b.0)));
+        keys.dedup_by(|later, earlier| {
+            let same = later.1 == earlier.1;
+            if same {
+                duplicate = Some(duplicate.map_or(later.0, |i: usize| cmp::min(i, later.0)));
+            }
+            same
+        });
+
+        let mut seed = 0;
+        loop {
+            if let Some(table) = Table::build(&keys, seed) {
+                return Index { table, duplicate };
+            }
+            seed += 1;
+        }
+    }
+}
+
+impl Table {
+    // The keys are distinct, each paired with the entry it finds.
+    fn build(keys: &[(usize, &str)], seed: u64) -> Option<Self> {
+        let len = keys.len();
+        let buckets_len = len / 4 + 1;
+        let mut buckets: Vec<Vec<(usize, u64)>> = vec![Vec::new(); buckets_len];
+        for &(i, k) in keys {
+            let hash = hash(k, seed);
+            buckets[reduce(hash, buckets_len)].push((i, hash));
+        }
+
+        let mut order: Vec<usize> = (0..buckets_len).collect();
+        order.sort_by_key(|&b| Reverse(buckets[b].len()));
+
+        let mut displacements = vec![0; buckets_len];
+        let mut slots = vec![EMPTY; len + len / 4 + 1];
+        let mut placed = Vec::new();
+        for b in order {
+            let bucket = &buckets[b];
+            let mut displacement = 0;
+            loop {
+                placed.clear();
+                for &(_, hash) in bucket {
+                    let slot = slot(hash, displacement, slots.len());
+                    if slots[slot] != EMPTY || placed.contains(&slot) {
+                        break;
+                    }
+                    placed.push(slot);
+                }
+                if placed.len() == bucket.len() {
+                    break;
+                }
+                displacement += 1;
+                if displacement == MAX_DISPLACEMENT {
+                    return None;
+                }
+            }
+            for (&(i, _), &slot) in bucket.iter().zip(&placed) {
+                slots[slot] = i;
+            }
+            displacements[b] = displacement;
+        }
+
+        Some(Table {
+            seed,
+            displacements,
+            slots,
+        })
+    }
+
+    fn lookup(&self, key: &str) -> Option<usize> {
+        let hash = hash(key, self.seed);
+        let bucket = reduce(hash, self.displacements.len());
+        let slot = slot(hash, self.displacements[bucket], self.slots.len());
+        match self.slots[slot] {
+            EMPTY => None,
+            i => Some(i),
+        }
+    }
+}
+
+fn hash(key: &str, seed: u64) -> u64 {
+    // FNV-1a, from an offset basis perturbed by the seed
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325 ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    for &byte in key.as_bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn slot(hash: u64, displacement: u32, len: usize) -> usize {
+    // splitmix64 finalizer
+    let mut z = hash ^ u64::from(displacement).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    reduce(z, len)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn reduce(hash: u64, len: usize) -> usize {
+    // The remainder is less than len, so it fits.
+    (hash % len as u64) as usize
+}
+
+/// Error returned by [`IndexedSlice::validate`] when two entries share a key.
+#[derive(Debug)]
+pub struct DuplicateKey {
+    pub key: &'static str,
+}
+
+impl Display for DuplicateKey {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "key {:?} is registered twice", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DuplicateKey {}
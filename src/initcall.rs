@@ -0,0 +1,164 @@
+//! Kernel-style initialization calls run in fixed levels.
+//!
+//! Like Linux initcalls, each initialization function is registered into one
+//! of five levels, [`EARLY`], [`CORE`], [`SUBSYS`], [`DEVICE`] and [`LATE`],
+//! each backed by its own link section. [`run`] executes every level to
+//! completion before starting the next, so a device driver can rely on the
+//! subsystems it plugs into having been initialized, without any crate
+//! needing to know about the others. Within a level, calls run in the order
+//! the linker placed them, which should not be relied upon.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::initcall::{self, Initcall, CORE, DEVICE};
+//!
+//! #[distributed_slice(CORE)]
+//! static ALLOCATOR: Initcall = Initcall {
+//!     name: "allocator",
+//!     call: || { /* set up the heap */ },
+//! };
+//!
+//! #[distributed_slice(DEVICE)]
+//! static UART: Initcall = Initcall {
+//!     name: "uart",
+//!     call: || { /* probe the serial port, allocating buffers */ },
+//! };
+//!
+//! fn main() {
+//!     initcall::run();
+//! }
+//! ```
+
+use crate::distributed_slice;
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Calls run before anything else, such as setting up the console.
+///
+/// These have the type `DistributedSlice<[Initcall]>`.
+pub use self::LINKME_INIT0 as EARLY;
+
+/// Calls setting up core facilities such as memory allocation.
+///
+/// These have the type `DistributedSlice<[Initcall]>`.
+pub use self::LINKME_INIT1 as CORE;
+
+/// Calls initializing subsystems such as buses and filesystems.
+///
+/// These have the type `DistributedSlice<[Initcall]>`.
+pub use self::LINKME_INIT2 as SUBSYS;
+
+/// Calls registering and probing device drivers.
+///
+/// These have the type `DistributedSlice<[Initcall]>`.
+pub use self::LINKME_INIT3 as DEVICE;
+
+/// Calls run after everything else has been initialized.
+///
+/// These have the type `DistributedSlice<[Initcall]>`.
+pub use self::LINKME_INIT4 as LATE;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_INIT0: [Initcall] = [..];
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_INIT1: [Initcall] = [..];
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_INIT2: [Initcall] = [..];
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_INIT3: [Initcall] = [..];
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_INIT4: [Initcall] = [..];
+
+/// Initialization function registered at one of the levels.
+pub struct Initcall {
+    /// Name used when reporting timings.
+    pub name: &'static str,
+    pub call: fn(),
+}
+
+/// Initialization level, in the order the levels are run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Early,
+    Core,
+    Subsys,
+    Device,
+    Late,
+}
+
+impl Level {
+    /// Every level, in the order they are run.
+    pub const ALL: [Level; 5] = [
+        Level::Early,
+        Level::Core,
+        Level::Subsys,
+        Level::Device,
+        Level::Late,
+    ];
+
+    /// Calls registered at this level.
+    pub fn calls(self) -> &'static [Initcall] {
+        match self {
+            Level::Early => &LINKME_INIT0,
+            Level::Core => &LINKME_INIT1,
+            Level::Subsys => &LINKME_INIT2,
+            Level::Device => &LINKME_INIT3,
+            Level::Late => &LINKME_INIT4,
+        }
+    }
+
+    /// Lowercase name of the level, such as `"subsys"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Early => "early",
+            Level::Core => "core",
+            Level::Subsys => "subsys",
+            Level::Device => "device",
+            Level::Late => "late",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+/// Runs every registered call, level by level.
+pub fn run() {
+    for &level in &Level::ALL {
+        for initcall in level.calls() {
+            (initcall.call)();
+        }
+    }
+}
+
+/// Runs every registered call like [`run`], reporting how long each took.
+///
+/// The report is invoked after each call returns.
+#[cfg(feature = "std")]
+pub fn run_timed(mut report: impl FnMut(Level, &Initcall, Duration)) {
+    for &level in &Level::ALL {
+        for initcall in level.calls() {
+            let start = Instant::now();
+            (initcall.call)();
+            report(level, initcall, start.elapsed());
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Dependency injection from constructors registered by any crate.
+//!
+//! Each crate registers a [`Provider`] saying how to construct one of its
+//! types, possibly from other types. A [`Resolver`] then instantiates the
+//! graph on demand, constructing every type at most once and handing out
+//! shared references to the instances.
+//!
+//! Requires the `alloc` feature.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::inject::{Provider, Resolver};
+//! use std::rc::Rc;
+//!
+//! pub struct Config {
+//!     url: &'static str,
+//! }
+//!
+//! pub struct Database {
+//!     config: Rc<Config>,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static PROVIDERS: [Provider] = [..];
+//!
+//! #[distributed_slice(PROVIDERS)]
+//! static CONFIG: Provider = Provider::new::<Config>(|_| {
+//!     Ok(Box::new(Config {
+//!         url: "postgres://localhost",
+//!     }))
+//! });
+//!
+//! #[distributed_slice(PROVIDERS)]
+//! static DATABASE: Provider = Provider::new::<Database>(|resolver| {
+//!     let config = resolver.resolve::<Config>()?;
+//!     Ok(Box::new(Database { config }))
+//! });
+//!
+//! fn main() {
+//!     let mut resolver = Resolver::new(&PROVIDERS);
+//!     let database = resolver.resolve::<Database>().unwrap();
+//!     assert_eq!(database.config.url, "postgres://localhost");
+//! }
+//! ```
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::any::{self, Any, TypeId};
+use core::fmt::{self, Display};
+
+/// Constructor for one type.
+pub struct Provider {
+    type_id: fn() -> TypeId,
+    construct: fn(&mut Resolver) -> Result<Box<dyn Any>, Error>,
+}
+
+impl Provider {
+    /// Registers `construct` as the way to obtain a `T`.
+    ///
+    /// The constructor must return a box containing a `T`. Its dependencies
+    /// are obtained from the resolver that it is given.
+    pub const fn new<T: 'static>(
+        construct: fn(&mut Resolver) -> Result<Box<dyn Any>, Error>,
+    ) -> Self {
+        Provider {
+            type_id: TypeId::of::<T>,
+            construct,
+        }
+    }
+}
+
+/// Instantiates types from a set of providers.
+///
+/// Each type is constructed the first time it is resolved and the same
+/// instance is returned by every later call.
+pub struct Resolver<'a> {
+    providers: &'a [Provider],
+    instances: Vec<(TypeId, Rc<dyn Any>)>,
+    in_progress: Vec<TypeId>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(providers: &'a [Provider]) -> Self {
+        Resolver {
+            providers,
+            instances: Vec::new(),
+            in_progress: Vec::new(),
+        }
+    }
+
+    /// Returns the instance of `T`, constructing it and its dependencies if
+    /// this is the first request for it.
+    pub fn resolve<T: 'static>(&mut self) -> Result<Rc<T>, Error> {
+        let type_id = TypeId::of::<T>();
+        let type_name = any::type_name::<T>();
+
+        let cached = self.instances.iter().find(|(id, _)| *id == type_id);
+        let instance = if let Some((_, instance)) = cached {
+            Rc::clone(instance)
+        } else {
+            if self.in_progress.contains(&type_id) {
+                return Err(Error::Cycle { type_name });
+            }
+            let mut providers = self
+                .providers
+                .iter()
+                .filter(|provider| (provider.type_id)() == type_id);
+            let provider = match (providers.next(), providers.next()) {
+                (Some(provider), None) => provider,
+                (None, _) => return Err(Error::Missing { type_name }),
+                (Some(_), Some(_)) => return Err(Error::Ambiguous { type_name }),
+            };
+            self.in_progress.push(type_id);
+            let constructed = (provider.construct)(self);
+            self.in_progress.pop();
+            let instance = Rc::from(constructed?);
+            self.instances.push((type_id, Rc::clone(&instance)));
+            instance
+        };
+
+        instance
+            .downcast::<T>()
+            .map_err(|_| Error::WrongType { type_name })
+    }
+}
+
+/// Error returned by [`Resolver::resolve`].
+#[derive(Debug)]
+pub enum Error {
+    /// No provider is registered for the type.
+    Missing { type_name: &'static str },
+    /// More than one provider is registered for the type.
+    Ambiguous { type_name: &'static str },
+    /// Constructing the type requires an instance of itself.
+    Cycle { type_name: &'static str },
+    /// The provider for the type constructed a value of some other type.
+    WrongType { type_name: &'static str },
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Missing { type_name } => write!(formatter, "no provider for {type_name}"),
+            Error::Ambiguous { type_name } => {
+                write!(formatter, "multiple providers for {type_name}")
+            }
+            Error::Cycle { type_name } => {
+                write!(formatter, "dependency cycle while constructing {type_name}")
+            }
+            Error::WrongType { type_name } => write!(
+                formatter,
+                "provider for {type_name} constructed a value of a different type",
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,175 @@
+//! Interrupt handlers registered by the drivers that own each interrupt.
+//!
+//! In firmware split across many crates, each driver registers an
+//! [`Interrupt`] for the interrupt lines it services into a distributed slice
+//! owned by the board support crate. The board crate then fills its
+//! dispatch table from the slice with [`populate`], or looks handlers up
+//! directly from a shared vector with [`dispatch`], and sets each line's
+//! priority from the registered value.
+//!
+//! Registering through the [`interrupt!`] macro turns two crates claiming the
+//! same interrupt number into a build error rather than a handler silently
+//! going missing.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::interrupt::{self, Interrupt};
+//!
+//! #[distributed_slice]
+//! pub static INTERRUPTS: [Interrupt] = [..];
+//!
+//! // In the UART driver crate.
+//! #[distributed_slice(INTERRUPTS)]
+//! static UART0: Interrupt = linkme::interrupt!(INTERRUPTS, 5, uart0_isr, 2);
+//!
+//! fn uart0_isr() {
+//!     /* drain the receive FIFO */
+//! }
+//!
+//! fn default_isr() {}
+//!
+//! fn main() {
+//!     let mut table: [fn(); 32] = [default_isr; 32];
+//!     interrupt::populate(&INTERRUPTS, &mut table).unwrap();
+//!     for entry in INTERRUPTS {
+//!         /* nvic.set_priority(entry.irq, entry.priority) */
+//!     }
+//! }
+//! ```
+//!
+//! [`interrupt!`]: crate::interrupt!
+
+use crate::duplicate;
+use core::fmt::{self, Display};
+
+/// Handler for one interrupt line.
+pub struct Interrupt {
+    /// Interrupt number, counted from the first device-specific interrupt.
+    pub irq: u16,
+    pub handler: fn(),
+    /// Priority to configure for the line, in the interrupt controller's own
+    /// encoding.
+    pub priority: u8,
+}
+
+/// Builds an [`Interrupt`] from an interrupt number, handler and priority,
+/// rejecting duplicate interrupt numbers within a slice at build time.
+///
+/// The first argument is the slice the interrupt is registered into, and the
+/// interrupt number must be an integer literal. Two interrupts built with this
+/// macro for the same number of the same slice anywhere in the final binary
+/// fail to build: within one crate the compiler rejects them, and across
+/// crates the linker reports a duplicate symbol named
+/// `linkme.irq.<SLICE>.<number>`. Separate vector tables may use the same
+/// numbers. Requires rustc 1.54 or newer.
+///
+/// ```compile_fail
+/// # use linkme::distributed_slice;
+/// # use linkme::interrupt::Interrupt;
+/// #
+/// # #[distributed_slice]
+/// # pub static INTERRUPTS: [Interrupt] = [..];
+/// #
+/// # fn timer_isr() {}
+/// # fn watchdog_isr() {}
+/// #
+/// #[distributed_slice(INTERRUPTS)]
+/// static TIMER: Interrupt = linkme::interrupt!(INTERRUPTS, 7, timer_isr, 1);
+///
+/// // error: symbol `linkme.irq.INTERRUPTS.7` is already defined
+/// #[distributed_slice(INTERRUPTS)]
+/// static WATCHDOG: Interrupt = linkme::interrupt!(INTERRUPTS, 7, watchdog_isr, 0);
+/// #
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! interrupt {
+    ($slice:path, $irq:expr, $handler:expr, $priority:expr) => {{
+        $slice! {
+            #![linkme_unique = "irq", $irq]
+        }
+
+        $crate::interrupt::Interrupt {
+            irq: $irq,
+            handler: $handler,
+            priority: $priority,
+        }
+    }};
+}
+
+/// Writes every registered handler into its slot of a dispatch table indexed
+/// by interrupt number.
+///
+/// Slots with no registered handler keep their previous contents, usually a
+/// default handler. Two handlers for the same interrupt number are an error,
+/// even though [`interrupt!`] already rejects that at build time, to cover
+/// interrupts written out as struct literals. The table is left partially
+/// populated if an error is returned.
+pub fn populate(interrupts: &[Interrupt], table: &mut [fn()]) -> Result<(), Invalid> {
+    if let Some((interrupt, _)) = duplicate::find(interrupts.iter(), |a, b| a.irq == b.irq) {
+        return Err(Invalid::Duplicate { irq: interrupt.irq });
+    }
+    for interrupt in interrupts {
+        match table.get_mut(usize::from(interrupt.irq)) {
+            Some(slot) => *slot = interrupt.handler,
+            None => {
+                return Err(Invalid::OutOfRange {
+                    irq: interrupt.irq,
+                    len: table.len(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Finds the handler registered for the given interrupt number.
+pub fn handler(interrupts: &[Interrupt], irq: u16) -> Option<fn()> {
+    interrupts
+        .iter()
+        .find(|interrupt| interrupt.irq == irq)
+        .map(|interrupt| interrupt.handler)
+}
+
+/// Runs the handler registered for the given interrupt number, returning
+/// whether there was one.
+///
+/// This is for a shared vector that reads the active interrupt number from
+/// the controller. The search is linear, so a populated table is preferable
+/// where interrupt latency matters.
+pub fn dispatch(interrupts: &[Interrupt], irq: u16) -> bool {
+    match handler(interrupts, irq) {
+        Some(handler) => {
+            handler();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Error returned by [`populate`].
+#[derive(Debug)]
+pub enum Invalid {
+    /// Two handlers are registered for the same interrupt number.
+    Duplicate { irq: u16 },
+    /// An interrupt number does not fit in the dispatch table.
+    OutOfRange { irq: u16, len: usize },
+}
+
+impl Display for Invalid {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Invalid::Duplicate { irq } => {
+                write!(formatter, "interrupt {} has more than one handler", irq)
+            }
+            Invalid::OutOfRange { irq, len } => write!(
+                formatter,
+                "interrupt {} is out of range for a table of {} entries",
+                irq, len,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Invalid {}
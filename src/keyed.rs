@@ -0,0 +1,111 @@
+//! Lookups by a constant key that fail to build when no crate registers the
+//! key.
+//!
+//! An element whose key is written with [`key!`] also defines a marker symbol
+//! named after the registry and the key. [`lookup!`] refers to that symbol, so
+//! a lookup of a key that nothing registers, typically a typo in a handler or
+//! route name, is reported by the linker as an undefined symbol instead of
+//! turning into a `None` at runtime. Since the symbol is defined at most once,
+//! two elements registering the same key this way fail to build as well.
+//!
+//! The lookup itself is delegated to the registry's own `get` method, so it
+//! works with any registry that provides keyed access, such as a
+//! [`StaticMapView`] or a [`DistributedMap`]. Both macros name the registry by
+//! a plain identifier, which must be the same at the registration and at the
+//! lookup.
+//!
+//! Requires rustc 1.54 or newer.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::map::StaticMapView;
+//!
+//! #[distributed_slice]
+//! pub static LINKED: [(&'static str, fn() -> u16)] = [..];
+//!
+//! pub static ROUTES: StaticMapView<&'static str, fn() -> u16> = StaticMapView::new(&LINKED);
+//!
+//! #[distributed_slice(LINKED)]
+//! static USERS: (&'static str, fn() -> u16) = (linkme::key!(ROUTES, "/users"), list_users);
+//!
+//! fn list_users() -> u16 {
+//!     200
+//! }
+//!
+//! fn main() {
+//!     let handler = linkme::lookup!(ROUTES, "/users");
+//!     assert_eq!(handler(), 200);
+//! }
+//! ```
+//!
+//! A lookup of a key without a matching [`key!`] fails to link:
+//!
+//! ```compile_fail
+//! # use linkme::distributed_slice;
+//! # use linkme::map::StaticMapView;
+//! #
+//! # #[distributed_slice]
+//! # pub static LINKED: [(&'static str, fn() -> u16)] = [..];
+//! #
+//! # pub static ROUTES: StaticMapView<&'static str, fn() -> u16> = StaticMapView::new(&LINKED);
+//! #
+//! # #[distributed_slice(LINKED)]
+//! # static USERS: (&'static str, fn() -> u16) = (linkme::key!(ROUTES, "/users"), list_users);
+//! #
+//! # fn list_users() -> u16 {
+//! #     200
+//! # }
+//! #
+//! fn main() {
+//!     // error: undefined symbol: linkme.key.ROUTES./user
+//!     let handler = linkme::lookup!(ROUTES, "/user");
+//! }
+//! ```
+//!
+//! The check only covers lookups that end up in the final binary; one inside
+//! a generic function that is never instantiated is not checked.
+//!
+//! [`key!`]: crate::key!
+//! [`lookup!`]: crate::lookup!
+//! [`StaticMapView`]: crate::map::StaticMapView
+//! [`DistributedMap`]: crate::DistributedMap
+
+/// Registers a key that [`lookup!`] can check for at build time.
+///
+/// Expands to the key itself, for use in an element's initializer. The key
+/// must be a string literal and the registry a plain identifier.
+///
+/// [`lookup!`]: crate::lookup!
+#[macro_export]
+macro_rules! key {
+    ($registry:ident, $key:expr) => {{
+        #[export_name = concat!("linkme.key.", stringify!($registry), ".", $key)]
+        static MARKER: u8 = 0;
+
+        $key
+    }};
+}
+
+/// Looks up a key registered with [`key!`], failing to link if none was.
+///
+/// Expands to `$registry.get($key)` with the `None` case ruled out, so the
+/// result is whatever the registry's `get` returns inside its `Option`.
+///
+/// [`key!`]: crate::key!
+#[macro_export]
+macro_rules! lookup {
+    ($registry:ident, $key:expr) => {{
+        extern "C" {
+            #[link_name = concat!("linkme.key.", stringify!($registry), ".", $key)]
+            static LINKME_KEY: u8;
+        }
+
+        // Keep the reference to the marker in the binary even though its value
+        // is never used.
+        let _ = unsafe { $crate::private::read_volatile(&LINKME_KEY) };
+        match $registry.get($key) {
+            Some(value) => value,
+            None => $crate::private::missing_key(stringify!($registry), $key),
+        }
+    }};
+}
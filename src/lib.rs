@@ -12,9 +12,9 @@
 //!
 //! # Platform support
 //!
-//! | Component | Linux | macOS | Windows | Other...<sup>†</sup> |
-//! |:---|:---:|:---:|:---:|:---:|
-//! | Distributed slice | ✅ | ✅ | ✅ | |
+//! | Component | Linux | macOS | Windows | WebAssembly | Other...<sup>†</sup> |
+//! |:---|:---:|:---:|:---:|:---:|:---:|
+//! | Distributed slice | ✅ | ✅ | ✅ | ✅ | |
 //!
 //! <br>***<sup>†</sup>*** We welcome PRs adding support for any platforms not
 //! listed here.
@@ -105,16 +105,111 @@
     clippy::doc_markdown,
     clippy::empty_enum,
     clippy::expl_impl_clone_on_copy,
+    clippy::missing_errors_doc,
     clippy::missing_panics_doc,
     clippy::must_use_candidate,
     clippy::unused_self
 )]
 
+#[cfg(any(feature = "alloc", target_family = "wasm"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod asset;
+pub mod blob;
+pub mod boot;
+#[cfg(feature = "std")]
+#[clippy::msrv = "1.61"]
+pub mod command;
+pub mod completion;
+pub mod dense;
+pub mod devicetree;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+mod distributed_map;
 mod distributed_slice;
+pub mod duplicate;
+#[cfg(feature = "alloc")]
+pub mod dylib;
+pub mod embassy;
+pub mod error_code;
+#[doc(hidden)]
+pub mod event;
+pub mod feature_flag;
+pub mod fixture;
+pub mod graphql;
+pub mod grpc;
+#[cfg(feature = "std")]
+pub mod health;
+pub mod hwid;
+pub mod i18n;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+pub mod indexed;
+pub mod initcall;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+pub mod inject;
+pub mod interrupt;
+pub mod keyed;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+pub mod lint;
+pub mod map;
+#[cfg(not(no_const_panic))]
+#[clippy::msrv = "1.57"]
+pub mod metadata;
+#[cfg(any(feature = "alloc", target_family = "wasm"))]
+#[clippy::msrv = "1.61"]
+mod once;
+mod order;
+pub mod padding;
+pub mod panic_hook;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+pub mod periodic;
+pub mod plugin;
+pub mod profile;
+pub mod registry;
+pub mod rtic;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+pub mod runtime;
+pub mod scenario;
+#[cfg(any(
+    all(
+        target_os = "linux",
+        any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            target_arch = "arm",
+            target_arch = "aarch64",
+            target_arch = "riscv64",
+        ),
+    ),
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "illumos",
+))]
+pub mod signal;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(not(no_const_control_flow))]
+#[clippy::msrv = "1.46"]
+pub mod stable_id;
+#[cfg(feature = "alloc")]
+#[clippy::msrv = "1.61"]
+pub mod startup;
+pub mod state_machine;
 
 #[doc(hidden)]
 pub mod private;
 
 pub use linkme_impl::*;
 
+#[cfg(feature = "alloc")]
+pub use crate::distributed_map::DistributedMap;
 pub use crate::distributed_slice::DistributedSlice;
+pub use crate::event::{publish, Event};
@@ -0,0 +1,165 @@
+//! Project checks contributed by many crates to one build tool.
+//!
+//! An xtask-style binary declares a distributed slice of [`Check`]s, and each
+//! crate in the workspace registers the project-specific checks it cares
+//! about: license headers, forbidden dependencies, naming rules. The tool runs
+//! them uniformly with [`run`], optionally restricted to checks carrying some
+//! tag, and reports every [`Finding`] together with the check that produced
+//! it.
+//!
+//! Requires the `alloc` feature.
+//!
+//! ```
+//! use linkme::lint::{self, Check, CheckCtx, Finding};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! #[distributed_slice]
+//! pub static CHECKS: [Check] = [..];
+//!
+//! #[distributed_slice(CHECKS)]
+//! static NO_TODO: Check = Check {
+//!     name: "no-todo",
+//!     tags: &["style"],
+//!     run: |ctx| {
+//!         let mut findings = Vec::new();
+//!         if ctx.root.is_empty() {
+//!             findings.push(Finding::error("workspace root is not set"));
+//!         }
+//!         findings
+//!     },
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&CHECKS, |check| check.name).unwrap();
+//!
+//!     let ctx = CheckCtx { root: ".", fix: false };
+//!     let report = lint::run(&CHECKS, &ctx, &["style"]);
+//!     print!("{}", report);
+//!     if report.has_errors() {
+//!         std::process::exit(1);
+//!     }
+//! }
+//! ```
+
+use crate::order;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+/// Project check registered by some crate.
+pub struct Check {
+    pub name: &'static str,
+    /// Tags used to select a subset of checks, such as `"style"` or `"ci"`.
+    pub tags: &'static [&'static str],
+    pub run: fn(&CheckCtx) -> Vec<Finding>,
+}
+
+/// Information about the project passed to every check.
+#[derive(Copy, Clone, Debug)]
+pub struct CheckCtx<'a> {
+    /// Path of the workspace root.
+    pub root: &'a str,
+    /// Whether checks are allowed to fix what they find in place.
+    pub fix: bool,
+}
+
+/// Problem reported by a check.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+    /// File the finding refers to, relative to the workspace root.
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// How serious a finding is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Finding {
+    pub fn error(message: impl Into<String>) -> Self {
+        Finding {
+            severity: Severity::Error,
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Finding {
+            severity: Severity::Warning,
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
+
+    /// Attaches the location the finding refers to.
+    #[must_use]
+    pub fn at(mut self, file: impl Into<String>, line: u32) -> Self {
+        self.file = Some(file.into());
+        self.line = Some(line);
+        self
+    }
+}
+
+/// Every finding from a run of checks.
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// Findings paired with the name of the check that produced them.
+    pub findings: Vec<(&'static str, Finding)>,
+}
+
+impl Report {
+    /// Whether any check reported a finding of [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|(_, finding)| finding.severity == Severity::Error)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (check, finding) in &self.findings {
+            let severity = match finding.severity {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            write!(formatter, "{severity}[{check}]: {}", finding.message)?;
+            match (&finding.file, finding.line) {
+                (Some(file), Some(line)) => write!(formatter, " ({file}:{line})")?,
+                (Some(file), None) => write!(formatter, " ({file})")?,
+                (None, _) => {}
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a check is selected by the given tags.
+///
+/// An empty list of tags selects every check. Otherwise a check is selected
+/// if it carries at least one of the tags.
+pub fn is_selected(check: &Check, tags: &[&str]) -> bool {
+    tags.is_empty() || check.tags.iter().any(|tag| tags.contains(tag))
+}
+
+/// Runs every check selected by `tags`, in order of name.
+pub fn run(checks: &[Check], ctx: &CheckCtx, tags: &[&str]) -> Report {
+    let mut findings = Vec::new();
+    for check in order::by_key(checks, |check| check.name) {
+        if is_selected(check, tags) {
+            for finding in (check.run)(ctx) {
+                findings.push((check.name, finding));
+            }
+        }
+    }
+    Report { findings }
+}
@@ -0,0 +1,172 @@
+//! Keyed lookups over distributed slices of `(K, V)` pairs without an
+//! allocator.
+//!
+//! A [`StaticMapView`] sits in a static next to a distributed slice of
+//! key-value pairs and looks values up by key using binary search, for
+//! `no_std` targets with no heap that still want keyed access to link-time
+//! data.
+//!
+//! Binary search needs the pairs to be in key order, which the linker does not
+//! guarantee unless the elements are placed in order explicitly: declare the
+//! slice `#[linkme(ordered)]` and give each pair an `order` equal to its key,
+//! as below. The first lookup checks the order once. If the pairs turn out not to be sorted,
+//! lookups fall back to a linear scan, which is still correct, and
+//! [`StaticMapView::validate`] reports the problem so it can be caught in
+//! tests.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::map::StaticMapView;
+//!
+//! #[distributed_slice]
+//! #[linkme(ordered)]
+//! pub static BAUD_DIVISORS: [(u32, u16)] = [..];
+//!
+//! pub static DIVISORS: StaticMapView<u32, u16> = StaticMapView::new(&BAUD_DIVISORS);
+//!
+//! #[distributed_slice(BAUD_DIVISORS, order = 115_200)]
+//! static B115200: (u32, u16) = (115_200, 1);
+//!
+//! #[distributed_slice(BAUD_DIVISORS, order = 9_600)]
+//! static B9600: (u32, u16) = (9_600, 12);
+//!
+//! fn main() {
+//!     assert!(DIVISORS.is_sorted());
+//!     assert_eq!(DIVISORS.get(&115_200), Some(&1));
+//!     assert_eq!(DIVISORS.get(&300), None);
+//! }
+//! ```
+//!
+//! With an allocator, a [`DistributedMap`] instead builds a sorted index of
+//! its entries on first access, so lookups are logarithmic whatever order the
+//! linker placed the entries in. Its iterator and error types are defined
+//! here.
+//!
+//! [`DistributedMap`]: crate::DistributedMap
+
+#[cfg(feature = "alloc")]
+pub use crate::distributed_map::{DuplicateKey, Iter, Values};
+use crate::DistributedSlice;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Display};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+const UNKNOWN: usize = 0;
+const SORTED: usize = 1;
+const UNSORTED: usize = 2;
+
+/// Map view over a distributed slice of `(K, V)` pairs.
+pub struct StaticMapView<K: 'static, V: 'static> {
+    pairs: &'static DistributedSlice<[(K, V)]>,
+    order: AtomicUsize,
+}
+
+impl<K, V> StaticMapView<K, V> {
+    /// Creates a view over `pairs`, for use as a static.
+    pub const fn new(pairs: &'static DistributedSlice<[(K, V)]>) -> Self {
+        StaticMapView {
+            pairs,
+            order: AtomicUsize::new(UNKNOWN),
+        }
+    }
+
+    /// Number of pairs, counting pairs with a repeated key.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Pairs in slice order, which is key order if [`is_sorted`] is true.
+    ///
+    /// [`is_sorted`]: StaticMapView::is_sorted
+    pub fn pairs(&self) -> &'static [(K, V)] {
+        self.pairs.static_slice()
+    }
+}
+
+impl<K: Ord, V> StaticMapView<K, V> {
+    /// Finds the value for `key`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let pairs = self.pairs.static_slice();
+        if self.is_sorted() {
+            let i = pairs.binary_search_by(|(k, _)| k.borrow().cmp(key)).ok()?;
+            Some(&pairs[i].1)
+        } else {
+            pairs
+                .iter()
+                .find(|(k, _)| k.borrow() == key)
+                .map(|(_, v)| v)
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Whether the pairs are in strictly increasing key order, enabling
+    /// binary search. Checked on first use and remembered.
+    pub fn is_sorted(&self) -> bool {
+        match self.order.load(Ordering::Relaxed) {
+            SORTED => true,
+            UNSORTED => false,
+            _ => {
+                let sorted = self.first_out_of_order().is_none();
+                let order = if sorted { SORTED } else { UNSORTED };
+                self.order.store(order, Ordering::Relaxed);
+                sorted
+            }
+        }
+    }
+
+    /// Checks that the pairs are in strictly increasing key order, meaning
+    /// sorted and with no key repeated.
+    pub fn validate(&self) -> Result<(), Unsorted<'static, K>> {
+        match self.first_out_of_order() {
+            None => Ok(()),
+            Some(i) => {
+                let pairs = self.pairs.static_slice();
+                Err(Unsorted {
+                    previous: &pairs[i - 1].0,
+                    key: &pairs[i].0,
+                })
+            }
+        }
+    }
+
+    fn first_out_of_order(&self) -> Option<usize> {
+        let pairs = self.pairs.static_slice();
+        (1..pairs.len()).find(|&i| pairs[i - 1].0 >= pairs[i].0)
+    }
+}
+
+/// Error returned by [`StaticMapView::validate`] when a key does not come
+/// strictly after the key before it.
+#[derive(Debug)]
+pub struct Unsorted<'a, K> {
+    pub previous: &'a K,
+    pub key: &'a K,
+}
+
+impl<K: Debug> Display for Unsorted<'_, K> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "key {:?} is not in order after {:?}",
+            self.key, self.previous,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Debug> std::error::Error for Unsorted<'_, K> {}
@@ -0,0 +1,332 @@
+//! Element metadata in a fixed binary format that external tools can read.
+//!
+//! A [`Record`] describes one registered element: the slice it belongs to,
+//! its name, a version tag and where it was defined. Records are collected in
+//! their own link section, [`RECORDS`], whose contents are plain bytes with a
+//! layout independent of the target. Tools inspecting a binary for a
+//! big-endian target such as s390x or powerpc64 therefore parse it exactly as
+//! they would one for x86_64, without knowing anything about the target.
+//!
+//! Records live in an ordinary data section that is loaded at runtime, not in
+//! debug information or the symbol table, so `strip` and split debuginfo
+//! leave them intact. If the section is removed anyway, for example with
+//! `objcopy --remove-section`, [`find`] reports [`LookupError::Stripped`]
+//! instead of nothing being found: linkme registers a record of its own, with
+//! slice `"linkme"` and name `"sentinel"`, whose absence tells the two
+//! situations apart. The section bounds are fixed when the binary is linked,
+//! so removing the section afterwards leaves them in place over whatever now
+//! occupies that memory, typically zero bytes. The sentinel is therefore
+//! looked for by its contents, which only the intact section holds.
+//!
+//! Requires rustc 1.57 or newer. On older compilers the module is left out.
+//!
+//! # Format
+//!
+//! Every record is [`RECORD_LEN`] bytes with alignment 1:
+//!
+//! | Offset | Size | Contents |
+//! |:---|:---|:---|
+//! | 0 | 4 | [`MAGIC`], the ASCII bytes `LKMD` |
+//! | 4 | 1 | [`FORMAT`], currently 1 |
+//! | 5 | 3 | reserved, zero |
+//! | 8 | ... | fields, then zero bytes to the end of the record |
+//!
+//! Each field is a one-byte [`Tag`], a two-byte little-endian length, and
+//! that many bytes of UTF-8. A zero tag ends the fields. Readers should skip
+//! fields whose tag they do not know, and skip records consisting entirely of
+//! zero bytes, which some linkers insert as padding.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::metadata::{self, Record, RECORDS};
+//!
+//! #[distributed_slice]
+//! pub static CODECS: [fn(&[u8]) -> Vec<u8>] = [..];
+//!
+//! #[distributed_slice(CODECS)]
+//! static IDENTITY: fn(&[u8]) -> Vec<u8> = <[u8]>::to_vec;
+//!
+//! #[distributed_slice(RECORDS)]
+//! static IDENTITY_META: Record = linkme::metadata!(CODECS, "identity", "1.0.0");
+//!
+//! fn main() {
+//!     let record = IDENTITY_META.decode().unwrap();
+//!     assert_eq!(record.slice, "CODECS");
+//!     assert_eq!(record.version, "1.0.0");
+//!
+//!     // The same bytes as a tool would find them in the binary.
+//!     for record in metadata::records(IDENTITY_META.as_bytes()) {
+//!         println!("{}", record.unwrap().name);
+//!     }
+//! }
+//! ```
+
+use crate::distributed_slice;
+use core::fmt::{self, Display};
+use core::str;
+
+/// Size in bytes of every [`Record`].
+pub const RECORD_LEN: usize = 128;
+
+/// First four bytes of every record.
+pub const MAGIC: [u8; 4] = *b"LKMD";
+
+/// Version of the record format described in the module documentation.
+pub const FORMAT: u8 = 1;
+
+const HEADER_LEN: usize = 8;
+
+/// Metadata records of registered elements.
+///
+/// These have the type `DistributedSlice<[Record]>`.
+pub use self::LINKME_META as RECORDS;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_META: [Record] = [..];
+
+#[distributed_slice(LINKME_META)]
+#[linkme(crate = crate)]
+static SENTINEL: Record = EXPECTED_SENTINEL;
+
+// Compared against by value: the static itself lives in the section and is
+// gone along with it.
+const EXPECTED_SENTINEL: Record = Record::new("linkme", "sentinel", "", "linkme::metadata");
+
+/// Builds a [`Record`] for an element of `$slice`, recording the module the
+/// macro is called from as its provenance.
+#[macro_export]
+macro_rules! metadata {
+    ($slice:path, $name:expr, $version:expr) => {
+        $crate::metadata::Record::new(stringify!($slice), $name, $version, module_path!())
+    };
+}
+
+/// Field identifiers within a record.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Tag {
+    Slice = 1,
+    Name = 2,
+    Version = 3,
+    Provenance = 4,
+}
+
+/// One encoded metadata record.
+#[repr(C)]
+pub struct Record {
+    bytes: [u8; RECORD_LEN],
+}
+
+impl Record {
+    /// Encodes a record, failing to compile in const context if the fields do
+    /// not fit in [`RECORD_LEN`] bytes.
+    pub const fn new(slice: &str, name: &str, version: &str, provenance: &str) -> Self {
+        let mut bytes = [0; RECORD_LEN];
+        let mut i = 0;
+        while i < MAGIC.len() {
+            bytes[i] = MAGIC[i];
+            i += 1;
+        }
+        bytes[4] = FORMAT;
+        let (bytes, at) = put(bytes, HEADER_LEN, Tag::Slice, slice);
+        let (bytes, at) = put(bytes, at, Tag::Name, name);
+        let (bytes, at) = put(bytes, at, Tag::Version, version);
+        let (bytes, _) = put(bytes, at, Tag::Provenance, provenance);
+        Record { bytes }
+    }
+
+    /// The encoded bytes, exactly as they appear in the binary.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn decode(&self) -> Result<Decoded<'_>, DecodeError> {
+        decode(&self.bytes)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const fn put(
+    mut bytes: [u8; RECORD_LEN],
+    at: usize,
+    tag: Tag,
+    value: &str,
+) -> ([u8; RECORD_LEN], usize) {
+    let value = value.as_bytes();
+    assert!(
+        at + 3 + value.len() <= RECORD_LEN,
+        "metadata record does not fit in RECORD_LEN bytes",
+    );
+    let len = (value.len() as u16).to_le_bytes();
+    bytes[at] = tag as u8;
+    bytes[at + 1] = len[0];
+    bytes[at + 2] = len[1];
+    let mut i = 0;
+    while i < value.len() {
+        bytes[at + 3 + i] = value[i];
+        i += 1;
+    }
+    (bytes, at + 3 + value.len())
+}
+
+/// Fields of a decoded record. Fields missing from the record are empty.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Decoded<'a> {
+    pub slice: &'a str,
+    pub name: &'a str,
+    pub version: &'a str,
+    pub provenance: &'a str,
+}
+
+/// Decodes one record from the start of `bytes`.
+pub fn decode(bytes: &[u8]) -> Result<Decoded<'_>, DecodeError> {
+    if bytes.len() < RECORD_LEN {
+        return Err(DecodeError::Truncated);
+    }
+    let bytes = &bytes[..RECORD_LEN];
+    if bytes[..4] != MAGIC {
+        return Err(DecodeError::BadMagic);
+    }
+    if bytes[4] != FORMAT {
+        return Err(DecodeError::UnsupportedFormat(bytes[4]));
+    }
+    let mut decoded = Decoded::default();
+    let mut at = HEADER_LEN;
+    while at < RECORD_LEN && bytes[at] != 0 {
+        if at + 3 > RECORD_LEN {
+            return Err(DecodeError::Truncated);
+        }
+        let len = u16::from_le_bytes([bytes[at + 1], bytes[at + 2]]) as usize;
+        let value = bytes
+            .get(at + 3..at + 3 + len)
+            .ok_or(DecodeError::Truncated)?;
+        let value = str::from_utf8(value).map_err(|_| DecodeError::InvalidUtf8)?;
+        match bytes[at] {
+            1 => decoded.slice = value,
+            2 => decoded.name = value,
+            3 => decoded.version = value,
+            4 => decoded.provenance = value,
+            _ => {}
+        }
+        at += 3 + len;
+    }
+    Ok(decoded)
+}
+
+/// Whether the metadata section of this binary is present.
+pub fn is_available() -> bool {
+    RECORDS
+        .iter()
+        .any(|record| record.as_bytes() == EXPECTED_SENTINEL.as_bytes())
+}
+
+/// Finds the record registered for element `name` of slice `slice`.
+pub fn find(slice: &str, name: &str) -> Result<Decoded<'static>, LookupError> {
+    if !is_available() {
+        return Err(LookupError::Stripped);
+    }
+    for record in RECORDS {
+        if is_padding(record.as_bytes()) {
+            continue;
+        }
+        let decoded = record.decode().map_err(LookupError::Corrupt)?;
+        if decoded.slice == slice && decoded.name == name {
+            return Ok(decoded);
+        }
+    }
+    Err(LookupError::NotFound)
+}
+
+// Some linkers fill the gaps between contributions to a section with zero
+// bytes, which no real record consists of.
+fn is_padding(record: &[u8]) -> bool {
+    record.iter().all(|&byte| byte == 0)
+}
+
+/// Decodes the contents of a metadata section, such as [`RECORDS`] extracted
+/// from a binary by an external tool.
+pub fn records(section: &[u8]) -> Records<'_> {
+    Records { section }
+}
+
+/// Iterator returned by [`records`].
+pub struct Records<'a> {
+    section: &'a [u8],
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<Decoded<'a>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.section.is_empty() {
+                return None;
+            }
+            if self.section.len() < RECORD_LEN {
+                self.section = &[];
+                return Some(Err(DecodeError::Truncated));
+            }
+            let (record, rest) = self.section.split_at(RECORD_LEN);
+            self.section = rest;
+            if is_padding(record) {
+                continue;
+            }
+            return Some(decode(record));
+        }
+    }
+}
+
+/// Error decoding a record.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    BadMagic,
+    UnsupportedFormat(u8),
+    Truncated,
+    InvalidUtf8,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::BadMagic => formatter.write_str("metadata record has a bad magic number"),
+            DecodeError::UnsupportedFormat(format) => {
+                write!(formatter, "unsupported metadata format {}", format)
+            }
+            DecodeError::Truncated => formatter.write_str("metadata record is truncated"),
+            DecodeError::InvalidUtf8 => formatter.write_str("metadata field is not UTF-8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Error returned by [`find`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum LookupError {
+    /// The metadata section was removed from the binary.
+    Stripped,
+    /// No record is registered for the element.
+    NotFound,
+    /// A record in the section could not be decoded.
+    Corrupt(DecodeError),
+}
+
+impl Display for LookupError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::Stripped => {
+                formatter.write_str("metadata section was stripped from the binary")
+            }
+            LookupError::NotFound => {
+                formatter.write_str("no metadata is registered for the element")
+            }
+            LookupError::Corrupt(error) => Display::fmt(error, formatter),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LookupError {}
@@ -0,0 +1,63 @@
+// Lazily initialized value usable from a static without std, for registries
+// that build an index on first access. Concurrent first accesses spin until
+// the thread running the initializer finishes; if the initializer panics,
+// the next access runs it again.
+
+use core::cell::UnsafeCell;
+use core::hint;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const UNINIT: u8 = 0;
+const RUNNING: u8 = 1;
+const DONE: u8 = 2;
+
+pub(crate) struct Once<T> {
+    state: AtomicU8,
+    value: UnsafeCell<Option<T>>,
+}
+
+unsafe impl<T: Send + Sync> Sync for Once<T> {}
+
+impl<T> Once<T> {
+    pub(crate) const fn new() -> Self {
+        Once {
+            state: AtomicU8::new(UNINIT),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    pub(crate) fn get_or_init(&self, init: impl FnOnce() -> T) -> &T {
+        loop {
+            match self.state.compare_exchange_weak(
+                UNINIT,
+                RUNNING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let reset = Reset(&self.state);
+                    let value = init();
+                    unsafe { *self.value.get() = Some(value) };
+                    core::mem::forget(reset);
+                    self.state.store(DONE, Ordering::Release);
+                    break;
+                }
+                Err(DONE) => break,
+                Err(_) => hint::spin_loop(),
+            }
+        }
+        match unsafe { &*self.value.get() } {
+            Some(value) => value,
+            None => unreachable!(),
+        }
+    }
+}
+
+// Puts the state back to UNINIT if the initializer unwinds.
+struct Reset<'a>(&'a AtomicU8);
+
+impl Drop for Reset<'_> {
+    fn drop(&mut self) {
+        self.0.store(UNINIT, Ordering::Release);
+    }
+}
@@ -0,0 +1,167 @@
+// Iteration in key order without an allocator, for registries whose output
+// must not depend on link order. Entries with equal keys keep slice order.
+
+use core::mem;
+use core::ptr;
+use core::slice;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// Only compiled where build.rs found rustc 1.49 or newer.
+#[cfg(not(no_hint_spin_loop))]
+#[allow(clippy::incompatible_msrv)]
+fn spin_loop() {
+    core::hint::spin_loop();
+}
+
+#[cfg(no_hint_spin_loop)]
+#[allow(deprecated)]
+fn spin_loop() {
+    core::sync::atomic::spin_loop_hint();
+}
+
+pub(crate) fn by_key<T, K, F>(items: &[T], key: F) -> ByKey<'_, T, K, F>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    ByKey {
+        items,
+        key,
+        prev: None,
+    }
+}
+
+pub(crate) struct ByKey<'a, T, K, F> {
+    items: &'a [T],
+    key: F,
+    prev: Option<(K, usize)>,
+}
+
+impl<'a, T, K, F> Iterator for ByKey<'a, T, K, F>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next: Option<(K, usize)> = None;
+        for (i, item) in self.items.iter().enumerate() {
+            let candidate = ((self.key)(item), i);
+            let after_prev = self.prev.as_ref().map_or(true, |prev| candidate > *prev);
+            let before_next = next.as_ref().map_or(true, |next| candidate < *next);
+            if after_prev && before_next {
+                next = Some(candidate);
+            }
+        }
+        let i = next.as_ref()?.1;
+        self.prev = next;
+        Some(&self.items[i])
+    }
+}
+
+// Ordered distributed slices are sorted in place on first access. Each
+// element places a key next to it in a companion section recording its order
+// and address; the keys are sorted by order, falling back to link order, and
+// the elements are then permuted to match.
+#[repr(C)]
+pub struct OrderKey {
+    order: u32,
+    element: *const (),
+}
+
+impl OrderKey {
+    pub const fn new(order: u32, element: *const ()) -> Self {
+        OrderKey { order, element }
+    }
+}
+
+const UNSORTED: usize = 0;
+const SORTING: usize = 1;
+const SORTED: usize = 2;
+
+pub struct Sort {
+    state: AtomicUsize,
+    start: *mut OrderKey,
+    stop: *mut OrderKey,
+}
+
+unsafe impl Sync for Sort {}
+
+impl Sort {
+    #[doc(hidden)]
+    pub const unsafe fn new(start: *mut OrderKey, stop: *mut OrderKey) -> Self {
+        Sort {
+            state: AtomicUsize::new(UNSORTED),
+            start,
+            stop,
+        }
+    }
+
+    // For targets where the linker or registry already sorted the elements.
+    pub const fn sorted() -> Self {
+        Sort {
+            state: AtomicUsize::new(SORTED),
+            start: ptr::null_mut(),
+            stop: ptr::null_mut(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn run<T>(&self, elements: *mut T, len: usize) {
+        if self.state.load(Ordering::Acquire) == SORTED {
+            return;
+        }
+        // Checked before taking the lock, so that every thread reading the
+        // slice panics instead of waiting on one that did.
+        let count = (self.stop as usize - self.start as usize) / mem::size_of::<OrderKey>();
+        assert!(
+            count == len,
+            "ordered distributed slice has {} elements but {} order keys",
+            len,
+            count,
+        );
+        if self
+            .state
+            .compare_exchange(UNSORTED, SORTING, Ordering::Acquire, Ordering::Acquire)
+            .is_err()
+        {
+            while self.state.load(Ordering::Acquire) != SORTED {
+                spin_loop();
+            }
+            return;
+        }
+        self.sort(elements, len);
+        self.state.store(SORTED, Ordering::Release);
+    }
+
+    unsafe fn sort<T>(&self, elements: *mut T, len: usize) {
+        let stride = mem::size_of::<T>();
+        let keys = slice::from_raw_parts_mut(self.start, len);
+        let index = |key: &OrderKey| (key.element as usize - elements as usize) / stride;
+
+        // Insertion sort, since there is no allocator to lean on. Slices are
+        // sorted once and are rarely long.
+        for i in 1..keys.len() {
+            let mut j = i;
+            while j > 0
+                && (keys[j - 1].order, index(&keys[j - 1])) > (keys[j].order, index(&keys[j]))
+            {
+                keys.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+
+        // keys[i] now names the element that belongs at position i. Elements
+        // before i are final, so an element that was there has since been
+        // swapped out to where the key of its old position points.
+        let elements = slice::from_raw_parts_mut(elements, len);
+        for i in 0..len {
+            let mut j = index(&keys[i]);
+            while j < i {
+                j = index(&keys[j]);
+            }
+            elements.swap(i, j);
+        }
+    }
+}
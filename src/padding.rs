@@ -0,0 +1,90 @@
+//! Iteration that tolerates zero padding between slice elements.
+//!
+//! On most targets the elements of a distributed slice are exactly
+//! contiguous. The MSVC linker, however, may insert zero bytes between the
+//! contributions of different object files to a grouped section, notably when
+//! linking incrementally. On such targets
+//! [`DistributedSlice::may_contain_padding`] returns true, and a slice should
+//! be read with [`DistributedSlice::iter_skip_null`], which steps over
+//! element-sized runs of zero bytes instead of presenting them as elements.
+//!
+//! Skipping is only sound for element types that have no padding bytes of
+//! their own and for which an all-zero value is never a real element, such as
+//! function pointers and references, which is why `iter_skip_null` is an
+//! `unsafe fn`. Code that hands the raw bounds of a slice to C through
+//! [`static_slice`] should check [`may_contain_padding`] and apply the same
+//! rule on the other side.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//!
+//! #[distributed_slice]
+//! pub static HOOKS: [fn()] = [..];
+//!
+//! #[distributed_slice(HOOKS)]
+//! static HOOK: fn() = || {};
+//!
+//! fn main() {
+//!     // Function pointers are never null and have no padding bytes.
+//!     for hook in unsafe { HOOKS.iter_skip_null() } {
+//!         hook();
+//!     }
+//! }
+//! ```
+//!
+//! [`DistributedSlice::may_contain_padding`]: crate::DistributedSlice::may_contain_padding
+//! [`DistributedSlice::iter_skip_null`]: crate::DistributedSlice::iter_skip_null
+//! [`static_slice`]: crate::DistributedSlice::static_slice
+//! [`may_contain_padding`]: crate::DistributedSlice::may_contain_padding
+
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr;
+
+/// Whether the linker for the current target may insert zero padding between
+/// slice elements.
+pub const MAY_CONTAIN_PADDING: bool = cfg!(target_os = "windows");
+
+/// Iterator returned by [`DistributedSlice::iter_skip_null`].
+///
+/// [`DistributedSlice::iter_skip_null`]: crate::DistributedSlice::iter_skip_null
+pub struct SkipNull<T: 'static> {
+    next: *const T,
+    stop: *const T,
+    marker: PhantomData<&'static T>,
+}
+
+unsafe impl<T: Sync> Send for SkipNull<T> {}
+unsafe impl<T: Sync> Sync for SkipNull<T> {}
+
+impl<T> SkipNull<T> {
+    // The range must be element-aligned and readable, and every element-sized
+    // run in it either a valid T without padding bytes or all zero bytes.
+    pub(crate) unsafe fn new(start: *const T, stop: *const T) -> Self {
+        SkipNull {
+            next: start,
+            stop,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for SkipNull<T> {
+    type Item = &'static T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stride = mem::size_of::<T>();
+        while stride != 0 && (self.stop as usize) - (self.next as usize) >= stride {
+            let candidate = self.next;
+            self.next = unsafe { self.next.add(1) };
+            // Read byte by byte through the raw pointer; no reference is made
+            // to the run until it is known not to be padding.
+            let bytes = candidate as *const u8;
+            let is_padding = (0..stride).all(|i| unsafe { ptr::read(bytes.add(i)) } == 0);
+            if !is_padding {
+                return Some(unsafe { &*candidate });
+            }
+        }
+        None
+    }
+}
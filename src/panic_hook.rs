@@ -0,0 +1,105 @@
+//! Panic observers from many crates sharing one panic hook.
+//!
+//! The standard library has a single panic hook, and every crate that calls
+//! `std::panic::set_hook` replaces whatever hook was installed before it.
+//! Instead, crash reporting, logging and reset logic may each register a
+//! function into [`OBSERVERS`], and the application calls [`install`] once to
+//! install a hook that notifies all of them.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::panic_hook::{self, Panic, OBSERVERS};
+//!
+//! // In a crash reporting crate.
+//! #[distributed_slice(OBSERVERS)]
+//! fn report_crash(panic: &Panic) {
+//!     if let Some(location) = panic.location() {
+//!         eprintln!("crash at {}:{}", location.file(), location.line());
+//!     }
+//! }
+//!
+//! fn main() {
+//!     # #[cfg(feature = "std")]
+//!     panic_hook::install();
+//!     /* ... */
+//! }
+//! ```
+//!
+//! Observers are called in the order they appear in the slice, which is
+//! unspecified. An observer that itself panics aborts the process: the
+//! standard library aborts on any panic raised while a panic hook is running,
+//! even one that the hook would catch with `catch_unwind`, so no observer can
+//! be shielded from another's panic and the hook does not try. Observers
+//! should be kept as simple and infallible as possible.
+//!
+//! Programs without the standard library can notify the observers from their
+//! `#[panic_handler]` by calling [`notify`].
+
+use crate::distributed_slice;
+use core::fmt::Display;
+use core::panic::Location;
+
+/// Functions notified of every panic once [`install`] has been called.
+///
+/// These have the type `DistributedSlice<[fn(&Panic)]>`.
+pub use self::LINKME_PANIC as OBSERVERS;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_PANIC: [fn(&Panic)] = [..];
+
+/// Description of a panic passed to each observer.
+pub struct Panic<'a> {
+    message: Option<&'a dyn Display>,
+    location: Option<&'a Location<'a>>,
+}
+
+impl<'a> Panic<'a> {
+    pub fn new(message: Option<&'a dyn Display>, location: Option<&'a Location<'a>>) -> Self {
+        Panic { message, location }
+    }
+
+    /// The panic message, if the panic payload was a string.
+    pub fn message(&self) -> Option<&'a dyn Display> {
+        self.message
+    }
+
+    /// Where in the source the panic originated.
+    pub fn location(&self) -> Option<&'a Location<'a>> {
+        self.location
+    }
+}
+
+/// Calls every observer with the given panic.
+pub fn notify(panic: &Panic) {
+    for observer in OBSERVERS {
+        observer(panic);
+    }
+}
+
+/// Installs a panic hook that notifies every observer, then runs the hook
+/// that was installed previously.
+///
+/// Keeping the previous hook means the default message on stderr is still
+/// printed unless some earlier code replaced it. The observers run first and
+/// are not wrapped in `catch_unwind`, which cannot catch a panic inside a
+/// panic hook; one that panics aborts the process before the previous hook
+/// prints anything.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn install() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(std::boxed::Box::new(move |info| {
+        let payload = info.payload();
+        let message = match payload.downcast_ref::<&str>() {
+            Some(message) => Some(message as &dyn Display),
+            None => payload
+                .downcast_ref::<std::string::String>()
+                .map(|message| message as &dyn Display),
+        };
+        notify(&Panic::new(message, info.location()));
+        previous(info);
+    }));
+}
@@ -0,0 +1,139 @@
+//! Periodic background tasks that register themselves with the scheduler.
+//!
+//! Domain crates register their maintenance jobs as [`PeriodicTask`]s into a
+//! distributed slice owned by the application. The application's scheduler,
+//! whatever runtime it uses, drives a [`Schedule`] to learn which tasks are due
+//! and runs them.
+//!
+//! Requires the `alloc` feature.
+//!
+//! ```no_run
+//! # mod executor {
+//! #     pub fn spawn(_future: linkme::periodic::BoxFuture) {}
+//! # }
+//! use core::time::Duration;
+//! use linkme::distributed_slice;
+//! use linkme::periodic::{Job, PeriodicTask, Schedule};
+//!
+//! #[distributed_slice]
+//! pub static PERIODIC_TASKS: [PeriodicTask] = [..];
+//!
+//! #[distributed_slice(PERIODIC_TASKS)]
+//! static PURGE_SESSIONS: PeriodicTask = PeriodicTask {
+//!     name: "purge-sessions",
+//!     interval: Duration::from_secs(60),
+//!     job: Job::Blocking(purge_sessions),
+//! };
+//!
+//! fn purge_sessions() {
+//!     /* ... */
+//! }
+//!
+//! fn main() {
+//!     let start = std::time::Instant::now();
+//!     let mut schedule = Schedule::new(&PERIODIC_TASKS, Duration::from_secs(0));
+//!     loop {
+//!         let deadline = schedule.next_deadline().unwrap();
+//!         std::thread::sleep(deadline.saturating_sub(start.elapsed()));
+//!         for task in schedule.due(start.elapsed()) {
+//!             match task.job {
+//!                 Job::Blocking(run) => run(),
+//!                 Job::Async(make) => executor::spawn(make()),
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::future::Future;
+use core::pin::Pin;
+use core::time::Duration;
+
+/// Descriptor of a task to be run repeatedly.
+pub struct PeriodicTask {
+    pub name: &'static str,
+    /// Time between consecutive runs. A task with a zero interval is due on
+    /// every call to [`Schedule::due`], and one whose next run would be
+    /// further away than a `Duration` can represent, such as with
+    /// `Duration::MAX`, is never due again.
+    pub interval: Duration,
+    pub job: Job,
+}
+
+/// What to do each time a task is due.
+pub enum Job {
+    /// Function to call. It runs to completion on the scheduler's thread, or
+    /// on a blocking pool if the scheduler is async.
+    Blocking(fn()),
+    /// Factory producing a fresh future for each run.
+    Async(fn() -> BoxFuture),
+}
+
+/// Future produced by [`Job::Async`].
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Tracks when each task is next due.
+///
+/// Time is measured by the caller as a monotonic offset from any fixed
+/// origin, for example the time elapsed since startup. This keeps the
+/// schedule independent of any particular clock or runtime.
+pub struct Schedule<'a> {
+    tasks: &'a [PeriodicTask],
+    // None for a task that is never due again.
+    next: Vec<Option<Duration>>,
+}
+
+impl<'a> Schedule<'a> {
+    /// Schedules the first run of every task one interval after `now`.
+    pub fn new(tasks: &'a [PeriodicTask], now: Duration) -> Self {
+        let next = tasks
+            .iter()
+            .map(|task| now.checked_add(task.interval))
+            .collect();
+        Schedule { tasks, next }
+    }
+
+    /// The earliest time at which some task is due, or `None` if there are
+    /// no tasks that will ever be due.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.next.iter().flatten().min().copied()
+    }
+
+    /// Returns the tasks due at `now` and schedules their next run.
+    ///
+    /// A task whose deadline was missed by more than one interval is returned
+    /// once, not once per missed run, and remains aligned to its original
+    /// period.
+    pub fn due(&mut self, now: Duration) -> Vec<&'a PeriodicTask> {
+        let mut due = Vec::new();
+        for (task, slot) in self.tasks.iter().zip(&mut self.next) {
+            let next = match *slot {
+                Some(next) if next <= now => next,
+                _ => continue,
+            };
+            due.push(task);
+            *slot = if task.interval == Duration::from_secs(0) {
+                Some(now)
+            } else {
+                // Skip every run missed since the deadline in one step.
+                let interval = task.interval.as_nanos();
+                let missed = now.saturating_sub(next).as_nanos() / interval;
+                from_nanos((missed + 1) * interval).and_then(|step| next.checked_add(step))
+            };
+        }
+        due
+    }
+}
+
+// None if the duration does not fit.
+fn from_nanos(nanos: u128) -> Option<Duration> {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    let secs = u64::try_from(nanos / NANOS_PER_SEC).ok()?;
+    // Less than one second's worth, so it fits.
+    #[allow(clippy::cast_possible_truncation)]
+    let subsec_nanos = (nanos % NANOS_PER_SEC) as u32;
+    Some(Duration::new(secs, subsec_nanos))
+}
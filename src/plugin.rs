@@ -0,0 +1,162 @@
+//! Manifests of the plugins linked into the binary.
+//!
+//! Each plugin crate registers a [`Plugin`] describing itself and the
+//! capabilities it provides to and requires from other plugins. At startup the
+//! loader calls [`validate`] to reject inconsistent combinations of plugins
+//! before any of them is initialized.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::plugin::{self, Plugin, Version};
+//!
+//! #[distributed_slice]
+//! pub static PLUGINS: [Plugin] = [..];
+//!
+//! #[distributed_slice(PLUGINS)]
+//! static S3: Plugin = Plugin {
+//!     name: "s3",
+//!     version: Version::new(1, 4, 0),
+//!     provides: &["blob-storage"],
+//!     requires: &["http-client"],
+//! };
+//!
+//! #[distributed_slice(PLUGINS)]
+//! static HYPER: Plugin = Plugin {
+//!     name: "hyper",
+//!     version: Version::new(0, 14, 2),
+//!     provides: &["http-client"],
+//!     requires: &[],
+//! };
+//!
+//! fn main() {
+//!     plugin::validate(&PLUGINS).unwrap();
+//!
+//!     let storage = plugin::provider(&PLUGINS, "blob-storage").unwrap();
+//!     assert_eq!(storage.name, "s3");
+//! }
+//! ```
+
+use crate::duplicate;
+use core::fmt::{self, Display};
+
+/// Manifest of one plugin.
+pub struct Plugin {
+    pub name: &'static str,
+    pub version: Version,
+    /// Capabilities this plugin implements. At most one plugin in the binary
+    /// may provide any given capability.
+    pub provides: &'static [&'static str],
+    /// Capabilities this plugin needs some other plugin to provide.
+    pub requires: &'static [&'static str],
+}
+
+/// Semantic version of a plugin.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Finds the plugin providing the given capability.
+pub fn provider<'a>(plugins: &'a [Plugin], capability: &str) -> Option<&'a Plugin> {
+    plugins
+        .iter()
+        .find(|plugin| plugin.provides.contains(&capability))
+}
+
+/// Checks that plugin names are unique, that no capability is provided by two
+/// plugins, and that every required capability is provided by some plugin.
+pub fn validate(plugins: &[Plugin]) -> Result<(), Invalid> {
+    if let Some((plugin, _)) = duplicate::find(plugins.iter(), |a, b| a.name == b.name) {
+        return Err(Invalid::DuplicateName { name: plugin.name });
+    }
+
+    let provided = plugins.iter().flat_map(|plugin| {
+        plugin
+            .provides
+            .iter()
+            .map(move |capability| (plugin, *capability))
+    });
+    if let Some(((first, capability), (second, _))) = duplicate::find(provided, |a, b| a.1 == b.1) {
+        return Err(Invalid::DuplicateCapability {
+            capability,
+            first: first.name,
+            second: second.name,
+        });
+    }
+
+    for plugin in plugins {
+        for capability in plugin.requires {
+            if provider(plugins, capability).is_none() {
+                return Err(Invalid::MissingCapability {
+                    capability,
+                    plugin: plugin.name,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Error returned by [`validate`].
+#[derive(Debug)]
+pub enum Invalid {
+    /// Two plugins have the same name.
+    DuplicateName { name: &'static str },
+    /// Two plugins provide the same capability.
+    DuplicateCapability {
+        capability: &'static str,
+        first: &'static str,
+        second: &'static str,
+    },
+    /// A plugin requires a capability that no plugin provides.
+    MissingCapability {
+        capability: &'static str,
+        plugin: &'static str,
+    },
+}
+
+impl Display for Invalid {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Invalid::DuplicateName { name } => {
+                write!(formatter, "plugin {:?} is registered twice", name)
+            }
+            Invalid::DuplicateCapability {
+                capability,
+                first,
+                second,
+            } => write!(
+                formatter,
+                "capability {:?} is provided by both {:?} and {:?}",
+                capability, first, second,
+            ),
+            Invalid::MissingCapability { capability, plugin } => write!(
+                formatter,
+                "plugin {:?} requires capability {:?} which no plugin provides",
+                plugin, capability,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Invalid {}
@@ -1,3 +1,10 @@
+pub use core::any::TypeId;
+
+pub use crate::order::{OrderKey, Sort};
+
+#[cfg(target_family = "wasm")]
+pub mod wasm;
+
 pub trait Slice {
     type Element;
 }
@@ -8,6 +15,82 @@ impl<T> Slice for [T] {
 
 pub enum Void {}
 
+// Implemented for the function pointer types of one ABI, so that
+// #[linkme(abi = "...")] can check an element type written as a type alias.
+#[cfg_attr(
+    not(no_diagnostic_namespace),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not an `extern \"C\" fn` pointer",
+        label = "the slice is declared with abi = \"C\"",
+    )
+)]
+pub trait ExternC {}
+
+#[cfg_attr(
+    not(no_diagnostic_namespace),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not an `extern \"system\" fn` pointer",
+        label = "the slice is declared with abi = \"system\"",
+    )
+)]
+pub trait ExternSystem {}
+
+macro_rules! fn_ptr_abi {
+    ($($arg:ident)*) => {
+        impl<R, $($arg),*> ExternC for extern "C" fn($($arg),*) -> R {}
+        impl<R, $($arg),*> ExternC for unsafe extern "C" fn($($arg),*) -> R {}
+        impl<R, $($arg),*> ExternSystem for extern "system" fn($($arg),*) -> R {}
+        impl<R, $($arg),*> ExternSystem for unsafe extern "system" fn($($arg),*) -> R {}
+    };
+}
+
+fn_ptr_abi!();
+fn_ptr_abi!(A);
+fn_ptr_abi!(A B);
+fn_ptr_abi!(A B C);
+fn_ptr_abi!(A B C D);
+fn_ptr_abi!(A B C D E);
+fn_ptr_abi!(A B C D E F);
+fn_ptr_abi!(A B C D E F G);
+fn_ptr_abi!(A B C D E F G H);
+fn_ptr_abi!(A B C D E F G H I);
+fn_ptr_abi!(A B C D E F G H I J);
+fn_ptr_abi!(A B C D E F G H I J K);
+fn_ptr_abi!(A B C D E F G H I J K L);
+
+pub fn abi_c<T: ExternC>() {}
+
+pub fn abi_system<T: ExternSystem>() {}
+
+#[repr(transparent)]
+pub struct ExportPtr<T>(pub *const T);
+
+unsafe impl<T> Sync for ExportPtr<T> {}
+
+// Exported by a slice declared with #[linkme(dylib = "name")] and read by
+// linkme::dylib in the host. The version comes first, so that a host can
+// reject a descriptor of a different layout before reading the rest.
+pub const DYLIB_VERSION: u32 = 1;
+
+#[repr(C)]
+pub struct DylibExport<T: 'static> {
+    pub version: u32,
+    pub size: usize,
+    pub align: usize,
+    pub type_id: fn() -> TypeId,
+    pub slice: fn() -> &'static [T],
+}
+
 pub fn value<T>() -> T {
     panic!()
 }
+
+pub use core::ptr::read_volatile;
+
+#[cold]
+pub fn missing_key(registry: &str, key: &str) -> ! {
+    panic!(
+        "key {:?} has a marker but no entry in {}; register it with linkme::key!",
+        key, registry,
+    )
+}
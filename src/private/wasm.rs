@@ -0,0 +1,128 @@
+// WebAssembly has no linker-defined section bounds, and a link_section there
+// names a custom section outside of linear memory. Instead each element
+// registers itself from a constructor, which wasm-ld runs from
+// __wasm_call_ctors, and the first access copies the registered elements into
+// a single allocation in slice order.
+//
+// With shared memory, each thread that instantiates the module runs the
+// constructors again, so registration is lock-free and idempotent per element
+// and nothing relies on the constructors running only once.
+
+use crate::once::Once;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+pub struct Registry<T> {
+    head: AtomicPtr<Node<T>>,
+    slice: Once<*const [T]>,
+    // Set for the registry of a mirror, which forwards to the original.
+    mirror: Option<fn() -> *const Registry<T>>,
+    // Sort key of elements registered without one.
+    default_key: &'static str,
+}
+
+unsafe impl<T> Sync for Registry<T> {}
+
+pub struct Node<T> {
+    element: *const T,
+    sort_key: &'static str,
+    registered: AtomicBool,
+    next: AtomicPtr<Node<T>>,
+}
+
+unsafe impl<T> Sync for Node<T> {}
+
+impl<T> Registry<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Registry {
+            head: AtomicPtr::new(ptr::null_mut()),
+            slice: Once::new(),
+            mirror: None,
+            default_key: "",
+        }
+    }
+
+    // In an ordered slice, elements without an order have order 0.
+    pub const fn ordered() -> Self {
+        Registry {
+            head: AtomicPtr::new(ptr::null_mut()),
+            slice: Once::new(),
+            mirror: None,
+            default_key: "80000000",
+        }
+    }
+
+    pub const fn mirror(original: fn() -> *const Registry<T>) -> Self {
+        Registry {
+            head: AtomicPtr::new(ptr::null_mut()),
+            slice: Once::new(),
+            mirror: Some(original),
+            default_key: "",
+        }
+    }
+
+    pub fn register(&self, node: &'static Node<T>) {
+        if let Some(original) = self.mirror {
+            return unsafe { (*original()).register(node) };
+        }
+        if node.registered.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let node_ptr = node as *const Node<T> as *mut Node<T>;
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            node.next.store(head, Ordering::Relaxed);
+            match self.head.compare_exchange_weak(
+                head,
+                node_ptr,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    pub fn slice(&self) -> &'static [T] {
+        if let Some(original) = self.mirror {
+            return unsafe { (*original()).slice() };
+        }
+        let slice = *self.slice.get_or_init(|| {
+            let mut nodes = Vec::new();
+            let mut node = self.head.load(Ordering::Acquire);
+            while let Some(current) = unsafe { node.as_ref() } {
+                nodes.push(current);
+                node = current.next.load(Ordering::Relaxed);
+            }
+            // Pushed in reverse order of registration. Elements without a
+            // sort key have an empty one and come first, as with the
+            // section-based backends, unless the slice is ordered.
+            nodes.reverse();
+            nodes.sort_by_key(|node| match node.sort_key {
+                "" => self.default_key,
+                sort_key => sort_key,
+            });
+            let elements: Vec<T> = nodes
+                .iter()
+                .map(|node| unsafe { ptr::read(node.element) })
+                .collect();
+            Box::into_raw(elements.into_boxed_slice())
+        });
+        unsafe { &*slice }
+    }
+}
+
+impl<T> Node<T> {
+    pub const fn new(element: &'static T, sort_key: &'static str) -> Self {
+        Node {
+            element,
+            sort_key,
+            registered: AtomicBool::new(false),
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
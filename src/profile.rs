@@ -0,0 +1,129 @@
+//! Profiler backends linked in by adding a crate.
+//!
+//! Instrumented code opens scopes with [`profile_scope!`]. Every function
+//! registered into [`HOOKS`] is informed when a scope is entered and exited,
+//! so a backend such as Tracy or puffin is enabled by linking a crate that
+//! registers a hook, without threading a callback through every layer of the
+//! program. With no hooks linked, a scope costs one check of an empty slice.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::profile::{ScopeEvent, ScopeKind, HOOKS};
+//!
+//! // In a backend crate.
+//! #[distributed_slice(HOOKS)]
+//! fn print_scopes(event: &ScopeEvent) {
+//!     let arrow = match event.kind {
+//!         ScopeKind::Enter => "->",
+//!         ScopeKind::Exit => "<-",
+//!     };
+//!     eprintln!("{} {} ({}:{})", arrow, event.name, event.file, event.line);
+//! }
+//!
+//! // In instrumented code.
+//! fn decode_frame() {
+//!     linkme::profile_scope!("decode_frame");
+//!     /* ... */
+//! }
+//! #
+//! # fn main() {
+//! #     decode_frame();
+//! # }
+//! ```
+//!
+//! [`profile_scope!`]: crate::profile_scope!
+
+use crate::distributed_slice;
+
+/// Hooks notified of every profiling scope in the program.
+///
+/// These have the type `DistributedSlice<[fn(&ScopeEvent)]>`.
+pub use self::LINKME_PROFILE as HOOKS;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_PROFILE: [fn(&ScopeEvent)] = [..];
+
+/// Notification passed to each profiling hook.
+#[derive(Copy, Clone, Debug)]
+pub struct ScopeEvent {
+    pub kind: ScopeKind,
+    pub name: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+}
+
+/// Whether a scope is being entered or exited.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScopeKind {
+    Enter,
+    Exit,
+}
+
+/// Delivers an event to every registered hook.
+#[inline]
+pub fn emit(event: &ScopeEvent) {
+    for hook in LINKME_PROFILE {
+        hook(event);
+    }
+}
+
+/// Whether any profiling hook is linked into the program.
+#[inline]
+pub fn is_enabled() -> bool {
+    !LINKME_PROFILE.is_empty()
+}
+
+/// Guard representing an open scope; emits the exit event when dropped.
+///
+/// Usually created by [`profile_scope!`].
+///
+/// [`profile_scope!`]: crate::profile_scope!
+pub struct Scope {
+    name: &'static str,
+    file: &'static str,
+    line: u32,
+}
+
+impl Scope {
+    /// Emits the enter event for a scope and returns the guard that will
+    /// emit its exit event.
+    #[inline]
+    pub fn enter(name: &'static str, file: &'static str, line: u32) -> Self {
+        emit(&ScopeEvent {
+            kind: ScopeKind::Enter,
+            name,
+            file,
+            line,
+        });
+        Scope { name, file, line }
+    }
+}
+
+impl Drop for Scope {
+    #[inline]
+    fn drop(&mut self) {
+        emit(&ScopeEvent {
+            kind: ScopeKind::Exit,
+            name: self.name,
+            file: self.file,
+            line: self.line,
+        });
+    }
+}
+
+/// Profiles the rest of the enclosing block as a scope with the given name.
+///
+/// ```
+/// fn decode_frame() {
+///     linkme::profile_scope!("decode_frame");
+///     /* ... */
+/// }
+/// ```
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _linkme_profile_scope = $crate::profile::Scope::enter($name, file!(), line!());
+    };
+}
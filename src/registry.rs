@@ -0,0 +1,125 @@
+//! Abstraction over where a collection of registrations comes from.
+//!
+//! Code that consumes a distributed slice is awkward to unit test, because the
+//! elements are whatever the final binary happened to link in. Libraries that
+//! take a [`Registry`] instead of a concrete slice can be handed a
+//! [`DistributedSlice`] or a [`RuntimeSlice`] in production and an ordinary
+//! `&[T]` in tests, without the linker being involved.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::registry::Registry;
+//!
+//! pub struct Route {
+//!     pub path: &'static str,
+//! }
+//!
+//! pub fn paths<R: Registry<Route> + ?Sized>(routes: &R) -> Vec<&'static str> {
+//!     routes.iter().map(|route| route.path).collect()
+//! }
+//!
+//! #[distributed_slice]
+//! pub static ROUTES: [Route] = [..];
+//!
+//! #[distributed_slice(ROUTES)]
+//! static INDEX: Route = Route { path: "/" };
+//!
+//! fn main() {
+//!     assert_eq!(paths(&ROUTES), ["/"]);
+//!
+//!     let fake: &[Route] = &[Route { path: "/a" }, Route { path: "/b" }];
+//!     assert_eq!(paths(fake), ["/a", "/b"]);
+//! }
+//! ```
+//!
+//! [`RuntimeSlice`]: crate::runtime::RuntimeSlice
+
+use crate::DistributedSlice;
+use core::marker::PhantomData;
+
+/// Indexable source of registered entries.
+///
+/// Entries are numbered from 0 to `len() - 1`. An implementation may grow
+/// while it is being read, as [`RuntimeSlice`] does, but must never remove or
+/// move an entry once it has been counted by `len`.
+///
+/// [`RuntimeSlice`]: crate::runtime::RuntimeSlice
+pub trait Registry<T> {
+    /// Number of entries currently present.
+    fn len(&self) -> usize;
+
+    /// Entry at position `index`, or `None` past the end.
+    fn get(&self, index: usize) -> Option<&T>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the entries present when the iterator is created.
+    fn iter(&self) -> Iter<'_, T, Self> {
+        Iter {
+            registry: self,
+            next: 0,
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Registry<T> for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        <[T]>::get(self, index)
+    }
+}
+
+impl<T: 'static> Registry<T> for DistributedSlice<[T]> {
+    fn len(&self) -> usize {
+        self.static_slice().len()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        self.static_slice().get(index)
+    }
+}
+
+impl<T, R: Registry<T> + ?Sized> Registry<T> for &R {
+    fn len(&self) -> usize {
+        R::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        R::get(self, index)
+    }
+}
+
+/// Iterator over the entries of a [`Registry`].
+pub struct Iter<'a, T, R: ?Sized> {
+    registry: &'a R,
+    next: usize,
+    len: usize,
+    marker: PhantomData<fn() -> &'a T>,
+}
+
+impl<'a, T: 'a, R: Registry<T> + ?Sized> Iterator for Iter<'a, T, R> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.len {
+            return None;
+        }
+        let entry = self.registry.get(self.next);
+        self.next += 1;
+        entry
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: 'a, R: Registry<T> + ?Sized> ExactSizeIterator for Iter<'a, T, R> {}
@@ -0,0 +1,106 @@
+//! RTIC plumbing contributed by crates outside the `#[rtic::app]` module.
+//!
+//! An RTIC application is one macro invocation, so crates that are not part
+//! of it cannot declare tasks or monotonics of their own. These registries
+//! let them contribute the work instead, and leave the app with a fixed
+//! amount of glue:
+//!
+//! - each [`Monotonic`] configures a timer and is started from `#[init]` with
+//!   [`start_monotonics`];
+//! - each [`Task`] names the RTIC priority it must run at, and the app
+//!   declares one software task per priority level in use whose body calls
+//!   [`run_at`].
+//!
+//! The context type `C` is chosen by the application and holds whatever the
+//! contributed functions need, typically the peripherals it owns.
+//!
+//! ```
+//! use linkme::rtic::{self, Monotonic, Task};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! pub struct Board {
+//!     pub ticks: u32,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static MONOTONICS: [Monotonic<Board>] = [..];
+//!
+//! #[distributed_slice]
+//! pub static TASKS: [Task<Board>] = [..];
+//!
+//! // In a driver crate.
+//! #[distributed_slice(MONOTONICS)]
+//! static SYSTICK: Monotonic<Board> = Monotonic {
+//!     name: "systick",
+//!     start: |board| board.ticks = 0,
+//! };
+//!
+//! #[distributed_slice(TASKS)]
+//! static BLINK: Task<Board> = Task {
+//!     name: "blink",
+//!     priority: 1,
+//!     run: |board| board.ticks += 1,
+//! };
+//!
+//! // In the app. With RTIC these calls go in `#[init]` and in a
+//! // `#[task(priority = 1)]` software task respectively.
+//! fn main() {
+//!     duplicate::check(&TASKS, |task| task.name).unwrap();
+//!     assert!(rtic::undispatched(&TASKS, 3).is_none());
+//!
+//!     let mut board = Board { ticks: 99 };
+//!     rtic::start_monotonics(&MONOTONICS, &mut board);
+//!     rtic::run_at(&TASKS, 1, &mut board);
+//!     assert_eq!(board.ticks, 1);
+//! }
+//! ```
+
+use crate::order;
+
+/// Timer configuration started during the app's `#[init]`.
+pub struct Monotonic<C: 'static> {
+    pub name: &'static str,
+    pub start: fn(&mut C),
+}
+
+/// Unit of work run from the app's software task at `priority`.
+pub struct Task<C: 'static> {
+    pub name: &'static str,
+    /// RTIC priority level, where 1 is the lowest software task priority.
+    pub priority: u8,
+    pub run: fn(&mut C),
+}
+
+/// Starts every registered monotonic, in order of name.
+pub fn start_monotonics<C>(monotonics: &[Monotonic<C>], ctx: &mut C) {
+    for monotonic in order::by_key(monotonics, |monotonic| monotonic.name) {
+        (monotonic.start)(ctx);
+    }
+}
+
+/// Runs every task registered at the given priority, in order of name.
+///
+/// Call this from the app's software task declared at that same priority, so
+/// the tasks preempt and are preempted exactly as if they had been declared in
+/// the app.
+pub fn run_at<C>(tasks: &[Task<C>], priority: u8, ctx: &mut C) {
+    for task in order::by_key(tasks, |task| task.name) {
+        if task.priority == priority {
+            (task.run)(ctx);
+        }
+    }
+}
+
+/// Whether any task is registered at the given priority, meaning the app
+/// needs a software task there.
+pub fn uses_priority<C>(tasks: &[Task<C>], priority: u8) -> bool {
+    tasks.iter().any(|task| task.priority == priority)
+}
+
+/// Finds a task whose priority is not between 1 and `max_priority`, the
+/// highest level the app dispatches, and which would therefore never run.
+pub fn undispatched<C>(tasks: &[Task<C>], max_priority: u8) -> Option<&Task<C>> {
+    tasks
+        .iter()
+        .find(|task| task.priority == 0 || task.priority > max_priority)
+}
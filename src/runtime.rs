@@ -0,0 +1,284 @@
+//! Entries added at runtime alongside a distributed slice's linked entries.
+//!
+//! A [`RuntimeSlice`] sits in a static next to a distributed slice and accepts
+//! additional entries while the program runs, for plugins loaded late or
+//! configuration-driven registrations. Readers never take a lock: each
+//! [`Snapshot`] captures the number of entries present when it was taken and
+//! iterates exactly those, while writers append to a list of segments that
+//! are never moved or freed, so references handed out stay valid.
+//!
+//! Requires the `alloc` feature.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::runtime::RuntimeSlice;
+//!
+//! pub struct Handler {
+//!     pub route: &'static str,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static LINKED: [Handler] = [..];
+//!
+//! pub static HANDLERS: RuntimeSlice<Handler> = RuntimeSlice::new(&LINKED);
+//!
+//! #[distributed_slice(LINKED)]
+//! static INDEX: Handler = Handler { route: "/" };
+//!
+//! fn main() {
+//!     HANDLERS.push(Handler { route: "/plugin" });
+//!
+//!     let snapshot = HANDLERS.snapshot();
+//!     HANDLERS.push(Handler { route: "/late" });
+//!
+//!     let routes: Vec<_> = snapshot.iter().map(|handler| handler.route).collect();
+//!     assert_eq!(routes, ["/", "/plugin"]);
+//!     assert_eq!(HANDLERS.snapshot().len(), 3);
+//! }
+//! ```
+
+use crate::registry::Registry;
+use crate::DistributedSlice;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::hint;
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+// Segment k holds FIRST << k entries, so capacity doubles with each segment
+// and no entry ever needs to move.
+const FIRST: usize = 4;
+const SEGMENTS: usize = 32;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_SEGMENT: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Distributed slice that also accepts entries at runtime.
+pub struct RuntimeSlice<T: 'static> {
+    linked: &'static DistributedSlice<[T]>,
+    segments: [AtomicPtr<()>; SEGMENTS],
+    len: AtomicUsize,
+    writing: AtomicBool,
+}
+
+unsafe impl<T: Send + Sync> Sync for RuntimeSlice<T> {}
+unsafe impl<T: Send> Send for RuntimeSlice<T> {}
+
+impl<T> RuntimeSlice<T> {
+    /// Creates a runtime slice extending `linked`, for use as a static.
+    pub const fn new(linked: &'static DistributedSlice<[T]>) -> Self {
+        RuntimeSlice {
+            linked,
+            segments: [NO_SEGMENT; SEGMENTS],
+            len: AtomicUsize::new(0),
+            writing: AtomicBool::new(false),
+        }
+    }
+
+    /// Appends an entry, returning a reference to it in its final location.
+    ///
+    /// Concurrent pushes are serialized with each other but never block
+    /// readers. The entry becomes visible to snapshots taken after this
+    /// returns.
+    pub fn push(&self, value: T) -> &T {
+        while self
+            .writing
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        let unlock = Unlock(&self.writing);
+
+        let i = self.len.load(Ordering::Relaxed);
+        let (k, offset) = locate(i);
+        let mut segment = self.segments[k].load(Ordering::Relaxed).cast::<T>();
+        if segment.is_null() {
+            let storage: Box<[MaybeUninit<T>]> =
+                (0..FIRST << k).map(|_| MaybeUninit::uninit()).collect();
+            segment = Box::into_raw(storage).cast::<T>();
+            self.segments[k].store(segment.cast(), Ordering::Release);
+        }
+        let slot = unsafe { segment.add(offset) };
+        unsafe { ptr::write(slot, value) };
+        self.len.store(i + 1, Ordering::Release);
+
+        drop(unlock);
+        unsafe { &*slot }
+    }
+
+    /// Captures the entries currently present, linked ones first.
+    pub fn snapshot(&self) -> Snapshot<'_, T> {
+        Snapshot {
+            slice: self,
+            added: self.len.load(Ordering::Acquire),
+        }
+    }
+
+    /// Iterates over a snapshot taken now.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.snapshot().iter()
+    }
+
+    fn added(&self, i: usize) -> &T {
+        let (k, offset) = locate(i);
+        let segment = self.segments[k].load(Ordering::Acquire).cast::<T>();
+        unsafe { &*segment.add(offset) }
+    }
+}
+
+impl<T> Drop for RuntimeSlice<T> {
+    fn drop(&mut self) {
+        let mut remaining = *self.len.get_mut();
+        for (k, segment) in self.segments.iter_mut().enumerate() {
+            let segment = segment.get_mut().cast::<MaybeUninit<T>>();
+            if segment.is_null() {
+                break;
+            }
+            let capacity = FIRST << k;
+            let mut storage =
+                unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(segment, capacity)) };
+            for entry in &mut storage[..remaining.min(capacity)] {
+                unsafe { entry.assume_init_drop() };
+            }
+            remaining = remaining.saturating_sub(capacity);
+        }
+    }
+}
+
+impl<T> Registry<T> for RuntimeSlice<T> {
+    fn len(&self) -> usize {
+        self.snapshot().len()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        self.snapshot().get(index)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RuntimeSlice<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Segment and offset within it of the i'th runtime entry.
+fn locate(i: usize) -> (usize, usize) {
+    let j = i / FIRST + 1;
+    let k = (usize::BITS - 1 - j.leading_zeros()) as usize;
+    (k, i - FIRST * ((1 << k) - 1))
+}
+
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Entries of a [`RuntimeSlice`] as of some point in time.
+pub struct Snapshot<'a, T: 'static> {
+    slice: &'a RuntimeSlice<T>,
+    added: usize,
+}
+
+impl<'a, T> Snapshot<'a, T> {
+    /// Number of entries, linked and added.
+    pub fn len(&self) -> usize {
+        self.slice.linked.len() + self.added
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Entry at position `i`, counting linked entries first.
+    pub fn get(&self, i: usize) -> Option<&'a T> {
+        let linked = self.slice.linked.static_slice();
+        match i.checked_sub(linked.len()) {
+            None => Some(&linked[i]),
+            Some(i) if i < self.added => Some(self.slice.added(i)),
+            Some(_) => None,
+        }
+    }
+
+    /// Every entry, linked ones in slice order followed by added ones in the
+    /// order they were pushed.
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter {
+            slice: self.slice,
+            linked: self.slice.linked.static_slice().iter(),
+            next: 0,
+            added: self.added,
+        }
+    }
+
+    /// Copies the references into a vector, for callers that need random
+    /// access to the whole snapshot.
+    pub fn to_vec(&self) -> Vec<&'a T> {
+        self.iter().collect()
+    }
+}
+
+impl<T> Clone for Snapshot<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Snapshot<'_, T> {}
+
+impl<'a, T> IntoIterator for Snapshot<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &Snapshot<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`Snapshot`].
+pub struct Iter<'a, T: 'static> {
+    slice: &'a RuntimeSlice<T>,
+    linked: slice::Iter<'static, T>,
+    next: usize,
+    added: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(entry) = self.linked.next() {
+            return Some(entry);
+        }
+        if self.next == self.added {
+            return None;
+        }
+        let entry = self.slice.added(self.next);
+        self.next += 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.linked.len() + self.added - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
@@ -0,0 +1,71 @@
+//! Named scenarios contributed by content crates to a simulation harness.
+//!
+//! A game or simulation harness declares a distributed slice of
+//! [`Scenario`]s over its world type, and content crates register the
+//! scenarios they provide. The harness lists them for a menu or command line
+//! with [`menu`], and drives the one picked with [`run`] or by calling the
+//! setup, tick and teardown functions itself.
+//!
+//! ```
+//! use linkme::scenario::{self, Scenario};
+//! use linkme::{distributed_slice, duplicate};
+//!
+//! pub struct World {
+//!     pub population: u32,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static SCENARIOS: [Scenario<World>] = [..];
+//!
+//! // In a content crate.
+//! #[distributed_slice(SCENARIOS)]
+//! static BOOM: Scenario<World> = Scenario {
+//!     name: "boom",
+//!     description: "Population doubles every tick",
+//!     setup: |world| world.population = 1,
+//!     tick: |world| world.population *= 2,
+//!     teardown: |_world| {},
+//! };
+//!
+//! fn main() {
+//!     duplicate::check(&SCENARIOS, |scenario| scenario.name).unwrap();
+//!     for scenario in scenario::menu(&SCENARIOS) {
+//!         println!("{:10} {}", scenario.name, scenario.description);
+//!     }
+//!
+//!     let mut world = World { population: 0 };
+//!     let boom = SCENARIOS.iter().find(|scenario| scenario.name == "boom");
+//!     scenario::run(boom.unwrap(), &mut world, 10);
+//!     assert_eq!(world.population, 1024);
+//! }
+//! ```
+
+use crate::order;
+
+/// Scenario operating on the harness's world type `W`.
+pub struct Scenario<W: 'static> {
+    pub name: &'static str,
+    /// One-line description shown in menus.
+    pub description: &'static str,
+    /// Prepares the world before the first tick.
+    pub setup: fn(&mut W),
+    /// Advances the world by one step.
+    pub tick: fn(&mut W),
+    /// Releases anything the scenario set up, after the last tick.
+    pub teardown: fn(&mut W),
+}
+
+/// Scenarios in order of name, for presenting in a menu or `--help`.
+pub fn menu<W>(scenarios: &[Scenario<W>]) -> impl Iterator<Item = &Scenario<W>> {
+    order::by_key(scenarios, |scenario| scenario.name)
+}
+
+/// Sets up the scenario, ticks it the given number of times, and tears it
+/// down.
+pub fn run<W>(scenario: &Scenario<W>, world: &mut W, ticks: u64) {
+    (scenario.setup)(world);
+    for _ in 0..ticks {
+        (scenario.tick)(world);
+    }
+    (scenario.teardown)(world);
+}
@@ -0,0 +1,177 @@
+//! Unix signal handlers from many crates sharing one handler per signal.
+//!
+//! When several crates each install a handler for the same signal with
+//! `sigaction`, the last one to do so wins and the others silently stop
+//! receiving the signal. Instead, crates register a [`SignalHandler`] into
+//! [`HANDLERS`] and the application calls [`install`] once. It installs a
+//! single process-wide handler for each signal that any crate is interested
+//! in, which dispatches to every registered handler for that signal.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::signal::{self, SignalHandler, HANDLERS, SIGHUP};
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//!
+//! static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+//!
+//! #[distributed_slice(HANDLERS)]
+//! static RELOAD: SignalHandler = SignalHandler {
+//!     signal: SIGHUP,
+//!     handler: |_| RELOAD_REQUESTED.store(true, Ordering::SeqCst),
+//! };
+//!
+//! fn main() {
+//!     signal::install().unwrap();
+//!     /* ... */
+//! }
+//! ```
+//!
+//! Handlers run inside the signal handler, so they are subject to the usual
+//! restrictions: only async-signal-safe operations are allowed, which rules
+//! out allocating, locking, and most I/O. Setting an atomic flag or writing to
+//! a self-pipe are the typical things to do.
+//!
+//! Handlers are installed with `SA_RESTART`, so that system calls interrupted
+//! by a signal are restarted instead of failing with `EINTR`.
+//!
+//! Available on Linux on x86, x86_64, ARM, AArch64 and RISC-V, and on macOS,
+//! FreeBSD and illumos, whose `sigaction` layout this module declares.
+
+use crate::distributed_slice;
+use core::fmt::{self, Display};
+use core::mem;
+use core::ptr;
+
+/// Handlers dispatched to by the signal handlers that [`install`] installs.
+///
+/// These have the type `DistributedSlice<[SignalHandler]>`.
+pub use self::LINKME_SIGNALS as HANDLERS;
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = crate)]
+pub static LINKME_SIGNALS: [SignalHandler] = [..];
+
+/// Hangup, by convention a request to reload configuration.
+pub const SIGHUP: i32 = 1;
+/// Interrupt from the keyboard.
+pub const SIGINT: i32 = 2;
+/// Quit from the keyboard.
+pub const SIGQUIT: i32 = 3;
+/// Termination request.
+pub const SIGTERM: i32 = 15;
+
+/// Interest of one handler in one signal.
+pub struct SignalHandler {
+    /// Signal number. The constants in this module cover the signals whose
+    /// number is the same on every Unix; use the platform's values for others.
+    pub signal: i32,
+    /// Called with the signal number each time the signal is delivered.
+    pub handler: fn(i32),
+}
+
+// The parts of <signal.h> used here. The mask is left empty, so only its size
+// matters.
+#[cfg(target_os = "linux")]
+mod sys {
+    #[repr(C)]
+    pub struct SigAction {
+        pub handler: usize,
+        pub mask: [u8; 128],
+        pub flags: i32,
+        pub restorer: usize,
+    }
+
+    pub const SA_RESTART: i32 = 0x1000_0000;
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    #[repr(C)]
+    pub struct SigAction {
+        pub handler: usize,
+        pub mask: u32,
+        pub flags: i32,
+    }
+
+    pub const SA_RESTART: i32 = 0x2;
+}
+
+#[cfg(target_os = "freebsd")]
+mod sys {
+    #[repr(C)]
+    pub struct SigAction {
+        pub handler: usize,
+        pub flags: i32,
+        pub mask: [u32; 4],
+    }
+
+    pub const SA_RESTART: i32 = 0x2;
+}
+
+#[cfg(target_os = "illumos")]
+mod sys {
+    #[repr(C)]
+    pub struct SigAction {
+        pub flags: i32,
+        pub handler: usize,
+        pub mask: [u32; 4],
+    }
+
+    pub const SA_RESTART: i32 = 0x4;
+}
+
+extern "C" {
+    fn sigaction(signum: i32, act: *const sys::SigAction, oldact: *mut sys::SigAction) -> i32;
+}
+
+/// Installs one handler for each signal mentioned in [`HANDLERS`],
+/// replacing any handler previously installed for those signals.
+///
+/// Signals that no registered handler is interested in are left alone.
+pub fn install() -> Result<(), InstallError> {
+    for (i, registration) in HANDLERS.iter().enumerate() {
+        let first = HANDLERS[..i]
+            .iter()
+            .all(|earlier| earlier.signal != registration.signal);
+        if first {
+            let mut action: sys::SigAction = unsafe { mem::zeroed() };
+            action.handler = dispatch as extern "C" fn(i32) as usize;
+            action.flags = sys::SA_RESTART;
+            if unsafe { sigaction(registration.signal, &action, ptr::null_mut()) } != 0 {
+                return Err(InstallError {
+                    signal: registration.signal,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+extern "C" fn dispatch(signum: i32) {
+    for registration in HANDLERS {
+        if registration.signal == signum {
+            (registration.handler)(signum);
+        }
+    }
+}
+
+/// Error returned by [`install`] when the operating system refuses a
+/// handler, for example for an invalid signal number.
+#[derive(Debug)]
+pub struct InstallError {
+    pub signal: i32,
+}
+
+impl Display for InstallError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "failed to install handler for signal {}",
+            self.signal,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InstallError {}
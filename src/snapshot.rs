@@ -0,0 +1,214 @@
+//! Snapshot test cases discovered from anywhere in a workspace.
+//!
+//! Each case is a [`SnapshotCase`] registered into a distributed slice owned by
+//! the test crate, so cases can be defined next to the code they exercise
+//! without `mod` declarations collecting them. A single `#[test]` runs them
+//! all with [`run`], which compares the output of every case against the
+//! snapshot file named after it.
+//!
+//! Requires the `std` feature.
+//!
+//! ```no_run
+//! use linkme::distributed_slice;
+//! use linkme::snapshot::{self, Mode, Settings, SnapshotCase};
+//! use std::path::Path;
+//!
+//! #[distributed_slice]
+//! pub static SNAPSHOTS: [SnapshotCase] = [..];
+//!
+//! #[distributed_slice(SNAPSHOTS)]
+//! static RENDER_EMPTY: SnapshotCase = SnapshotCase {
+//!     name: "render_empty",
+//!     input: || format!("{:#?}", Vec::<u8>::new()),
+//!     settings: Settings::DEFAULT,
+//! };
+//!
+//! #[test]
+//! fn snapshots() {
+//!     let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+//!     snapshot::run(&SNAPSHOTS, &dir, Mode::from_env()).assert_ok();
+//! }
+//! #
+//! # fn main() {}
+//! ```
+
+use crate::order;
+use std::env;
+use std::fmt::{self, Display};
+use std::format;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::Path;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Snapshot test case registered by some crate.
+pub struct SnapshotCase {
+    /// Name of the case, which is also the file name of its snapshot.
+    pub name: &'static str,
+    /// Produces the output to compare against the stored snapshot.
+    pub input: fn() -> String,
+    pub settings: Settings,
+}
+
+/// How a case's output is compared and stored.
+#[derive(Copy, Clone, Debug)]
+pub struct Settings {
+    /// Extension of the snapshot file, without the leading dot.
+    pub extension: &'static str,
+    /// Whether to ignore trailing whitespace at the end of the output.
+    pub trim: bool,
+}
+
+impl Settings {
+    pub const DEFAULT: Settings = Settings {
+        extension: "snap",
+        trim: true,
+    };
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings::DEFAULT
+    }
+}
+
+/// Whether mismatched snapshots fail or are rewritten.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Cases whose output differs from their snapshot, or that have no
+    /// snapshot yet, fail.
+    Compare,
+    /// Snapshots are written from the current output of every case.
+    Update,
+}
+
+impl Mode {
+    /// [`Mode::Update`] if the `UPDATE_SNAPSHOTS` environment variable is
+    /// set to anything other than `0`, otherwise [`Mode::Compare`].
+    pub fn from_env() -> Self {
+        match env::var_os("UPDATE_SNAPSHOTS") {
+            Some(ref value) if value != "0" => Mode::Update,
+            _ => Mode::Compare,
+        }
+    }
+}
+
+/// Result of one case within a [`Report`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Matched,
+    /// The snapshot was created or rewritten in [`Mode::Update`].
+    Written,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// There is no stored snapshot for the case.
+    Missing {
+        actual: String,
+    },
+    /// The input function panicked.
+    Panicked,
+    /// The snapshot file could not be read or written.
+    Io(String),
+}
+
+impl Outcome {
+    fn is_ok(&self) -> bool {
+        match self {
+            Outcome::Matched | Outcome::Written => true,
+            _ => false,
+        }
+    }
+}
+
+/// Outcome of every case, in order of name.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub cases: Vec<(&'static str, Outcome)>,
+}
+
+impl Report {
+    /// Whether every case matched its snapshot or had it written.
+    pub fn is_ok(&self) -> bool {
+        self.cases.iter().all(|(_, outcome)| outcome.is_ok())
+    }
+
+    /// Panics with a description of every failed case, for use as the body
+    /// of a `#[test]`.
+    pub fn assert_ok(&self) {
+        assert!(self.is_ok(), "snapshot tests failed:\n{}", self);
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (name, outcome) in &self.cases {
+            match outcome {
+                Outcome::Matched => writeln!(formatter, "{}: ok", name)?,
+                Outcome::Written => writeln!(formatter, "{}: written", name)?,
+                Outcome::Mismatch { expected, actual } => {
+                    writeln!(formatter, "{}: mismatch", name)?;
+                    writeln!(formatter, "--- expected\n{}", expected)?;
+                    writeln!(formatter, "+++ actual\n{}", actual)?;
+                }
+                Outcome::Missing { actual } => {
+                    writeln!(formatter, "{}: no snapshot, set UPDATE_SNAPSHOTS=1", name)?;
+                    writeln!(formatter, "+++ actual\n{}", actual)?;
+                }
+                Outcome::Panicked => writeln!(formatter, "{}: panicked", name)?,
+                Outcome::Io(message) => writeln!(formatter, "{}: {}", name, message)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs every case in order of name, comparing against or writing the
+/// snapshots stored in `dir`.
+pub fn run(cases: &[SnapshotCase], dir: &Path, mode: Mode) -> Report {
+    let mut report = Report { cases: Vec::new() };
+    for case in order::by_key(cases, |case| case.name) {
+        report.cases.push((case.name, run_case(case, dir, mode)));
+    }
+    report
+}
+
+fn run_case(case: &SnapshotCase, dir: &Path, mode: Mode) -> Outcome {
+    let actual = match panic::catch_unwind(case.input) {
+        Ok(actual) => normalize(actual, case.settings),
+        Err(_) => return Outcome::Panicked,
+    };
+
+    let path = dir.join(format!("{}.{}", case.name, case.settings.extension));
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => Some(normalize(expected, case.settings)),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Outcome::Io(format!("failed to read {}: {}", path.display(), err)),
+    };
+
+    match (mode, expected) {
+        (_, Some(ref expected)) if *expected == actual => Outcome::Matched,
+        (Mode::Compare, Some(expected)) => Outcome::Mismatch { expected, actual },
+        (Mode::Compare, None) => Outcome::Missing { actual },
+        (Mode::Update, _) => {
+            let written = fs::create_dir_all(dir).and_then(|()| fs::write(&path, &actual));
+            match written {
+                Ok(()) => Outcome::Written,
+                Err(err) => Outcome::Io(format!("failed to write {}: {}", path.display(), err)),
+            }
+        }
+    }
+}
+
+fn normalize(output: String, settings: Settings) -> String {
+    if settings.trim {
+        let mut trimmed = output.trim_end().to_string();
+        trimmed.push('\n');
+        trimmed
+    } else {
+        output
+    }
+}
@@ -0,0 +1,146 @@
+//! Small integer IDs for registered elements that stay the same across
+//! builds.
+//!
+//! Serialized data that refers to a registered codec, message type or similar
+//! by its position in a distributed slice breaks as soon as the link order
+//! changes. This module offers two stable alternatives, both derived from a
+//! name the element carries:
+//!
+//! - [`hash`] is computed at compile time from the name alone, so it does not
+//!   change when elements are added or removed elsewhere. Run [`validate`] in
+//!   a test to catch the rare collision.
+//! - [`index_of`] is the element's position in lexicographic order of name.
+//!   It yields dense IDs suitable for indexing a table, which stay the same
+//!   across builds that link the same set of elements.
+//!
+//! Requires rustc 1.46 or newer. On older compilers the module is left out.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::stable_id;
+//!
+//! pub struct Codec {
+//!     pub name: &'static str,
+//!     pub id: u32,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static CODECS: [Codec] = [..];
+//!
+//! #[distributed_slice(CODECS)]
+//! static JSON: Codec = Codec {
+//!     name: "json",
+//!     id: stable_id::hash("json"),
+//! };
+//!
+//! #[distributed_slice(CODECS)]
+//! static CBOR: Codec = Codec {
+//!     name: "cbor",
+//!     id: stable_id::hash("cbor"),
+//! };
+//!
+//! fn name(codec: &Codec) -> &'static str {
+//!     codec.name
+//! }
+//!
+//! fn main() {
+//!     stable_id::validate(&CODECS, name).unwrap();
+//!
+//!     let json = stable_id::by_hash(&CODECS, name, JSON.id).unwrap();
+//!     assert_eq!(json.name, "json");
+//!
+//!     assert_eq!(stable_id::index_of(&CODECS, name, "cbor"), Some(0));
+//!     assert_eq!(stable_id::by_index(&CODECS, name, 1).unwrap().name, "json");
+//! }
+//! ```
+
+use crate::{duplicate, order};
+use core::fmt::{self, Display};
+
+/// 32-bit FNV-1a hash of `name`, usable in const context.
+///
+/// The algorithm is fixed, so the same name gives the same ID on every
+/// target and with every compiler version.
+pub const fn hash(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut hash: u32 = 0x811c_9dc5;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        i += 1;
+    }
+    hash
+}
+
+/// Finds the element whose name hashes to `id`.
+pub fn by_hash<T, F>(items: &[T], name: F, id: u32) -> Option<&T>
+where
+    F: Fn(&T) -> &'static str,
+{
+    items.iter().find(|item| hash(name(item)) == id)
+}
+
+/// Position of the element named `target` in lexicographic order of name.
+#[allow(clippy::cast_possible_truncation)]
+pub fn index_of<T, F>(items: &[T], name: F, target: &str) -> Option<u32>
+where
+    F: Fn(&T) -> &'static str,
+{
+    if !items.iter().any(|item| name(item) == target) {
+        return None;
+    }
+    Some(items.iter().filter(|item| name(item) < target).count() as u32)
+}
+
+/// Element at position `index` in lexicographic order of name.
+pub fn by_index<T, F>(items: &[T], name: F, index: u32) -> Option<&T>
+where
+    F: Fn(&T) -> &'static str,
+{
+    order::by_key(items, name).nth(index as usize)
+}
+
+/// Checks that no two elements share a name or a hash.
+///
+/// With unique names, dense IDs from [`index_of`] are unique too; hash IDs
+/// additionally need the hashes of distinct names not to collide.
+pub fn validate<T, F>(items: &[T], name: F) -> Result<(), Collision>
+where
+    F: Fn(&T) -> &'static str,
+{
+    let names = items.iter().map(&name);
+    match duplicate::find(names, |a, b| hash(a) == hash(b)) {
+        None => Ok(()),
+        Some((first, second)) => Err(Collision {
+            first,
+            second,
+            id: hash(first),
+        }),
+    }
+}
+
+/// Error returned by [`validate`] when two elements would get the same ID.
+#[derive(Debug)]
+pub struct Collision {
+    pub first: &'static str,
+    pub second: &'static str,
+    pub id: u32,
+}
+
+impl Display for Collision {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.first == self.second {
+            write!(formatter, "name {:?} is registered twice", self.first)
+        } else {
+            write!(
+                formatter,
+                "names {:?} and {:?} both hash to {:#010x}",
+                self.first, self.second, self.id,
+            )
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Collision {}
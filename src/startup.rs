@@ -0,0 +1,146 @@
+//! Async startup routines contributed by any crate in the service.
+//!
+//! Each crate that needs to do asynchronous work at boot, such as warming a
+//! cache, running migrations or registering with service discovery, adds a
+//! [`Startup`] to a distributed slice owned by the application. The
+//! application awaits [`run_all`] under whichever async runtime it uses,
+//! instead of collecting the routines by hand in `main`.
+//!
+//! Requires the `alloc` feature.
+//!
+//! ```
+//! # async fn connect(_url: &str) -> Result<(), std::io::Error> { Ok(()) }
+//! # fn block_on<F: std::future::Future>(_: F) {}
+//! use linkme::distributed_slice;
+//! use linkme::startup::{self, Startup};
+//!
+//! #[derive(Clone)]
+//! pub struct Context {
+//!     pub database_url: &'static str,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static STARTUP: [Startup<Context, std::io::Error>] = [..];
+//!
+//! #[distributed_slice(STARTUP)]
+//! static DATABASE: Startup<Context, std::io::Error> = Startup {
+//!     name: "database",
+//!     run: |ctx| Box::pin(async move { connect(ctx.database_url).await }),
+//! };
+//!
+//! fn main() {
+//!     let ctx = Context {
+//!         database_url: "postgres://localhost",
+//!     };
+//!     // With tokio, `runtime.block_on(...)` or `.await` in `#[tokio::main]`.
+//!     block_on(async {
+//!         if let Err(failed) = startup::run_all(&STARTUP, ctx, 4).await {
+//!             panic!("{}", failed);
+//!         }
+//!     });
+//! }
+//! ```
+
+use crate::order;
+use alloc::boxed::Box;
+use alloc::vec::{self, Vec};
+use core::fmt::{self, Display};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Owned, type-erased future, as returned by startup routines.
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Async routine run at startup with a clone of the context `C`, failing
+/// with `E`.
+pub struct Startup<C: 'static, E: 'static> {
+    pub name: &'static str,
+    pub run: fn(C) -> BoxFuture<Result<(), E>>,
+}
+
+/// Runs every registered routine, keeping at most `limit` in flight at once.
+///
+/// Routines are started in order of name; with a `limit` of 1 they run one
+/// after another in that order. A `limit` of 0 is treated as 1. The returned
+/// future completes when every routine has succeeded, or with the first
+/// failure, at which point routines still in flight are dropped.
+pub fn run_all<C: Clone, E>(startups: &[Startup<C, E>], ctx: C, limit: usize) -> RunAll<'_, C, E> {
+    let queue: Vec<_> = order::by_key(startups, |startup| startup.name).collect();
+    RunAll {
+        queue: queue.into_iter(),
+        running: Vec::new(),
+        ctx,
+        limit: if limit == 0 { 1 } else { limit },
+    }
+}
+
+/// Future returned by [`run_all`].
+pub struct RunAll<'a, C: 'static, E: 'static> {
+    queue: vec::IntoIter<&'a Startup<C, E>>,
+    running: Vec<(&'static str, BoxFuture<Result<(), E>>)>,
+    ctx: C,
+    limit: usize,
+}
+
+// Nothing is pinned structurally: the routines' futures are boxed and the
+// context is only ever cloned.
+impl<C, E> Unpin for RunAll<'_, C, E> {}
+
+impl<C: Clone, E> Future for RunAll<'_, C, E> {
+    type Output = Result<(), StartupFailed<E>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            while this.running.len() < this.limit {
+                match this.queue.next() {
+                    Some(startup) => {
+                        let future = (startup.run)(this.ctx.clone());
+                        this.running.push((startup.name, future));
+                    }
+                    None => break,
+                }
+            }
+            if this.running.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            let before = this.running.len();
+            let mut i = 0;
+            while i < this.running.len() {
+                match this.running[i].1.as_mut().poll(cx) {
+                    Poll::Pending => i += 1,
+                    Poll::Ready(Ok(())) => {
+                        drop(this.running.remove(i));
+                    }
+                    Poll::Ready(Err(error)) => {
+                        let name = this.running[i].0;
+                        this.running.clear();
+                        return Poll::Ready(Err(StartupFailed { name, error }));
+                    }
+                }
+            }
+            if this.running.len() == before {
+                return Poll::Pending;
+            }
+            // Some routines finished, making room for queued ones.
+        }
+    }
+}
+
+/// Error returned by [`run_all`] when a routine fails.
+#[derive(Debug)]
+pub struct StartupFailed<E> {
+    pub name: &'static str,
+    pub error: E,
+}
+
+impl<E> Display for StartupFailed<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "startup routine {:?} failed", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug> std::error::Error for StartupFailed<E> {}
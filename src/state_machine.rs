@@ -0,0 +1,208 @@
+//! State machine transition tables assembled from many modules.
+//!
+//! Each [`Transition`] says that in state `from`, event `event` runs `action`
+//! and moves the machine to state `to`. Transitions of one machine may be
+//! registered into its distributed slice from anywhere in the program, so a
+//! large machine can be defined next to the code implementing each of its
+//! parts.
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme::state_machine::{self, Transition};
+//!
+//! #[derive(Copy, Clone, PartialEq, Debug)]
+//! pub enum State {
+//!     Idle,
+//!     Running,
+//! }
+//!
+//! #[derive(PartialEq, Debug)]
+//! pub enum Event {
+//!     Start,
+//!     Stop,
+//! }
+//!
+//! pub struct Job {
+//!     runs: usize,
+//! }
+//!
+//! #[distributed_slice]
+//! pub static JOB: [Transition<State, Event, Job>] = [..];
+//!
+//! #[distributed_slice(JOB)]
+//! static START: Transition<State, Event, Job> = Transition {
+//!     from: State::Idle,
+//!     event: Event::Start,
+//!     action: |job| job.runs += 1,
+//!     to: State::Running,
+//! };
+//!
+//! #[distributed_slice(JOB)]
+//! static STOP: Transition<State, Event, Job> = Transition {
+//!     from: State::Running,
+//!     event: Event::Stop,
+//!     action: |_| {},
+//!     to: State::Idle,
+//! };
+//!
+//! fn main() {
+//!     # #[cfg(feature = "alloc")]
+//!     state_machine::validate(&JOB, &State::Idle).unwrap();
+//!
+//!     let mut state = State::Idle;
+//!     let mut job = Job { runs: 0 };
+//!     assert!(state_machine::fire(&JOB, &mut state, &Event::Start, &mut job));
+//!     assert_eq!(state, State::Running);
+//!     assert_eq!(job.runs, 1);
+//! }
+//! ```
+
+#[cfg(feature = "alloc")]
+use crate::duplicate;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::fmt::{self, Debug, Display};
+
+/// One edge of a state machine.
+pub struct Transition<S: 'static, E: 'static, C: 'static = ()> {
+    pub from: S,
+    pub event: E,
+    /// Runs when the transition is taken, before the machine enters `to`.
+    pub action: fn(&mut C),
+    pub to: S,
+}
+
+/// Finds the transition taken when `event` arrives in `state`.
+pub fn next<'a, S, E, C>(
+    table: &'a [Transition<S, E, C>],
+    state: &S,
+    event: &E,
+) -> Option<&'a Transition<S, E, C>>
+where
+    S: PartialEq,
+    E: PartialEq,
+{
+    table
+        .iter()
+        .find(|transition| transition.from == *state && transition.event == *event)
+}
+
+/// Handles `event` in `state`: runs the matching transition's action and moves
+/// `state` to the transition's target.
+///
+/// Returns false and leaves `state` unchanged if no transition matches.
+pub fn fire<S, E, C>(
+    table: &[Transition<S, E, C>],
+    state: &mut S,
+    event: &E,
+    context: &mut C,
+) -> bool
+where
+    S: PartialEq + Copy,
+    E: PartialEq,
+{
+    match next(table, state, event) {
+        Some(transition) => {
+            (transition.action)(context);
+            *state = transition.to;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Checks that the table is deterministic and that every state it mentions
+/// can be reached from `initial`.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub fn validate<'a, S, E, C>(
+    table: &'a [Transition<S, E, C>],
+    initial: &S,
+) -> Result<(), Invalid<'a, S, E, C>>
+where
+    S: PartialEq,
+    E: PartialEq,
+{
+    let conflict = duplicate::find(table.iter(), |a, b| a.from == b.from && a.event == b.event);
+    if let Some((first, second)) = conflict {
+        return Err(Invalid::Conflict { first, second });
+    }
+
+    let mut reachable: Vec<&S> = Vec::new();
+    let mut taken = Vec::new();
+    taken.resize(table.len(), false);
+    loop {
+        let mut progress = false;
+        for (transition, taken) in table.iter().zip(&mut taken) {
+            if !*taken && (transition.from == *initial || reachable.contains(&&transition.from)) {
+                reachable.push(&transition.to);
+                *taken = true;
+                progress = true;
+            }
+        }
+        if !progress {
+            break;
+        }
+    }
+
+    for (transition, taken) in table.iter().zip(taken) {
+        if !taken {
+            return Err(Invalid::Unreachable {
+                state: &transition.from,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Error returned by [`validate`].
+#[cfg(feature = "alloc")]
+pub enum Invalid<'a, S: 'static, E: 'static, C: 'static> {
+    /// Two transitions leave the same state on the same event.
+    Conflict {
+        first: &'a Transition<S, E, C>,
+        second: &'a Transition<S, E, C>,
+    },
+    /// A state has outgoing transitions but no path from the initial state.
+    Unreachable { state: &'a S },
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Debug, E: Debug, C> Debug for Invalid<'_, S, E, C> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Invalid::Conflict { first, second } => formatter
+                .debug_struct("Conflict")
+                .field("from", &first.from)
+                .field("event", &first.event)
+                .field("first_to", &first.to)
+                .field("second_to", &second.to)
+                .finish(),
+            Invalid::Unreachable { state } => formatter
+                .debug_struct("Unreachable")
+                .field("state", state)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Debug, E: Debug, C> Display for Invalid<'_, S, E, C> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Invalid::Conflict { first, second } => write!(
+                formatter,
+                "conflicting transitions from {:?} on {:?}: to {:?} and to {:?}",
+                first.from, first.event, first.to, second.to,
+            ),
+            Invalid::Unreachable { state } => {
+                write!(formatter, "state {:?} is unreachable", state)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Debug, E: Debug, C> std::error::Error for Invalid<'_, S, E, C> {}
@@ -0,0 +1,42 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static CALLBACKS: [extern "C" fn(i32) -> i32] = [..];
+
+#[distributed_slice(CALLBACKS)]
+extern "C" fn double(x: i32) -> i32 {
+    x * 2
+}
+
+extern "C" fn square(x: i32) -> i32 {
+    x * x
+}
+
+#[distributed_slice(CALLBACKS)]
+static SQUARE: extern "C" fn(i32) -> i32 = square;
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static HANDLERS: [unsafe extern "C" fn()] = [..];
+
+#[distributed_slice(HANDLERS)]
+unsafe extern "C" fn handler() {}
+
+type Callback = extern "C" fn(i32) -> i32;
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static ALIASED: [Callback] = [..];
+
+#[distributed_slice(ALIASED)]
+static ALIASED_DOUBLE: Callback = double;
+
+#[test]
+fn test_abi() {
+    let mut results: Vec<i32> = CALLBACKS.iter().map(|callback| callback(3)).collect();
+    results.sort_unstable();
+    assert_eq!(results, [6, 9]);
+    assert_eq!(HANDLERS.len(), 1);
+    assert_eq!(ALIASED[0](4), 8);
+}
@@ -0,0 +1,46 @@
+use linkme::asset::{self, Asset};
+use linkme::{distributed_slice, duplicate};
+
+#[distributed_slice]
+static ASSETS: [Asset] = [..];
+
+#[distributed_slice(ASSETS)]
+static LICENSE: Asset = linkme::asset!("license", "../LICENSE-MIT", unique(ASSETS));
+
+#[distributed_slice(ASSETS)]
+static README: Asset = linkme::asset!("readme", "../README.md");
+
+#[distributed_slice(ASSETS)]
+static INLINE: Asset = Asset {
+    name: "inline",
+    bytes: b"\x00\x01",
+};
+
+// Unique names are only unique within their slice.
+#[distributed_slice]
+static LEGAL: [Asset] = [..];
+
+#[distributed_slice(LEGAL)]
+static LEGAL_LICENSE: Asset = linkme::asset!("license", "../LICENSE-APACHE", unique(LEGAL));
+
+#[test]
+fn test_get() {
+    duplicate::check(&ASSETS, |asset| asset.name).unwrap();
+    assert_eq!(
+        asset::get(&ASSETS, "license"),
+        Some(&include_bytes!("../LICENSE-MIT")[..])
+    );
+    assert!(asset::get(&ASSETS, "readme")
+        .unwrap()
+        .starts_with(b"## Linkme"));
+    assert_eq!(asset::get(&ASSETS, "inline"), Some(&b"\x00\x01"[..]));
+    assert_eq!(asset::get(&ASSETS, "missing"), None);
+}
+
+#[test]
+fn test_separate_slices() {
+    assert_eq!(
+        asset::get(&LEGAL, "license"),
+        Some(&include_bytes!("../LICENSE-APACHE")[..])
+    );
+}
@@ -0,0 +1,112 @@
+use linkme::distributed_slice;
+
+pub struct Descriptor {
+    pub name: &'static str,
+}
+
+pub trait Plugin {
+    const DESCRIPTOR: Descriptor;
+
+    fn create() -> Self;
+}
+
+#[distributed_slice]
+pub static DESCRIPTORS: [Descriptor] = [..];
+
+#[distributed_slice]
+pub static CONSTRUCTORS: [fn() -> Box<dyn std::any::Any>] = [..];
+
+#[distributed_slice]
+pub static DEFAULTS: [fn() -> Gzip] = [..];
+
+pub struct Gzip;
+
+#[distributed_slice]
+impl Plugin for Gzip {
+    #[distributed_slice(DESCRIPTORS)]
+    const DESCRIPTOR: Descriptor = Descriptor { name: "gzip" };
+
+    #[distributed_slice(DEFAULTS)]
+    fn create() -> Self {
+        Gzip
+    }
+}
+
+pub struct Zstd;
+
+#[distributed_slice]
+impl Plugin for Zstd {
+    #[distributed_slice(DESCRIPTORS)]
+    const DESCRIPTOR: Descriptor = Descriptor { name: "zstd" };
+
+    fn create() -> Self {
+        Zstd
+    }
+}
+
+#[distributed_slice]
+impl Zstd {
+    #[distributed_slice(CONSTRUCTORS)]
+    fn boxed() -> Box<dyn std::any::Any> {
+        Box::new(Zstd::create())
+    }
+}
+
+#[distributed_slice]
+pub static MAX_LEVELS: [fn() -> u8] = [..];
+
+#[distributed_slice]
+pub static LEVELS: [fn() -> Box<dyn Iterator<Item = u8>>] = [..];
+
+// Associated type bindings in signatures and where clauses, ahead of the
+// registered items.
+#[distributed_slice]
+impl Gzip {
+    fn levels() -> impl Iterator<Item = u8> {
+        1..=9
+    }
+
+    fn max<I>(levels: I) -> u8
+    where
+        I: Iterator<Item = u8>,
+    {
+        levels.max().unwrap_or(0)
+    }
+
+    #[distributed_slice(MAX_LEVELS)]
+    fn max_level() -> u8 {
+        Gzip::max(Gzip::levels())
+    }
+
+    #[distributed_slice(LEVELS)]
+    const BOXED_LEVELS: fn() -> Box<dyn Iterator<Item = u8>> = || {
+        let levels = Gzip::levels();
+        Box::new(levels)
+    };
+}
+
+#[test]
+fn test_associated_const() {
+    let mut names: Vec<_> = DESCRIPTORS
+        .iter()
+        .map(|descriptor| descriptor.name)
+        .collect();
+    names.sort_unstable();
+    assert_eq!(names, ["gzip", "zstd"]);
+    assert_eq!(Gzip::DESCRIPTOR.name, "gzip");
+}
+
+#[test]
+fn test_associated_fn() {
+    assert_eq!(DEFAULTS.len(), 1);
+    let _: Gzip = DEFAULTS[0]();
+    assert!(CONSTRUCTORS[0]().is::<Zstd>());
+}
+
+#[test]
+fn test_associated_type_binding() {
+    assert_eq!(MAX_LEVELS.len(), 1);
+    assert_eq!(MAX_LEVELS[0](), 9);
+    assert_eq!(LEVELS.len(), 1);
+    assert_eq!(LEVELS[0]().count(), 9);
+}
@@ -0,0 +1,70 @@
+use linkme::blob::{self, Blob};
+use linkme::{distributed_slice, duplicate};
+
+#[distributed_slice]
+static POOL: [Blob] = [..];
+
+#[distributed_slice(POOL)]
+static HELLO: Blob = Blob {
+    name: "b-hello",
+    bytes: b"hello",
+};
+
+#[distributed_slice(POOL)]
+static EMPTY: Blob = Blob {
+    name: "c-empty",
+    bytes: b"",
+};
+
+#[distributed_slice(POOL)]
+static PREFIX: Blob = Blob {
+    name: "a-prefix",
+    bytes: b">> ",
+};
+
+#[distributed_slice(POOL)]
+static WORLD: Blob = Blob {
+    name: "d-world",
+    bytes: b" world",
+};
+
+#[test]
+fn test_concatenation() {
+    duplicate::check(&POOL, |blob| blob.name).unwrap();
+    assert_eq!(blob::len(&POOL), 14);
+
+    let mut buf = [0; 20];
+    let n = blob::read(&POOL, 0, &mut buf);
+    assert_eq!(&buf[..n], b">> hello world");
+
+    let offsets: Vec<_> = blob::parts(&POOL)
+        .map(|(offset, blob)| (blob.name, offset))
+        .collect();
+    assert_eq!(
+        offsets,
+        [
+            ("a-prefix", 0),
+            ("b-hello", 3),
+            ("c-empty", 8),
+            ("d-world", 8)
+        ],
+    );
+}
+
+#[test]
+fn test_locate() {
+    assert_eq!(blob::locate(&POOL, "b-hello"), Some(3..8));
+    assert_eq!(blob::locate(&POOL, "c-empty"), Some(8..8));
+    assert_eq!(blob::locate(&POOL, "missing"), None);
+}
+
+#[test]
+fn test_read_window() {
+    let mut buf = [0; 4];
+    assert_eq!(blob::read(&POOL, 6, &mut buf), 4);
+    assert_eq!(&buf, b"lo w");
+    assert_eq!(blob::read(&POOL, 12, &mut buf), 2);
+    assert_eq!(&buf[..2], b"ld");
+    assert_eq!(blob::read(&POOL, 14, &mut buf), 0);
+    assert_eq!(blob::read(&POOL, 100, &mut buf), 0);
+}
@@ -0,0 +1,59 @@
+use linkme::boot::{self, Hook, Stage, POST_RAM_INIT, PRE_JUMP, PRE_RAM_INIT};
+use linkme::distributed_slice;
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log(name: &'static str) {
+    LOG.with(|log| log.borrow_mut().push(name));
+}
+
+#[distributed_slice(PRE_JUMP)]
+static HANDOFF: Hook = Hook {
+    name: "handoff",
+    order: 0,
+    run: || log("handoff"),
+};
+
+#[distributed_slice(POST_RAM_INIT)]
+static RELOCATE: Hook = Hook {
+    name: "relocate",
+    order: 10,
+    run: || log("relocate"),
+};
+
+#[distributed_slice(POST_RAM_INIT)]
+static ZERO_BSS: Hook = Hook {
+    name: "zero_bss",
+    order: -5,
+    run: || log("zero_bss"),
+};
+
+#[distributed_slice(PRE_RAM_INIT)]
+static WATCHDOG: Hook = Hook {
+    name: "watchdog",
+    order: 0,
+    run: || log("watchdog"),
+};
+
+#[distributed_slice(PRE_RAM_INIT)]
+static CLOCKS: Hook = Hook {
+    name: "clocks",
+    order: 0,
+    run: || log("clocks"),
+};
+
+#[test]
+fn test_stages() {
+    boot::run(Stage::PreRamInit);
+    boot::run(Stage::PostRamInit);
+    boot::run(Stage::PreJump);
+    let log = LOG.with(|log| log.borrow().clone());
+    assert_eq!(
+        log,
+        ["clocks", "watchdog", "zero_bss", "relocate", "handoff"],
+    );
+    assert_eq!(Stage::PostRamInit.hooks().len(), 2);
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use linkme::command::{Command, CommandRegistry, DispatchError};
+use linkme::distributed_slice;
+
+#[distributed_slice]
+static COMMANDS: [Command<String>] = [..];
+
+static REGISTRY: CommandRegistry<String> = CommandRegistry::new(&COMMANDS);
+
+#[distributed_slice(COMMANDS)]
+static ECHO: Command<String> = Command {
+    name: "echo",
+    handler: |args| args.join(" "),
+};
+
+#[distributed_slice(COMMANDS)]
+static COUNT: Command<String> = Command {
+    name: "count",
+    handler: |args| args.len().to_string(),
+};
+
+#[test]
+fn test_dispatch() {
+    REGISTRY.validate().unwrap();
+    assert_eq!(REGISTRY.dispatch("echo", &["a", "b"]).unwrap(), "a b");
+    assert_eq!(REGISTRY.dispatch("count", &["a", "b"]).unwrap(), "2");
+
+    let err = REGISTRY.dispatch("ehco", &[]).unwrap_err();
+    assert_eq!(err, DispatchError::Unknown { name: "ehco" });
+    assert_eq!(err.to_string(), "unknown command \"ehco\"");
+
+    assert_eq!(REGISTRY.names().unwrap(), ["count", "echo"]);
+    assert!(REGISTRY.get("count").unwrap().is_some());
+}
+
+#[distributed_slice]
+static DUPLICATED: [Command] = [..];
+
+static DUPLICATED_REGISTRY: CommandRegistry = CommandRegistry::new(&DUPLICATED);
+
+#[distributed_slice(DUPLICATED)]
+static RUN_1: Command = Command {
+    name: "run",
+    handler: |_args| {},
+};
+
+#[distributed_slice(DUPLICATED)]
+static RUN_2: Command = Command {
+    name: "run",
+    handler: |_args| {},
+};
+
+#[distributed_slice(DUPLICATED)]
+static STOP: Command = Command {
+    name: "stop",
+    handler: |_args| {},
+};
+
+#[test]
+fn test_duplicate() {
+    let err = DUPLICATED_REGISTRY.dispatch("stop", &[]).unwrap_err();
+    assert_eq!(err, DispatchError::Duplicate { name: "run" });
+    assert_eq!(err.to_string(), "command \"run\" is registered twice");
+}
@@ -1,4 +1,7 @@
-#[rustversion::attr(not(nightly), ignore)]
+// The expected output is that of nightly-2026-05-20, whose rustc reports the
+// date 2026-05-19, and is only checked on that toolchain. CI runs it in the
+// `ui` job; regenerate it there with TRYBUILD=overwrite after bumping both.
+#[rustversion::attr(not(nightly(2026-05-19)), ignore)]
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
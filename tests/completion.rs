@@ -0,0 +1,43 @@
+use linkme::completion::{self, Completion, Shell};
+use linkme::distributed_slice;
+use std::fmt::{self, Write};
+
+#[distributed_slice]
+static COMPLETIONS: [Completion] = [..];
+
+#[distributed_slice(COMPLETIONS)]
+static REMOTE_ADD: Completion = Completion {
+    path: &["remote", "add"],
+    generate: remote_add,
+};
+
+#[distributed_slice(COMPLETIONS)]
+static ROOT: Completion = Completion {
+    path: &[],
+    generate: root,
+};
+
+#[distributed_slice(COMPLETIONS)]
+static REMOTE: Completion = Completion {
+    path: &["remote"],
+    generate: remote,
+};
+
+fn root(shell: Shell, out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "{:?} app", shell)
+}
+
+fn remote(shell: Shell, out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "{:?} app remote", shell)
+}
+
+fn remote_add(shell: Shell, out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "{:?} app remote add", shell)
+}
+
+#[test]
+fn test_merge() {
+    let mut script = String::new();
+    completion::merge(&COMPLETIONS, Shell::Zsh, &mut script).unwrap();
+    assert_eq!(script, "Zsh app\nZsh app remote\nZsh app remote add\n");
+}
@@ -0,0 +1,89 @@
+use linkme::dense::{self, Slot};
+use linkme::distributed_slice;
+use std::{mem, ptr};
+
+type SyscallFn = fn(usize) -> isize;
+
+#[distributed_slice]
+static SYSCALLS: [Slot<SyscallFn>] = [..];
+
+#[distributed_slice(SYSCALLS, index = 3)]
+fn sys_read(fd: usize) -> isize {
+    fd as isize * 10
+}
+
+#[distributed_slice(SYSCALLS, index = 0)]
+fn sys_exit(code: usize) -> isize {
+    -(code as isize)
+}
+
+#[distributed_slice(SYSCALLS, index = 5)]
+static SYS_YIELD: SyscallFn = |_| 0;
+
+fn sys_enosys(_arg: usize) -> isize {
+    -38
+}
+
+#[test]
+fn test_populate() {
+    assert_eq!(dense::len(&SYSCALLS), 6);
+    assert_eq!(SYS_YIELD.index, 5);
+
+    let mut table: [SyscallFn; 8] = [sys_enosys; 8];
+    dense::populate(&SYSCALLS, &mut table).unwrap();
+    assert_eq!(table[0](2), -2);
+    assert_eq!(table[1](2), -38);
+    assert_eq!(table[3](2), 20);
+    assert_eq!(table[5](2), 0);
+    assert_eq!(table[7](2), -38);
+
+    let mut small: [SyscallFn; 4] = [sys_enosys; 4];
+    let err = dense::populate(&SYSCALLS, &mut small).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "index 5 is out of range for a table of 4 entries",
+    );
+
+    assert_eq!(dense::get(&SYSCALLS, 3).unwrap()(1), 10);
+    assert!(dense::get(&SYSCALLS, 4).is_none());
+
+    // The function item itself is still callable directly.
+    assert_eq!(sys_read(4), 40);
+}
+
+#[test]
+fn test_layout() {
+    // One slot per registration, contiguous, in whatever order the linker
+    // placed them. The slice is not itself indexed by slot number.
+    assert_eq!(SYSCALLS.len(), 3);
+    let mut indices: Vec<usize> = SYSCALLS.iter().map(|slot| slot.index).collect();
+    indices.sort_unstable();
+    assert_eq!(indices, [0, 3, 5]);
+    let first = SYSCALLS.as_ptr() as usize;
+    let last = &SYSCALLS[2] as *const Slot<SyscallFn> as usize;
+    assert_eq!(last - first, 2 * mem::size_of::<Slot<SyscallFn>>());
+
+    // The registered static has become a slot. On wasm the slice holds
+    // copies of the registered elements rather than the statics themselves.
+    let slot: &Slot<SyscallFn> = &SYS_YIELD;
+    if cfg!(not(target_family = "wasm")) {
+        assert!(SYSCALLS.iter().any(|element| ptr::eq(element, slot)));
+    }
+}
+
+#[test]
+fn test_duplicate() {
+    let slots = [
+        Slot {
+            index: 2,
+            value: 'a',
+        },
+        Slot {
+            index: 2,
+            value: 'b',
+        },
+    ];
+    let mut table = ['-'; 4];
+    let err = dense::populate(&slots, &mut table).unwrap_err();
+    assert_eq!(err.to_string(), "index 2 is claimed more than once");
+}
@@ -0,0 +1,66 @@
+use linkme::devicetree::{self, DriverMatch};
+use linkme::{distributed_slice, duplicate};
+use std::cell::RefCell;
+
+struct Node {
+    name: &'static str,
+    probed_by: RefCell<Vec<&'static str>>,
+}
+
+impl Node {
+    fn new(name: &'static str) -> Self {
+        Node {
+            name,
+            probed_by: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+#[distributed_slice]
+static DRIVERS: [DriverMatch<Node>] = [..];
+
+#[distributed_slice(DRIVERS)]
+static PL011: DriverMatch<Node> = DriverMatch {
+    compatible: "arm,pl011",
+    probe: |node| node.probed_by.borrow_mut().push("pl011"),
+};
+
+#[distributed_slice(DRIVERS)]
+static UART: DriverMatch<Node> = DriverMatch {
+    compatible: "vendor,uart",
+    probe: |node| node.probed_by.borrow_mut().push("uart"),
+};
+
+#[distributed_slice(DRIVERS)]
+static UART_V3: DriverMatch<Node> = DriverMatch {
+    compatible: "vendor,uart-v3",
+    probe: |node| node.probed_by.borrow_mut().push("uart-v3"),
+};
+
+#[test]
+fn test_exact_before_prefix() {
+    duplicate::check(&DRIVERS, |entry| entry.compatible).unwrap();
+
+    let node = Node::new("serial@7e201000");
+    let compatible = ["brcm,bcm2835-pl011", "arm,pl011", "arm,primecell"];
+    assert!(devicetree::probe(&DRIVERS, &compatible, &node));
+    assert_eq!(*node.probed_by.borrow(), ["pl011"]);
+    assert_eq!(node.name, "serial@7e201000");
+}
+
+#[test]
+fn test_prefix() {
+    let entry = devicetree::find_prefix(&DRIVERS, "vendor,uart-v2").unwrap();
+    assert_eq!(entry.compatible, "vendor,uart");
+    let entry = devicetree::find_prefix(&DRIVERS, "vendor,uart-v3.1").unwrap();
+    assert_eq!(entry.compatible, "vendor,uart-v3");
+    assert!(devicetree::find(&DRIVERS, "vendor,uart-v2").is_none());
+
+    let node = Node::new("serial@1000");
+    assert!(devicetree::probe(&DRIVERS, &["vendor,uart-v2"], &node));
+    assert_eq!(*node.probed_by.borrow(), ["uart"]);
+
+    let node = Node::new("gpio@2000");
+    assert!(!devicetree::probe(&DRIVERS, &["vendor,gpio"], &node));
+    assert!(node.probed_by.borrow().is_empty());
+}
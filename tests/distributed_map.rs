@@ -0,0 +1,62 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::incompatible_msrv)]
+
+use linkme::distributed_map;
+
+pub struct Codec {
+    pub id: u32,
+}
+
+#[distributed_map]
+pub static CODECS: [(&'static str, Codec)] = [..];
+
+#[distributed_map(CODECS)]
+static OPUS: (&'static str, Codec) = ("opus", Codec { id: 2 });
+
+#[distributed_map(CODECS)]
+static FLAC: (&'static str, Codec) = ("flac", Codec { id: 1 });
+
+#[distributed_map(CODECS)]
+static VORBIS: (&'static str, Codec) = ("vorbis", Codec { id: 3 });
+
+#[distributed_map]
+pub static LEVELS: [(u8, &'static str)] = [..];
+
+#[distributed_map(LEVELS)]
+static WARN: (u8, &'static str) = (2, "warn");
+
+#[distributed_map(LEVELS)]
+static ALSO_WARN: (u8, &'static str) = (2, "warning");
+
+#[distributed_map(LEVELS)]
+static ERROR: (u8, &'static str) = (1, "error");
+
+#[test]
+fn test_get() {
+    assert_eq!(CODECS.len(), 3);
+    assert_eq!(CODECS.get("flac").unwrap().id, 1);
+    assert_eq!(CODECS.get("vorbis").unwrap().id, 3);
+    assert!(CODECS.get("mp3").is_none());
+    assert!(CODECS.contains_key("opus"));
+    assert!(CODECS.validate().is_ok());
+}
+
+#[test]
+fn test_iter() {
+    let keys: Vec<&str> = CODECS.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, ["flac", "opus", "vorbis"]);
+    assert_eq!(CODECS.iter().len(), 3);
+}
+
+#[test]
+fn test_duplicate() {
+    let mut names: Vec<&str> = LEVELS.get_all(&2).copied().collect();
+    names.sort_unstable();
+    assert_eq!(names, ["warn", "warning"]);
+    assert_eq!(LEVELS.get(&1), Some(&"error"));
+    assert_eq!(LEVELS.get_all(&3).len(), 0);
+
+    let err = LEVELS.validate().unwrap_err();
+    assert_eq!(*err.key, 2);
+    assert_eq!(err.to_string(), "key 2 is registered twice");
+}
@@ -44,3 +44,45 @@ fn test_non_copy() {
 
     assert!(!NONCOPY.is_empty());
 }
+
+#[repr(align(64))]
+struct Line(u8);
+
+#[distributed_slice]
+static LINES: [Line] = [..];
+
+#[distributed_slice(LINES)]
+static A: Line = Line(1);
+
+#[distributed_slice(LINES)]
+static B: Line = Line(2);
+
+#[distributed_slice(LINES)]
+static C: Line = Line(3);
+
+#[test]
+fn test_over_aligned() {
+    assert_eq!(LINES.len(), 3);
+    let mut sum = 0;
+    for line in LINES {
+        assert_eq!(line as *const Line as usize % 64, 0);
+        sum += line.0;
+    }
+    assert_eq!(sum, 1 + 2 + 3);
+}
+
+#[distributed_slice]
+static IDENTICAL: [[u64; 4]] = [..];
+
+#[distributed_slice(IDENTICAL)]
+static FIRST: [u64; 4] = [1, 2, 3, 4];
+
+#[distributed_slice(IDENTICAL)]
+static SECOND: [u64; 4] = [1, 2, 3, 4];
+
+#[test]
+fn test_identical_elements() {
+    // Identical code folding must not merge these into one element.
+    assert_eq!(IDENTICAL.len(), 2);
+    assert_ne!(&FIRST as *const _, &SECOND as *const _);
+}
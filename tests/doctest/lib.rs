@@ -0,0 +1,61 @@
+//! Doctests of a crate that both declares a distributed slice and registers
+//! elements into it. Each doctest is linked as its own binary against the
+//! crate built normally.
+//!
+//! Elements registered by the crate itself are visible, including ones in
+//! private modules:
+//!
+//! ```
+//! let mut names: Vec<_> = linkme_doctest::PLUGINS.iter().map(|p| p.name).collect();
+//! names.sort_unstable();
+//! assert_eq!(names, ["private", "public"]);
+//! ```
+//!
+//! Elements registered by the doctest are added to those:
+//!
+//! ```
+//! use linkme::distributed_slice;
+//! use linkme_doctest::{Plugin, PLUGINS};
+//!
+//! #[distributed_slice(PLUGINS)]
+//! static LOCAL: Plugin = Plugin { name: "local" };
+//!
+//! assert_eq!(PLUGINS.len(), 3);
+//! ```
+//!
+//! Elements registered under `#[cfg(test)]` belong only to the crate's
+//! unit-test binary and are absent here:
+//!
+//! ```
+//! assert!(linkme_doctest::PLUGINS.iter().all(|p| p.name != "test-only"));
+//! ```
+
+use linkme::distributed_slice;
+
+pub struct Plugin {
+    pub name: &'static str,
+}
+
+#[distributed_slice]
+pub static PLUGINS: [Plugin] = [..];
+
+#[distributed_slice(PLUGINS)]
+static PUBLIC: Plugin = Plugin { name: "public" };
+
+mod private {
+    use super::{Plugin, PLUGINS};
+    use linkme::distributed_slice;
+
+    #[distributed_slice(PLUGINS)]
+    static PRIVATE: Plugin = Plugin { name: "private" };
+}
+
+#[cfg(test)]
+#[distributed_slice(PLUGINS)]
+static TEST_ONLY: Plugin = Plugin { name: "test-only" };
+
+#[test]
+fn test_unit_test_binary() {
+    assert_eq!(PLUGINS.len(), 3);
+    assert!(PLUGINS.iter().any(|p| p.name == TEST_ONLY.name));
+}
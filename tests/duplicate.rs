@@ -0,0 +1,55 @@
+use linkme::distributed_slice;
+use linkme::duplicate;
+
+struct Codec {
+    name: &'static str,
+    id: u16,
+}
+
+#[distributed_slice]
+static CODECS: [Codec] = [..];
+
+#[distributed_slice(CODECS)]
+static FLAC: Codec = Codec {
+    name: "flac",
+    id: 1,
+};
+
+#[distributed_slice(CODECS)]
+static OPUS: Codec = Codec {
+    name: "opus",
+    id: 2,
+};
+
+#[test]
+fn test_unique() {
+    duplicate::check(&CODECS, |codec| codec.name).unwrap();
+    duplicate::check(&CODECS, |codec| codec.id).unwrap();
+    duplicate::check(&CODECS, |codec| (codec.name, codec.id)).unwrap();
+}
+
+#[test]
+fn test_duplicate() {
+    static DUPLICATES: [Codec; 3] = [
+        Codec {
+            name: "flac",
+            id: 1,
+        },
+        Codec {
+            name: "opus",
+            id: 2,
+        },
+        Codec {
+            name: "flac",
+            id: 3,
+        },
+    ];
+    let err = duplicate::check(&DUPLICATES, |codec| codec.name).unwrap_err();
+    assert_eq!(err.key, "flac");
+    assert_eq!(err.first.id, 1);
+    assert_eq!(err.second.id, 3);
+    assert_eq!(err.to_string(), "\"flac\" is registered twice");
+    assert_eq!(format!("{:?}", err), "Duplicate { key: \"flac\" }");
+
+    duplicate::check(&DUPLICATES, |codec| codec.id).unwrap();
+}
@@ -0,0 +1,113 @@
+#![cfg(all(feature = "alloc", not(target_family = "wasm")))]
+
+use linkme::distributed_slice;
+use linkme::dylib::{DynamicSlice, LoadError};
+use linkme::registry::Registry;
+use linkme_dylib_api::{Command, LINKED};
+use std::env;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::path::PathBuf;
+
+pub static COMMANDS: DynamicSlice<Command> = DynamicSlice::new(&LINKED);
+
+#[distributed_slice(LINKED)]
+static HELP: Command = Command { name: "help" };
+
+#[distributed_slice]
+#[linkme(dylib = "linkme_test_dylib_other")]
+pub static OTHER: [u64] = [..];
+
+extern "C" {
+    #[link_name = "linkme_dylib_commands"]
+    static HOST_SYMBOL: u8;
+    #[link_name = "linkme_test_dylib_other"]
+    static OTHER_SYMBOL: u8;
+}
+
+#[cfg(unix)]
+extern "C" {
+    fn dlopen(filename: *const c_char, flag: i32) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+#[cfg(windows)]
+extern "system" {
+    fn LoadLibraryA(filename: *const c_char) -> *mut c_void;
+    fn GetProcAddress(module: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+// Loads the cdylib of tests/dylib/plugin, which Cargo builds next to this
+// test as a dev-dependency, and looks up the descriptor of its copy of the
+// slice. The library is never unloaded.
+fn load_plugin() -> *const () {
+    let mut path = PathBuf::from(env::current_exe().unwrap().parent().unwrap());
+    path.push(format!(
+        "{}linkme_dylib_plugin{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX,
+    ));
+    let filename = CString::new(path.to_str().unwrap()).unwrap();
+    let symbol = CString::new("linkme_dylib_commands").unwrap();
+
+    #[cfg(unix)]
+    let address = unsafe {
+        const RTLD_NOW: i32 = 2;
+        let library = dlopen(filename.as_ptr(), RTLD_NOW);
+        assert!(!library.is_null(), "failed to load {}", path.display());
+        dlsym(library, symbol.as_ptr())
+    };
+
+    #[cfg(windows)]
+    let address = unsafe {
+        let library = LoadLibraryA(filename.as_ptr());
+        assert!(!library.is_null(), "failed to load {}", path.display());
+        GetProcAddress(library, symbol.as_ptr())
+    };
+
+    assert!(!address.is_null());
+    address as *const ()
+}
+
+static FUTURE_VERSION: u32 = 99;
+
+fn names() -> Vec<&'static str> {
+    COMMANDS.iter().map(|command| command.name).collect()
+}
+
+#[test]
+fn test_load() {
+    assert_eq!(names(), ["help"]);
+
+    let plugin_symbol = load_plugin();
+    unsafe { COMMANDS.load(plugin_symbol) }.unwrap();
+    let mut plugin: Vec<_> = names().split_off(1);
+    plugin.sort_unstable();
+    assert_eq!(names()[0], "help");
+    assert_eq!(plugin, ["deploy", "rollback"]);
+    assert_eq!(COMMANDS.len(), 3);
+    assert_eq!(COMMANDS.get(0).unwrap().name, "help");
+    assert!(COMMANDS.get(3).is_none());
+    assert_eq!(COMMANDS.libraries().count(), 1);
+
+    // The plugin's elements live only in its own copy of the slice.
+    let host: Vec<_> = LINKED.iter().map(|command| command.name).collect();
+    assert_eq!(host, ["help"]);
+
+    // Neither the host's own slice nor a library loaded before is added
+    // again.
+    unsafe { COMMANDS.load(&HOST_SYMBOL as *const u8 as *const ()) }.unwrap();
+    unsafe { COMMANDS.load(plugin_symbol) }.unwrap();
+    assert_eq!(COMMANDS.len(), 3);
+
+    let err = unsafe { COMMANDS.load(&OTHER_SYMBOL as *const u8 as *const ()) }.unwrap_err();
+    assert!(matches!(err, LoadError::ElementType));
+
+    let err = unsafe { COMMANDS.load(&FUTURE_VERSION as *const u32 as *const ()) }.unwrap_err();
+    assert!(matches!(err, LoadError::Version { found: 99 }));
+    assert_eq!(
+        err.to_string(),
+        "library exports a version 99 slice descriptor, expected version 1",
+    );
+    assert_eq!(COMMANDS.len(), 3);
+}
@@ -0,0 +1,11 @@
+//! Crate shared by the host in tests/dylib.rs and the plugin it loads.
+
+use linkme::distributed_slice;
+
+pub struct Command {
+    pub name: &'static str,
+}
+
+#[distributed_slice]
+#[linkme(dylib = "linkme_dylib_commands")]
+pub static LINKED: [Command] = [..];
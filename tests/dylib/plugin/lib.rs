@@ -0,0 +1,10 @@
+//! Plugin loaded at runtime by tests/dylib.rs.
+
+use linkme::distributed_slice;
+use linkme_dylib_api::{Command, LINKED};
+
+#[distributed_slice(LINKED)]
+static DEPLOY: Command = Command { name: "deploy" };
+
+#[distributed_slice(LINKED)]
+static ROLLBACK: Command = Command { name: "rollback" };
@@ -0,0 +1,108 @@
+#![allow(clippy::incompatible_msrv)]
+
+use linkme::embassy::{self, Task};
+use linkme::{distributed_slice, duplicate};
+use std::cell::RefCell;
+
+thread_local! {
+    static SPAWNED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+#[derive(Copy, Clone)]
+struct Spawner {
+    slots: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Busy;
+
+impl Spawner {
+    fn spawn(self, name: &'static str) -> Result<(), Busy> {
+        SPAWNED.with(|spawned| {
+            let mut spawned = spawned.borrow_mut();
+            if spawned.len() == self.slots {
+                return Err(Busy);
+            }
+            spawned.push(name);
+            Ok(())
+        })
+    }
+}
+
+fn take_spawned() -> Vec<&'static str> {
+    SPAWNED.with(|spawned| spawned.borrow_mut().split_off(0))
+}
+
+#[distributed_slice]
+static TASKS: [Task<Spawner, Busy>] = [..];
+
+#[distributed_slice(TASKS)]
+static USB: Task<Spawner, Busy> = Task {
+    name: "usb",
+    pool_size: 1,
+    spawn: |spawner| spawner.spawn("usb"),
+};
+
+#[distributed_slice(TASKS)]
+static BLINK: Task<Spawner, Busy> = Task {
+    name: "blink",
+    pool_size: 2,
+    spawn: |spawner| spawner.spawn("blink"),
+};
+
+#[test]
+fn test_spawn_all() {
+    duplicate::check(&TASKS, |task| task.name).unwrap();
+    assert_eq!(embassy::task_count(&TASKS), 3);
+
+    embassy::spawn_all(&TASKS, Spawner { slots: 4 }).unwrap();
+    assert_eq!(take_spawned(), ["blink", "usb"]);
+
+    let err = embassy::spawn_all(&TASKS, Spawner { slots: 1 }).unwrap_err();
+    assert_eq!(err.name, "usb");
+    assert_eq!(err.error, Busy);
+    assert_eq!(take_spawned(), ["blink"]);
+}
+
+#[distributed_slice]
+static SIZED_TASKS: [Task<Spawner, Busy>] = [..];
+
+const TASK_CAPACITY: usize = 4;
+
+#[distributed_slice(SIZED_TASKS)]
+static RADIO: Task<Spawner, Busy> =
+    linkme::embassy_task!(SIZED_TASKS, TASK_CAPACITY, [0, 1], "radio", |spawner| {
+        spawner.spawn("radio")
+    });
+
+#[distributed_slice(SIZED_TASKS)]
+static SENSOR: Task<Spawner, Busy> =
+    linkme::embassy_task!(SIZED_TASKS, TASK_CAPACITY, [3], "sensor", |spawner| {
+        spawner.spawn("sensor")
+    });
+
+#[distributed_slice]
+static OTHER_TASKS: [Task<Spawner, Busy>] = [..];
+
+// The same slot as RADIO, in a separate slice.
+#[distributed_slice(OTHER_TASKS)]
+static LOGGER: Task<Spawner, Busy> =
+    linkme::embassy_task!(OTHER_TASKS, 1, [0], "logger", |spawner| spawner
+        .spawn("logger"));
+
+#[test]
+fn test_claimed_slots() {
+    assert_eq!(RADIO.pool_size, 2);
+    assert_eq!(SENSOR.pool_size, 1);
+    assert_eq!(LOGGER.pool_size, 1);
+    assert_eq!(embassy::task_count(&SIZED_TASKS), 3);
+
+    embassy::spawn_all(
+        &SIZED_TASKS,
+        Spawner {
+            slots: TASK_CAPACITY,
+        },
+    )
+    .unwrap();
+    assert_eq!(take_spawned(), ["radio", "sensor"]);
+}
@@ -0,0 +1,34 @@
+use linkme::error_code::{self, ErrorCode};
+use linkme::{distributed_slice, duplicate};
+
+#[distributed_slice]
+static ERROR_CODES: [ErrorCode] = [..];
+
+#[distributed_slice(ERROR_CODES)]
+static E_QUOTA: ErrorCode = ErrorCode {
+    code: "STORAGE-0002",
+    message: "storage quota exceeded",
+    docs_url: Some("https://example.com/errors/storage-0002"),
+};
+
+#[distributed_slice(ERROR_CODES)]
+static E_MISSING: ErrorCode = ErrorCode {
+    code: "STORAGE-0001",
+    message: "no such \"bucket\"",
+    docs_url: None,
+};
+
+#[test]
+fn test_unique() {
+    duplicate::check(&ERROR_CODES, |error| error.code).unwrap();
+}
+
+#[test]
+fn test_catalog() {
+    let catalog = error_code::catalog(&ERROR_CODES).to_string();
+    let expected = concat!(
+        r#"[{"code":"STORAGE-0001","message":"no such \"bucket\"","docs_url":null},"#,
+        r#"{"code":"STORAGE-0002","message":"storage quota exceeded","docs_url":"https://example.com/errors/storage-0002"}]"#,
+    );
+    assert_eq!(catalog, expected);
+}
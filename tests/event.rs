@@ -0,0 +1,77 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static RECEIVED: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+fn received() -> Vec<String> {
+    RECEIVED.with(|received| received.borrow_mut().drain(..).collect())
+}
+
+#[linkme::event]
+pub struct UserCreated {
+    pub name: &'static str,
+}
+
+#[linkme::event]
+pub struct UserDeleted;
+
+#[linkme::event]
+pub struct Unobserved;
+
+mod billing {
+    #[linkme::event]
+    pub enum Invoice {
+        Paid(u32),
+    }
+}
+
+#[linkme::subscribe(UserCreated)]
+fn welcome(event: &UserCreated) {
+    RECEIVED.with(|received| {
+        received
+            .borrow_mut()
+            .push(format!("welcome {}", event.name))
+    });
+}
+
+#[linkme::subscribe(UserCreated)]
+pub fn count(_: &UserCreated) {
+    RECEIVED.with(|received| received.borrow_mut().push("count".to_owned()));
+}
+
+#[linkme::subscribe(UserDeleted)]
+fn farewell(_event: &UserDeleted) {
+    RECEIVED.with(|received| received.borrow_mut().push("farewell".to_owned()));
+}
+
+mod handlers {
+    use super::billing::Invoice;
+    use super::RECEIVED;
+
+    #[linkme::subscribe(Invoice)]
+    fn receipt(event: &Invoice) {
+        let Invoice::Paid(amount) = event;
+        RECEIVED.with(|received| received.borrow_mut().push(format!("paid {}", amount)));
+    }
+}
+
+#[test]
+fn test_publish() {
+    linkme::publish(&UserCreated { name: "dtolnay" });
+    let mut created = received();
+    created.sort();
+    assert_eq!(created, ["count", "welcome dtolnay"]);
+
+    linkme::publish(&UserDeleted);
+    assert_eq!(received(), ["farewell"]);
+
+    linkme::publish(&Unobserved);
+    assert!(received().is_empty());
+}
+
+#[test]
+fn test_imported_event() {
+    linkme::publish(&billing::Invoice::Paid(30));
+    assert_eq!(received(), ["paid 30"]);
+}
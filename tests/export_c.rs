@@ -0,0 +1,42 @@
+#![cfg(not(target_family = "wasm"))]
+
+use linkme::distributed_slice;
+use std::slice;
+
+#[repr(C)]
+pub struct Entry {
+    pub id: u32,
+    pub flags: u32,
+}
+
+#[distributed_slice]
+#[linkme(export_c = "linkme_test_registry")]
+pub static REGISTRY: [Entry] = [..];
+
+#[distributed_slice(REGISTRY)]
+static FIRST: Entry = Entry { id: 1, flags: 0 };
+
+#[distributed_slice(REGISTRY)]
+static SECOND: Entry = Entry { id: 2, flags: 0 };
+
+// Declared the way C code would see the exported symbols.
+extern "C" {
+    static linkme_test_registry_data: *const Entry;
+    static linkme_test_registry_end: *const Entry;
+}
+
+#[test]
+fn test_export_c() {
+    let entries = unsafe {
+        let data = linkme_test_registry_data;
+        let end = linkme_test_registry_end;
+        let len = (end as usize - data as usize) / std::mem::size_of::<Entry>();
+        slice::from_raw_parts(data, len)
+    };
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries.as_ptr(), REGISTRY.as_ptr());
+
+    let mut ids: Vec<u32> = entries.iter().map(|entry| entry.id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, [1, 2]);
+}
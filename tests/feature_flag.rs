@@ -0,0 +1,31 @@
+use linkme::feature_flag::{self, FeatureFlag};
+use linkme::{distributed_slice, duplicate};
+
+#[distributed_slice]
+static FEATURE_FLAGS: [FeatureFlag] = [..];
+
+#[distributed_slice(FEATURE_FLAGS)]
+static PARALLEL_UPLOAD: FeatureFlag = FeatureFlag {
+    name: "parallel-upload",
+    default: false,
+    description: "Upload chunks over multiple connections",
+};
+
+#[distributed_slice(FEATURE_FLAGS)]
+static CACHE: FeatureFlag = FeatureFlag {
+    name: "cache",
+    default: true,
+    description: "Cache responses on disk",
+};
+
+#[test]
+fn test_unique() {
+    duplicate::check(&FEATURE_FLAGS, |flag| flag.name).unwrap();
+}
+
+#[test]
+fn test_help() {
+    let help = feature_flag::help(&FEATURE_FLAGS).to_string();
+    let expected = "  cache            Cache responses on disk [default: on]\n  parallel-upload  Upload chunks over multiple connections [default: off]\n";
+    assert_eq!(help, expected);
+}
@@ -0,0 +1,58 @@
+use linkme::fixture::{self, Fixture, FIXTURES};
+use linkme::{distributed_slice, duplicate};
+use std::cell::Cell;
+
+thread_local! {
+    static SEEDED: Cell<u32> = Cell::new(0);
+}
+
+// Integration tests are compiled with cfg(test), so this is registered.
+#[cfg(test)]
+#[distributed_slice(FIXTURES)]
+static SEED: Fixture = linkme::fixture!("seed", || SEEDED.with(|n| n.set(n.get() + 1)));
+
+// And this one is not.
+#[cfg(not(test))]
+#[distributed_slice(FIXTURES)]
+static NEVER: Fixture = linkme::fixture!("never", || {});
+
+mod nested {
+    use linkme::distributed_slice;
+    use linkme::fixture::{Fixture, FIXTURES};
+
+    #[distributed_slice(FIXTURES)]
+    static CLOCK: Fixture = linkme::fixture!("clock", || {});
+}
+
+#[test]
+fn test_visibility() {
+    duplicate::check(&FIXTURES, |fixture| fixture.name).unwrap();
+    assert_eq!(FIXTURES.len(), 2);
+    assert!(fixture::find("seed").is_some());
+    assert!(fixture::find("never").is_none());
+}
+
+#[test]
+fn test_setup() {
+    fixture::setup("seed");
+    fixture::setup("seed");
+    assert_eq!(SEEDED.with(Cell::get), 2);
+}
+
+#[test]
+#[should_panic = "no fixture named \"missing\" in this test binary"]
+fn test_setup_missing() {
+    fixture::setup("missing");
+}
+
+#[test]
+fn test_in_module() {
+    let names = |module| {
+        let mut names: Vec<_> = fixture::in_module(module).map(|f| f.name).collect();
+        names.sort_unstable();
+        names
+    };
+    assert_eq!(names("fixture"), ["clock", "seed"]);
+    assert_eq!(names("fixture::nested"), ["clock"]);
+    assert!(names("fixture::nest").is_empty());
+}
@@ -0,0 +1,78 @@
+use linkme::graphql::{self, FieldDef, Root};
+use linkme::{distributed_slice, duplicate};
+
+struct Object {
+    fields: Vec<&'static str>,
+}
+
+impl Object {
+    fn new() -> Self {
+        Object { fields: Vec::new() }
+    }
+
+    fn field(mut self, field: &'static str) -> Self {
+        self.fields.push(field);
+        self
+    }
+}
+
+#[distributed_slice]
+static FIELDS: [FieldDef<&'static str>] = [..];
+
+#[distributed_slice(FIELDS)]
+static USERS: FieldDef<&'static str> = FieldDef {
+    root: Root::Query,
+    name: "users",
+    field: || "users: [User!]!",
+};
+
+#[distributed_slice(FIELDS)]
+static INVOICES: FieldDef<&'static str> = FieldDef {
+    root: Root::Query,
+    name: "invoices",
+    field: || "invoices: [Invoice!]!",
+};
+
+#[distributed_slice(FIELDS)]
+static CREATE_USER: FieldDef<&'static str> = FieldDef {
+    root: Root::Mutation,
+    name: "createUser",
+    field: || "createUser(name: String!): User!",
+};
+
+#[test]
+fn test_assemble() {
+    duplicate::check(&FIELDS, |def| (def.root, def.name)).unwrap();
+
+    let query = graphql::assemble(&FIELDS, Root::Query, Object::new(), Object::field);
+    assert_eq!(query.fields, ["invoices: [Invoice!]!", "users: [User!]!"]);
+
+    let mutation = graphql::assemble(&FIELDS, Root::Mutation, Object::new(), Object::field);
+    assert_eq!(mutation.fields, ["createUser(name: String!): User!"]);
+
+    let subscription = graphql::assemble(&FIELDS, Root::Subscription, Object::new(), Object::field);
+    assert!(subscription.fields.is_empty());
+}
+
+#[test]
+fn test_duplicate() {
+    let fields = [
+        FieldDef {
+            root: Root::Query,
+            name: "users",
+            field: || (),
+        },
+        FieldDef {
+            root: Root::Mutation,
+            name: "users",
+            field: || (),
+        },
+        FieldDef {
+            root: Root::Query,
+            name: "users",
+            field: || (),
+        },
+    ];
+    let err = duplicate::check(&fields, |def| (def.root, def.name)).unwrap_err();
+    assert_eq!(err.to_string(), "(Query, \"users\") is registered twice");
+}
@@ -0,0 +1,37 @@
+use linkme::grpc::{self, Service};
+use linkme::{distributed_slice, duplicate};
+
+#[derive(Default)]
+struct Router {
+    services: Vec<&'static str>,
+}
+
+impl Router {
+    fn add_service(mut self, name: &'static str) -> Self {
+        self.services.push(name);
+        self
+    }
+}
+
+#[distributed_slice]
+static SERVICES: [Service<Router>] = [..];
+
+#[distributed_slice(SERVICES)]
+static HEALTH: Service<Router> = Service {
+    name: "grpc.health.v1.Health",
+    add: |router| router.add_service("health"),
+};
+
+#[distributed_slice(SERVICES)]
+static ACCOUNTS: Service<Router> = Service {
+    name: "accounts.v1.Accounts",
+    add: |router| router.add_service("accounts"),
+};
+
+#[test]
+fn test_serve() {
+    duplicate::check(&SERVICES, |service| service.name).unwrap();
+
+    let router = grpc::serve(&SERVICES, Router::default());
+    assert_eq!(router.services, ["accounts", "health"]);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "std")]
+
+use linkme::distributed_slice;
+use linkme::health::{self, CheckResult, HealthCheck, Outcome};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[distributed_slice]
+static HEALTH_CHECKS: [HealthCheck] = [..];
+
+#[distributed_slice(HEALTH_CHECKS)]
+static DATABASE: HealthCheck = HealthCheck {
+    name: "database",
+    check: || CheckResult::Healthy,
+};
+
+#[distributed_slice(HEALTH_CHECKS)]
+static DISK: HealthCheck = HealthCheck {
+    name: "disk",
+    check: || CheckResult::Unhealthy("93% full".to_owned()),
+};
+
+#[test]
+fn test_run() {
+    let report = health::run(&HEALTH_CHECKS, Some(Duration::from_secs(10)));
+    assert!(!report.is_healthy());
+
+    let mut checks = report.checks;
+    checks.sort_by_key(|(name, _)| *name);
+    assert_eq!(
+        checks,
+        [
+            ("database", Outcome::Healthy),
+            ("disk", Outcome::Unhealthy("93% full".to_owned())),
+        ],
+    );
+}
+
+#[test]
+fn test_inline() {
+    let report = health::run(&HEALTH_CHECKS, None);
+    assert_eq!(report.checks.len(), 2);
+    assert!(!report.is_healthy());
+}
+
+#[test]
+fn test_timeout_and_panic() {
+    let checks = [
+        HealthCheck {
+            name: "broken",
+            check: || panic!("check failed"),
+        },
+        HealthCheck {
+            name: "fast",
+            check: || CheckResult::Healthy,
+        },
+        HealthCheck {
+            name: "slow",
+            check: || {
+                thread::sleep(Duration::from_secs(5));
+                CheckResult::Healthy
+            },
+        },
+        HealthCheck {
+            name: "after",
+            check: || CheckResult::Healthy,
+        },
+    ];
+    let report = health::run(&checks, Some(Duration::from_millis(500)));
+    assert_eq!(
+        report.checks,
+        [
+            ("broken", Outcome::Panicked),
+            ("fast", Outcome::Healthy),
+            ("slow", Outcome::TimedOut),
+            ("after", Outcome::Healthy),
+        ],
+    );
+    assert_eq!(
+        report.to_string(),
+        "broken: panicked\nfast: healthy\nslow: timed out\nafter: healthy\n",
+    );
+}
+
+#[test]
+fn test_hung_check() {
+    let checks = [
+        HealthCheck {
+            name: "hung",
+            check: || loop {
+                thread::park();
+            },
+        },
+        HealthCheck {
+            name: "healthy",
+            check: || {
+                thread::sleep(Duration::from_millis(100));
+                CheckResult::Healthy
+            },
+        },
+    ];
+    let start = Instant::now();
+    let report = health::run(&checks, Some(Duration::from_millis(500)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(
+        report.checks,
+        [("hung", Outcome::TimedOut), ("healthy", Outcome::Healthy)],
+    );
+}
@@ -0,0 +1,80 @@
+use linkme::distributed_slice;
+use linkme::hwid::{self, Bus, HardwareId, IdMatch, IdPattern, ANY};
+
+#[distributed_slice]
+static DRIVERS: [IdMatch<&'static str>] = [..];
+
+#[distributed_slice(DRIVERS)]
+static STORAGE: IdMatch<&'static str> = IdMatch {
+    pattern: IdPattern::class(Bus::Pci, 0x01_00_00, 0xff_00_00),
+    probe: |_| {},
+};
+
+#[distributed_slice(DRIVERS)]
+static NVME: IdMatch<&'static str> = IdMatch {
+    pattern: IdPattern::class(Bus::Pci, 0x01_08_00, 0xff_ff_00),
+    probe: |_| {},
+};
+
+#[distributed_slice(DRIVERS)]
+static SAMSUNG_NVME: IdMatch<&'static str> = IdMatch {
+    pattern: IdPattern::device(Bus::Pci, 0x144d, 0xa808),
+    probe: |_| {},
+};
+
+#[distributed_slice(DRIVERS)]
+static INTEL_USB: IdMatch<&'static str> = IdMatch {
+    pattern: IdPattern {
+        bus: Bus::Usb,
+        vendor: 0x8087,
+        device: ANY,
+        class: 0,
+        class_mask: 0,
+    },
+    probe: |_| {},
+};
+
+fn pci(vendor: u32, device: u32, class: u32) -> HardwareId {
+    HardwareId {
+        bus: Bus::Pci,
+        vendor,
+        device,
+        class,
+    }
+}
+
+#[test]
+fn test_most_specific() {
+    let found = hwid::find(&DRIVERS, &pci(0x144d, 0xa808, 0x01_08_02)).unwrap();
+    assert_eq!(found.pattern, SAMSUNG_NVME.pattern);
+
+    let found = hwid::find(&DRIVERS, &pci(0x1e0f, 0x0001, 0x01_08_02)).unwrap();
+    assert_eq!(found.pattern, NVME.pattern);
+
+    let found = hwid::find(&DRIVERS, &pci(0x1000, 0x0097, 0x01_07_00)).unwrap();
+    assert_eq!(found.pattern, STORAGE.pattern);
+
+    assert!(hwid::find(&DRIVERS, &pci(0x8086, 0x100e, 0x02_00_00)).is_none());
+}
+
+#[test]
+fn test_bus() {
+    let usb = HardwareId {
+        bus: Bus::Usb,
+        vendor: 0x8087,
+        device: 0x0a2b,
+        class: 0xe0,
+    };
+    assert_eq!(
+        hwid::find(&DRIVERS, &usb).unwrap().pattern,
+        INTEL_USB.pattern
+    );
+
+    // Same numbers on a different bus do not match.
+    let pci = HardwareId {
+        bus: Bus::Pci,
+        ..usb
+    };
+    assert!(hwid::find(&DRIVERS, &pci).is_none());
+    assert!(!hwid::probe(&DRIVERS, &pci, &"device"));
+}
@@ -0,0 +1,67 @@
+use linkme::distributed_slice;
+use linkme::i18n::{self, Catalog};
+
+#[distributed_slice]
+static CATALOGS: [Catalog] = [..];
+
+#[distributed_slice(CATALOGS)]
+static BILLING_DE: Catalog = Catalog {
+    locale: "de",
+    domain: "billing",
+    messages: &[("invoice", "Rechnung"), ("refund", "Erstattung")],
+};
+
+#[distributed_slice(CATALOGS)]
+static BILLING_FR: Catalog = Catalog {
+    locale: "fr",
+    domain: "billing",
+    messages: &[("invoice", "Facture")],
+};
+
+#[distributed_slice(CATALOGS)]
+static ACCOUNT_DE: Catalog = Catalog {
+    locale: "de",
+    domain: "account",
+    messages: &[("invoice", "Rechnungsadresse"), ("refund", "Erstattung")],
+};
+
+#[test]
+fn test_lookup() {
+    assert_eq!(
+        i18n::lookup(&CATALOGS, "de", "billing", "invoice"),
+        Some("Rechnung")
+    );
+    assert_eq!(
+        i18n::lookup(&CATALOGS, "fr", "billing", "invoice"),
+        Some("Facture")
+    );
+    assert_eq!(
+        i18n::lookup(&CATALOGS, "de", "account", "invoice"),
+        Some("Rechnungsadresse")
+    );
+    assert_eq!(i18n::lookup(&CATALOGS, "fr", "billing", "refund"), None);
+}
+
+#[test]
+fn test_validate() {
+    i18n::validate(&CATALOGS).unwrap();
+
+    static CONFLICTING: [Catalog; 2] = [
+        Catalog {
+            locale: "de",
+            domain: "billing",
+            messages: &[("invoice", "Rechnung")],
+        },
+        Catalog {
+            locale: "de",
+            domain: "billing",
+            messages: &[("refund", "Erstattung"), ("invoice", "Faktura")],
+        },
+    ];
+    let conflict = i18n::validate(&CONFLICTING).unwrap_err();
+    assert_eq!(conflict.key, "invoice");
+    assert_eq!(
+        conflict.to_string(),
+        "conflicting translations of de/billing/invoice: \"Rechnung\" and \"Faktura\"",
+    );
+}
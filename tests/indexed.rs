@@ -0,0 +1,156 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::incompatible_msrv)]
+
+use linkme::distributed_slice;
+use linkme::indexed::{Indexed, IndexedSlice};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+pub struct Word {
+    name: &'static str,
+    len: usize,
+}
+
+#[distributed_slice]
+static WORDS: [Word] = [..];
+
+static BY_NAME: IndexedSlice<Word> = IndexedSlice::new(&WORDS, |word| word.name);
+
+macro_rules! words {
+    ($($word:ident)*) => {
+        $(
+            #[distributed_slice(WORDS)]
+            #[allow(non_upper_case_globals)]
+            static $word: Word = Word {
+                name: stringify!($word),
+                len: stringify!($word).len(),
+            };
+        )*
+    };
+}
+
+words! {
+    alpha bravo charlie delta echo foxtrot golf hotel india juliett kilo lima
+    mike november oscar papa quebec romeo sierra tango uniform victor whiskey
+    xray yankee zulu zero one two three four five six seven eight nine
+}
+
+#[test]
+fn test_get() {
+    BY_NAME.validate().unwrap();
+    assert_eq!(WORDS.len(), 36);
+    for word in WORDS {
+        let found = BY_NAME.get(word.name).unwrap();
+        assert!(std::ptr::eq(found, word));
+    }
+    assert_eq!(BY_NAME.get("november").unwrap().len, 8);
+    assert!(BY_NAME.get("ten").is_none());
+    assert!(BY_NAME.get("").is_none());
+}
+
+#[distributed_slice]
+static TWICE: [Word] = [..];
+
+static TWICE_BY_NAME: IndexedSlice<Word> = IndexedSlice::new(&TWICE, |word| word.name);
+
+#[distributed_slice(TWICE)]
+static FIRST: Word = Word {
+    name: "same",
+    len: 1,
+};
+
+#[distributed_slice(TWICE)]
+static SECOND: Word = Word {
+    name: "same",
+    len: 2,
+};
+
+#[test]
+fn test_duplicate() {
+    let err = TWICE_BY_NAME.validate().unwrap_err();
+    assert_eq!(err.to_string(), "key \"same\" is registered twice");
+    assert!(TWICE_BY_NAME.get("same").is_some());
+}
+
+#[distributed_slice]
+static EMPTY: [Word] = [..];
+
+static EMPTY_BY_NAME: IndexedSlice<Word> = IndexedSlice::new(&EMPTY, |word| word.name);
+
+#[test]
+fn test_empty() {
+    EMPTY_BY_NAME.validate().unwrap();
+    assert!(EMPTY_BY_NAME.get("anything").is_none());
+}
+
+pub struct Route {
+    method: &'static str,
+    path: &'static str,
+    weight: u32,
+}
+
+#[distributed_slice]
+static ROUTES: [Route] = [..];
+
+static BY_METHOD: Indexed<Route, &str> = Indexed::new(&ROUTES, |route| route.method);
+
+static BY_WEIGHT: Indexed<Route, u32> = Indexed::new(&ROUTES, |route| route.weight);
+
+#[distributed_slice(ROUTES)]
+static GET_USERS: Route = Route {
+    method: "GET",
+    path: "/users",
+    weight: 3,
+};
+
+#[distributed_slice(ROUTES)]
+static POST_USERS: Route = Route {
+    method: "POST",
+    path: "/users",
+    weight: 1,
+};
+
+#[distributed_slice(ROUTES)]
+static GET_ORDERS: Route = Route {
+    method: "GET",
+    path: "/orders",
+    weight: 2,
+};
+
+#[test]
+fn test_indexed() {
+    let mut get: Vec<_> = BY_METHOD.get_all(&"GET").map(|route| route.path).collect();
+    get.sort_unstable();
+    assert_eq!(get, ["/orders", "/users"]);
+    assert_eq!(BY_METHOD.get(&"POST").unwrap().weight, 1);
+    assert!(BY_METHOD.get(&"DELETE").is_none());
+    assert_eq!(BY_METHOD.get_all(&"DELETE").len(), 0);
+
+    let by_weight: Vec<_> = BY_WEIGHT.iter().map(|route| route.weight).collect();
+    assert_eq!(by_weight, [1, 2, 3]);
+    let by_weight: Vec<_> = (&BY_WEIGHT).into_iter().map(|route| route.weight).collect();
+    assert_eq!(by_weight, [1, 2, 3]);
+    assert_eq!(BY_WEIGHT.get(&2).unwrap().path, "/orders");
+}
+
+static KEY_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+static COUNTED: Indexed<Route, u32> = Indexed::new(&ROUTES, |route| {
+    KEY_CALLS.fetch_add(1, Ordering::Relaxed);
+    route.weight
+});
+
+#[test]
+fn test_build_once() {
+    let threads: Vec<_> = (0..8)
+        .map(|_| thread::spawn(|| COUNTED.get(&3).unwrap().path))
+        .collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), "/users");
+    }
+    assert_eq!(COUNTED.iter().len(), 3);
+    assert!(COUNTED.get(&4).is_none());
+
+    // The key fn ran once per entry, for a single build of the index.
+    assert_eq!(KEY_CALLS.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,71 @@
+use linkme::distributed_slice;
+use linkme::initcall::{self, Initcall, Level, CORE, DEVICE, EARLY, LATE};
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log(name: &'static str) {
+    LOG.with(|log| log.borrow_mut().push(name));
+}
+
+fn take_log() -> Vec<&'static str> {
+    LOG.with(|log| log.borrow_mut().split_off(0))
+}
+
+#[distributed_slice(LATE)]
+static SHELL: Initcall = Initcall {
+    name: "shell",
+    call: || log("shell"),
+};
+
+#[distributed_slice(DEVICE)]
+static UART: Initcall = Initcall {
+    name: "uart",
+    call: || log("uart"),
+};
+
+#[distributed_slice(CORE)]
+static HEAP: Initcall = Initcall {
+    name: "heap",
+    call: || log("heap"),
+};
+
+#[distributed_slice(EARLY)]
+static CONSOLE: Initcall = Initcall {
+    name: "console",
+    call: || log("console"),
+};
+
+#[test]
+fn test_run() {
+    initcall::run();
+    assert_eq!(take_log(), ["console", "heap", "uart", "shell"]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_run_timed() {
+    let mut reported = Vec::new();
+    initcall::run_timed(|level, initcall, _elapsed| {
+        reported.push((level, initcall.name));
+    });
+    assert_eq!(
+        reported,
+        [
+            (Level::Early, "console"),
+            (Level::Core, "heap"),
+            (Level::Device, "uart"),
+            (Level::Late, "shell"),
+        ],
+    );
+    assert_eq!(take_log(), ["console", "heap", "uart", "shell"]);
+}
+
+#[test]
+fn test_levels() {
+    assert!(Level::Subsys.calls().is_empty());
+    assert_eq!(Level::Device.calls().len(), 1);
+    assert_eq!(Level::Subsys.to_string(), "subsys");
+}
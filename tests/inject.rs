@@ -0,0 +1,86 @@
+#![cfg(feature = "alloc")]
+
+use linkme::distributed_slice;
+use linkme::inject::{Error, Provider, Resolver};
+use std::rc::Rc;
+
+pub struct Config {
+    url: &'static str,
+}
+
+pub struct Database {
+    config: Rc<Config>,
+}
+
+pub struct Service {
+    database: Rc<Database>,
+    config: Rc<Config>,
+}
+
+#[distributed_slice]
+static PROVIDERS: [Provider] = [..];
+
+#[distributed_slice(PROVIDERS)]
+static CONFIG: Provider = Provider::new::<Config>(|_| {
+    Ok(Box::new(Config {
+        url: "postgres://localhost",
+    }))
+});
+
+#[distributed_slice(PROVIDERS)]
+static DATABASE: Provider = Provider::new::<Database>(|resolver| {
+    let config = resolver.resolve::<Config>()?;
+    Ok(Box::new(Database { config }))
+});
+
+#[distributed_slice(PROVIDERS)]
+static SERVICE: Provider = Provider::new::<Service>(|resolver| {
+    let database = resolver.resolve::<Database>()?;
+    let config = resolver.resolve::<Config>()?;
+    Ok(Box::new(Service { database, config }))
+});
+
+#[test]
+fn test_resolve() {
+    let mut resolver = Resolver::new(&PROVIDERS);
+    let service = resolver.resolve::<Service>().unwrap();
+    assert_eq!(service.config.url, "postgres://localhost");
+    assert!(Rc::ptr_eq(&service.config, &service.database.config));
+
+    let again = resolver.resolve::<Service>().unwrap();
+    assert!(Rc::ptr_eq(&service, &again));
+}
+
+#[test]
+fn test_errors() {
+    struct Chicken;
+    struct Egg;
+
+    let providers = [
+        Provider::new::<Chicken>(|resolver| {
+            resolver.resolve::<Egg>()?;
+            Ok(Box::new(Chicken))
+        }),
+        Provider::new::<Egg>(|resolver| {
+            resolver.resolve::<Chicken>()?;
+            Ok(Box::new(Egg))
+        }),
+        Provider::new::<Config>(|_| Ok(Box::new(Egg))),
+    ];
+    let mut resolver = Resolver::new(&providers);
+
+    match resolver.resolve::<Chicken>() {
+        Err(Error::Cycle { type_name }) => assert!(type_name.ends_with("Chicken")),
+        _ => panic!("expected cycle"),
+    }
+    match resolver.resolve::<Config>() {
+        Err(Error::WrongType { type_name }) => assert!(type_name.ends_with("Config")),
+        _ => panic!("expected wrong type"),
+    }
+    match resolver.resolve::<Database>() {
+        Err(err @ Error::Missing { .. }) => {
+            assert_eq!(err.to_string(), "no provider for inject::Database");
+        }
+        _ => panic!("expected missing provider"),
+    }
+}
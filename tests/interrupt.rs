@@ -0,0 +1,87 @@
+use linkme::distributed_slice;
+use linkme::interrupt::{self, Interrupt, Invalid};
+use std::cell::Cell;
+
+thread_local! {
+    static FIRED: Cell<u16> = Cell::new(0);
+}
+
+fn fired() -> u16 {
+    FIRED.with(Cell::get)
+}
+
+#[distributed_slice]
+static INTERRUPTS: [Interrupt] = [..];
+
+#[distributed_slice(INTERRUPTS)]
+static UART0: Interrupt = linkme::interrupt!(INTERRUPTS, 3, || FIRED.with(|fired| fired.set(3)), 2);
+
+#[distributed_slice(INTERRUPTS)]
+static TIMER: Interrupt =
+    linkme::interrupt!(INTERRUPTS, 0x7, || FIRED.with(|fired| fired.set(7)), 1);
+
+// A second vector table may reuse the interrupt numbers of the first.
+#[distributed_slice]
+static WAKEUP_INTERRUPTS: [Interrupt] = [..];
+
+#[distributed_slice(WAKEUP_INTERRUPTS)]
+static UART0_WAKEUP: Interrupt = linkme::interrupt!(WAKEUP_INTERRUPTS, 3, default_isr, 0);
+
+fn default_isr() {
+    FIRED.with(|fired| fired.set(100));
+}
+
+#[test]
+fn test_populate() {
+    let mut table: [fn(); 8] = [default_isr; 8];
+    interrupt::populate(&INTERRUPTS, &mut table).unwrap();
+
+    table[3]();
+    assert_eq!(fired(), 3);
+    table[7]();
+    assert_eq!(fired(), 7);
+    table[0]();
+    assert_eq!(fired(), 100);
+
+    let mut small: [fn(); 4] = [default_isr; 4];
+    let err = interrupt::populate(&INTERRUPTS, &mut small).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "interrupt 7 is out of range for a table of 4 entries",
+    );
+}
+
+#[test]
+fn test_dispatch() {
+    assert!(interrupt::dispatch(&INTERRUPTS, 7));
+    assert_eq!(fired(), 7);
+    assert!(!interrupt::dispatch(&INTERRUPTS, 4));
+    assert_eq!(TIMER.priority, 1);
+}
+
+#[test]
+fn test_separate_tables() {
+    assert_eq!(WAKEUP_INTERRUPTS.len(), 1);
+    assert_eq!(WAKEUP_INTERRUPTS[0].irq, UART0.irq);
+}
+
+#[test]
+fn test_duplicate() {
+    let interrupts = [
+        Interrupt {
+            irq: 1,
+            handler: default_isr,
+            priority: 0,
+        },
+        Interrupt {
+            irq: 1,
+            handler: default_isr,
+            priority: 3,
+        },
+    ];
+    let mut table: [fn(); 4] = [default_isr; 4];
+    match interrupt::populate(&interrupts, &mut table) {
+        Err(Invalid::Duplicate { irq: 1 }) => {}
+        other => panic!("unexpected {:?}", other.map(|()| "ok")),
+    }
+}
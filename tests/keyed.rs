@@ -0,0 +1,56 @@
+use linkme::distributed_slice;
+use linkme::map::StaticMapView;
+
+#[distributed_slice]
+static LINKED: [(&'static str, u8)] = [..];
+
+static LEVELS: StaticMapView<&'static str, u8> = StaticMapView::new(&LINKED);
+
+#[distributed_slice(LINKED)]
+static DEBUG: (&'static str, u8) = (linkme::key!(LEVELS, "debug"), 1);
+
+#[distributed_slice(LINKED)]
+static INFO: (&'static str, u8) = (linkme::key!(LEVELS, "info"), 2);
+
+#[test]
+fn test_lookup() {
+    assert_eq!(*linkme::lookup!(LEVELS, "debug"), 1);
+    assert_eq!(*linkme::lookup!(LEVELS, "info"), 2);
+}
+
+#[cfg(feature = "alloc")]
+mod distributed_map {
+    use linkme::distributed_map;
+
+    #[distributed_map]
+    static HANDLERS: [(&'static str, fn() -> &'static str)] = [..];
+
+    #[distributed_map(HANDLERS)]
+    static PING: (&'static str, fn() -> &'static str) = (linkme::key!(HANDLERS, "ping"), || "pong");
+
+    #[test]
+    fn test_lookup() {
+        let handler = linkme::lookup!(HANDLERS, "ping");
+        assert_eq!(handler(), "pong");
+    }
+}
+
+struct Broken;
+
+impl Broken {
+    fn get(&self, _key: &str) -> Option<u8> {
+        None
+    }
+}
+
+static BROKEN: Broken = Broken;
+
+// Marker registered without a matching entry in the registry.
+#[used]
+static ORPHAN: &str = linkme::key!(BROKEN, "orphan");
+
+#[test]
+#[should_panic = "key \"orphan\" has a marker but no entry in BROKEN"]
+fn test_missing_entry() {
+    linkme::lookup!(BROKEN, "orphan");
+}
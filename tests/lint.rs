@@ -0,0 +1,61 @@
+#![cfg(feature = "alloc")]
+
+use linkme::lint::{self, Check, CheckCtx, Finding, Severity};
+use linkme::{distributed_slice, duplicate};
+
+#[distributed_slice]
+static CHECKS: [Check] = [..];
+
+#[distributed_slice(CHECKS)]
+static LICENSE: Check = Check {
+    name: "license",
+    tags: &["ci"],
+    run: |ctx| vec![Finding::error(format!("missing LICENSE in {}", ctx.root)).at("Cargo.toml", 3)],
+};
+
+#[distributed_slice(CHECKS)]
+static FORMAT: Check = Check {
+    name: "format",
+    tags: &["style", "ci"],
+    run: |ctx| {
+        if ctx.fix {
+            Vec::new()
+        } else {
+            vec![Finding::warning("file is not formatted").at("src/lib.rs", 1)]
+        }
+    },
+};
+
+#[distributed_slice(CHECKS)]
+static CLEAN: Check = Check {
+    name: "clean",
+    tags: &["style"],
+    run: |_ctx| Vec::new(),
+};
+
+#[test]
+fn test_run() {
+    duplicate::check(&CHECKS, |check| check.name).unwrap();
+
+    let ctx = CheckCtx {
+        root: "/work",
+        fix: false,
+    };
+
+    let report = lint::run(&CHECKS, &ctx, &[]);
+    assert!(report.has_errors());
+    let expected = "\
+        warning[format]: file is not formatted (src/lib.rs:1)\n\
+        error[license]: missing LICENSE in /work (Cargo.toml:3)\n";
+    assert_eq!(report.to_string(), expected);
+
+    let report = lint::run(&CHECKS, &ctx, &["style"]);
+    assert!(!report.has_errors());
+    assert_eq!(report.findings.len(), 1);
+    assert_eq!(report.findings[0].0, "format");
+    assert_eq!(report.findings[0].1.severity, Severity::Warning);
+
+    let ctx = CheckCtx { fix: true, ..ctx };
+    let report = lint::run(&CHECKS, &ctx, &["style"]);
+    assert!(report.findings.is_empty());
+}
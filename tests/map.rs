@@ -0,0 +1,91 @@
+use linkme::distributed_slice;
+use linkme::map::StaticMapView;
+
+#[distributed_slice]
+static COLORS: [(&'static str, u32)] = [..];
+
+static BY_NAME: StaticMapView<&'static str, u32> = StaticMapView::new(&COLORS);
+
+#[distributed_slice(COLORS)]
+static RED: (&'static str, u32) = ("red", 0xff0000);
+
+#[distributed_slice(COLORS)]
+static GREEN: (&'static str, u32) = ("green", 0x00ff00);
+
+#[distributed_slice(COLORS)]
+static BLUE: (&'static str, u32) = ("blue", 0x0000ff);
+
+#[test]
+fn test_get() {
+    assert_eq!(BY_NAME.len(), 3);
+    assert_eq!(BY_NAME.get("red"), Some(&0xff0000));
+    assert_eq!(BY_NAME.get("green"), Some(&0x00ff00));
+    assert_eq!(BY_NAME.get("blue"), Some(&0x0000ff));
+    assert!(!BY_NAME.contains_key("cyan"));
+
+    // Whether the linker placed these in key order is up to the linker;
+    // the answer just has to be consistent with validate.
+    assert_eq!(BY_NAME.is_sorted(), BY_NAME.validate().is_ok());
+}
+
+#[distributed_slice]
+static ONE: [(u8, char)] = [..];
+
+static ONE_VIEW: StaticMapView<u8, char> = StaticMapView::new(&ONE);
+
+#[distributed_slice(ONE)]
+static A: (u8, char) = (1, 'a');
+
+#[test]
+fn test_single() {
+    ONE_VIEW.validate().unwrap();
+    assert!(ONE_VIEW.is_sorted());
+    assert_eq!(ONE_VIEW.get(&1), Some(&'a'));
+    assert_eq!(ONE_VIEW.get(&2), None);
+}
+
+#[distributed_slice]
+static REPEATED: [(u8, char)] = [..];
+
+static REPEATED_VIEW: StaticMapView<u8, char> = StaticMapView::new(&REPEATED);
+
+#[distributed_slice(REPEATED)]
+static B: (u8, char) = (2, 'b');
+
+#[distributed_slice(REPEATED)]
+static C: (u8, char) = (2, 'c');
+
+#[test]
+fn test_repeated_key() {
+    let err = REPEATED_VIEW.validate().unwrap_err();
+    assert_eq!(err.to_string(), "key 2 is not in order after 2");
+    assert!(!REPEATED_VIEW.is_sorted());
+    assert!(REPEATED_VIEW.get(&2).is_some());
+}
+
+// An ordered slice with each pair placed at its own key is guaranteed to be
+// in key order, so lookups always binary search.
+#[distributed_slice]
+#[linkme(ordered)]
+static PORTS: [(u16, &'static str)] = [..];
+
+static BY_PORT: StaticMapView<u16, &'static str> = StaticMapView::new(&PORTS);
+
+#[distributed_slice(PORTS, order = 443)]
+static HTTPS: (u16, &'static str) = (443, "https");
+
+#[distributed_slice(PORTS, order = 22)]
+static SSH: (u16, &'static str) = (22, "ssh");
+
+#[distributed_slice(PORTS, order = 80)]
+static HTTP: (u16, &'static str) = (80, "http");
+
+#[test]
+fn test_ordered() {
+    BY_PORT.validate().unwrap();
+    assert!(BY_PORT.is_sorted());
+    assert_eq!(BY_PORT.get(&22), Some(&"ssh"));
+    assert_eq!(BY_PORT.get(&80), Some(&"http"));
+    assert_eq!(BY_PORT.get(&443), Some(&"https"));
+    assert_eq!(BY_PORT.get(&8080), None);
+}
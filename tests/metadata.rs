@@ -0,0 +1,154 @@
+#![cfg(not(no_const_panic))]
+
+use linkme::distributed_slice;
+use linkme::metadata::{self, DecodeError, LookupError, Record, RECORDS, RECORD_LEN};
+use std::mem;
+#[cfg(target_os = "linux")]
+use std::{env, process::Command};
+
+#[distributed_slice]
+static CODECS: [u8] = [..];
+
+#[distributed_slice(CODECS)]
+static GZIP: u8 = 1;
+
+#[distributed_slice(RECORDS)]
+static GZIP_META: Record = linkme::metadata!(CODECS, "gzip", "2.1");
+
+// What a linker inserts as padding between contributions to the section.
+#[distributed_slice(RECORDS)]
+#[clippy::msrv = "1.57"]
+static PADDING: Record = unsafe { mem::transmute::<[u8; RECORD_LEN], Record>([0; RECORD_LEN]) };
+
+#[distributed_slice(CODECS)]
+static ZSTD: u8 = 2;
+
+#[distributed_slice(RECORDS)]
+static ZSTD_META: Record = linkme::metadata!(CODECS, "zstd", "1.5");
+
+#[test]
+fn test_layout() {
+    // Exact bytes, identical on every target regardless of endianness.
+    let mut expected = Vec::new();
+    expected.extend_from_slice(b"LKMD\x01\x00\x00\x00");
+    expected.extend_from_slice(b"\x01\x01\x00a");
+    expected.extend_from_slice(b"\x02\x02\x00bc");
+    expected.extend_from_slice(b"\x03\x00\x00");
+    expected.extend_from_slice(b"\x04\x04\x00d::e");
+    expected.resize(RECORD_LEN, 0);
+
+    const RECORD: Record = Record::new("a", "bc", "", "d::e");
+    assert_eq!(RECORD.as_bytes(), &expected[..]);
+}
+
+#[test]
+fn test_registered() {
+    assert!(RECORDS
+        .iter()
+        .filter(|record| record.as_bytes() != PADDING.as_bytes())
+        .all(|record| record.decode().is_ok()));
+
+    let decoded = GZIP_META.decode().unwrap();
+    assert_eq!(decoded.slice, "CODECS");
+    assert_eq!(decoded.name, "gzip");
+    assert_eq!(decoded.version, "2.1");
+    assert_eq!(decoded.provenance, "metadata");
+}
+
+#[test]
+fn test_section() {
+    let record = Record::new("S", "x", "1", "m");
+    let mut section = Vec::new();
+    section.extend_from_slice(&[0; RECORD_LEN]);
+    section.extend_from_slice(record.as_bytes());
+    section.extend_from_slice(&[0; 3]);
+
+    let records: Vec<_> = metadata::records(&section).collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].as_ref().unwrap().name, "x");
+    assert_eq!(records[1], Err(DecodeError::Truncated));
+}
+
+#[test]
+fn test_errors() {
+    let mut bytes = Record::new("S", "x", "1", "m").as_bytes().to_vec();
+    bytes[4] = 9;
+    assert_eq!(
+        metadata::decode(&bytes),
+        Err(DecodeError::UnsupportedFormat(9))
+    );
+    bytes[0] = b'X';
+    assert_eq!(metadata::decode(&bytes), Err(DecodeError::BadMagic));
+
+    let mut bytes = Record::new("S", "x", "1", "m").as_bytes().to_vec();
+    bytes[HEADER_PLUS_TAG_AND_LEN] = 0xff;
+    assert_eq!(metadata::decode(&bytes), Err(DecodeError::InvalidUtf8));
+}
+
+// Offset of the value of the first field.
+const HEADER_PLUS_TAG_AND_LEN: usize = 8 + 3;
+
+#[test]
+fn test_find() {
+    assert!(metadata::is_available());
+    assert_eq!(metadata::find("CODECS", "gzip").unwrap().version, "2.1");
+    assert_eq!(metadata::find("CODECS", "zstd").unwrap().version, "1.5");
+    assert_eq!(
+        metadata::find("CODECS", "brotli"),
+        Err(LookupError::NotFound)
+    );
+    assert_eq!(
+        metadata::find("linkme", "sentinel").unwrap().provenance,
+        "linkme::metadata"
+    );
+}
+
+// Runs this test binary again after objcopy has rewritten it, once with the
+// metadata section removed and once with only the symbols and debug
+// information stripped.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_stripped() {
+    let rewritten = env::var("LINKME_METADATA_OBJCOPY").unwrap_or_default();
+    match rewritten.as_str() {
+        "--remove-section=linkme_LINKME_META" => {
+            assert!(!metadata::is_available());
+            assert_eq!(metadata::find("CODECS", "gzip"), Err(LookupError::Stripped));
+            return;
+        }
+        "--strip-all" => {
+            assert!(metadata::is_available());
+            assert_eq!(metadata::find("CODECS", "gzip").unwrap().version, "2.1");
+            return;
+        }
+        _ => {}
+    }
+
+    let exe = env::current_exe().unwrap();
+    for (option, suffix) in &[
+        ("--remove-section=linkme_LINKME_META", "removed"),
+        ("--strip-all", "stripped"),
+    ] {
+        let mut copy = exe.clone().into_os_string();
+        copy.push("-");
+        copy.push(suffix);
+        let status = Command::new("objcopy")
+            .arg(option)
+            .arg(&exe)
+            .arg(&copy)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let output = Command::new(&copy)
+            .args(&["--exact", "test_stripped"])
+            .env("LINKME_METADATA_OBJCOPY", option)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}\n{}",
+            option,
+            String::from_utf8_lossy(&output.stdout),
+        );
+    }
+}
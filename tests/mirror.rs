@@ -0,0 +1,40 @@
+mod upstream {
+    use linkme::distributed_slice;
+
+    pub struct Plugin {
+        pub name: &'static str,
+    }
+
+    #[distributed_slice]
+    pub static PLUGINS: [Plugin] = [..];
+
+    #[distributed_slice(PLUGINS)]
+    static BUILTIN: Plugin = Plugin { name: "builtin" };
+}
+
+// Within one crate the mirror needs a name of its own; across crates it may
+// reuse the upstream name.
+mod downstream {
+    use crate::upstream::{self, Plugin};
+    use linkme::distributed_slice;
+
+    #[distributed_slice]
+    #[linkme(mirror = upstream::PLUGINS)]
+    pub static ALL_PLUGINS: [Plugin] = [..];
+
+    #[distributed_slice(ALL_PLUGINS)]
+    static EXTRA: Plugin = Plugin { name: "extra" };
+
+    pub fn names() -> Vec<&'static str> {
+        let mut names: Vec<_> = ALL_PLUGINS.iter().map(|plugin| plugin.name).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[test]
+fn test_mirror() {
+    assert_eq!(upstream::PLUGINS.len(), 2);
+    assert_eq!(upstream::PLUGINS.as_ptr(), downstream::ALL_PLUGINS.as_ptr());
+    assert_eq!(downstream::names(), ["builtin", "extra"]);
+}
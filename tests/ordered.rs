@@ -0,0 +1,97 @@
+use linkme::distributed_slice;
+
+pub struct Layer {
+    pub name: &'static str,
+}
+
+#[distributed_slice]
+#[linkme(ordered)]
+pub static LAYERS: [Layer] = [..];
+
+#[distributed_slice(LAYERS, order = 100)]
+static LOGGING: Layer = Layer { name: "logging" };
+
+#[distributed_slice(LAYERS, order = 2147483647)]
+static LAST: Layer = Layer { name: "last" };
+
+#[distributed_slice(LAYERS)]
+static DEFAULT: Layer = Layer { name: "default" };
+
+#[distributed_slice(LAYERS, order = -5)]
+static AUTH: Layer = Layer { name: "auth" };
+
+mod early {
+    use super::{Layer, LAYERS};
+    use linkme::distributed_slice;
+
+    #[distributed_slice(LAYERS, order = 1)]
+    static COMPRESSION: Layer = Layer {
+        name: "compression",
+    };
+
+    #[distributed_slice(LAYERS, order = -2147483648)]
+    static FIRST: Layer = Layer { name: "first" };
+}
+
+#[distributed_slice]
+#[linkme(ordered)]
+pub static HOOKS: [fn() -> u32] = [..];
+
+#[distributed_slice(HOOKS, order = 2)]
+fn two() -> u32 {
+    2
+}
+
+#[distributed_slice(HOOKS, order = -1)]
+fn minus_one() -> u32 {
+    0
+}
+
+#[distributed_slice(HOOKS)]
+fn zero() -> u32 {
+    1
+}
+
+#[test]
+fn test_ordered() {
+    let names: Vec<_> = LAYERS.iter().map(|layer| layer.name).collect();
+    assert_eq!(
+        names,
+        ["first", "auth", "default", "compression", "logging", "last"],
+    );
+
+    let hooks: Vec<u32> = HOOKS.iter().map(|hook| hook()).collect();
+    assert_eq!(hooks, [0, 1, 2]);
+
+    // Function elements remain callable by name.
+    assert_eq!(two(), 2);
+}
+
+// Long enough that its sections need a hashed name on macOS. Only ever read
+// through `iter_skip_null`, which has to sort it all the same.
+#[distributed_slice]
+#[linkme(ordered)]
+pub static ORDERED_STARTUP_HOOKS: [fn() -> u32] = [..];
+
+#[distributed_slice(ORDERED_STARTUP_HOOKS, order = 9)]
+fn late() -> u32 {
+    9
+}
+
+#[distributed_slice(ORDERED_STARTUP_HOOKS, order = -9)]
+fn early() -> u32 {
+    -9i32 as u32
+}
+
+#[distributed_slice(ORDERED_STARTUP_HOOKS)]
+fn middle() -> u32 {
+    0
+}
+
+#[test]
+fn test_iter_skip_null() {
+    let hooks: Vec<u32> = unsafe { ORDERED_STARTUP_HOOKS.iter_skip_null() }
+        .map(|hook| hook())
+        .collect();
+    assert_eq!(hooks, [early(), middle(), late()]);
+}
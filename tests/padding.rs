@@ -0,0 +1,41 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+static HOOKS: [fn() -> u8] = [..];
+
+#[distributed_slice(HOOKS)]
+static ONE: fn() -> u8 = || 1;
+
+#[distributed_slice(HOOKS)]
+static TWO: fn() -> u8 = || 2;
+
+#[test]
+fn test_linked() {
+    assert_eq!(HOOKS.may_contain_padding(), cfg!(windows));
+
+    let mut values: Vec<u8> = unsafe { HOOKS.iter_skip_null() }
+        .map(|hook| hook())
+        .collect();
+    values.sort_unstable();
+    assert_eq!(values, [1, 2]);
+}
+
+#[cfg(not(any(windows, target_family = "wasm")))]
+#[test]
+fn test_padded() {
+    use linkme::DistributedSlice;
+
+    // What a slice of references looks like after a linker has padded
+    // between contributions.
+    static PADDED: [usize; 6] = [0, 7, 0, 0, 8, 0];
+
+    let range = PADDED.as_ptr_range();
+    let slice: DistributedSlice<[usize]> =
+        unsafe { DistributedSlice::private_new(range.start, range.end) };
+    let values: Vec<usize> = unsafe { slice.iter_skip_null() }.copied().collect();
+    assert_eq!(values, [7, 8]);
+
+    let empty: DistributedSlice<[usize]> =
+        unsafe { DistributedSlice::private_new(range.start, range.start) };
+    assert_eq!(unsafe { empty.iter_skip_null() }.count(), 0);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+use linkme::distributed_slice;
+use linkme::panic_hook::{self, Panic, OBSERVERS};
+use std::cell::RefCell;
+use std::env;
+use std::panic;
+use std::process::Command;
+
+thread_local! {
+    static SEEN: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+#[distributed_slice(OBSERVERS)]
+fn record_message(panic: &Panic) {
+    let message = panic
+        .message()
+        .map_or("<none>".to_owned(), |m| m.to_string());
+    SEEN.with(|seen| seen.borrow_mut().push(format!("message: {}", message)));
+}
+
+#[distributed_slice(OBSERVERS)]
+fn record_location(panic: &Panic) {
+    let location = panic.location().unwrap();
+    assert!(location.file().ends_with("panic_hook.rs"));
+    SEEN.with(|seen| seen.borrow_mut().push("location".to_owned()));
+}
+
+// Set in the child process started by test_observer_panics.
+const PANIC_IN_OBSERVER: &str = "LINKME_TEST_PANIC_IN_OBSERVER";
+
+#[distributed_slice(OBSERVERS)]
+fn panic_on_request(_: &Panic) {
+    if env::var_os(PANIC_IN_OBSERVER).is_some() {
+        let _ = panic::catch_unwind(|| panic!("observer"));
+    }
+}
+
+#[test]
+fn test_install() {
+    panic::set_hook(Box::new(|_| {}));
+    panic_hook::install();
+
+    let _ = panic::catch_unwind(|| panic!("boom {}", 1));
+    let _ = panic::catch_unwind(|| panic::panic_any(1));
+
+    let mut seen = SEEN.with(|seen| seen.borrow_mut().split_off(0));
+    seen.sort();
+    assert_eq!(
+        seen,
+        ["location", "location", "message: <none>", "message: boom 1"],
+    );
+}
+
+#[test]
+fn test_observer_panics() {
+    if env::var_os(PANIC_IN_OBSERVER).is_some() {
+        panic_hook::install();
+        let _ = panic::catch_unwind(|| panic!("boom"));
+        return;
+    }
+
+    // Even a panic the observer catches aborts the process.
+    let output = Command::new(env::current_exe().unwrap())
+        .args(&["--exact", "test_observer_panics", "--test-threads=1"])
+        .env(PANIC_IN_OBSERVER, "1")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("panicked while"), "{}", stderr);
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "alloc")]
+
+use linkme::periodic::{Job, PeriodicTask, Schedule};
+use linkme::{distributed_slice, duplicate};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[distributed_slice]
+static PERIODIC_TASKS: [PeriodicTask] = [..];
+
+static PURGES: AtomicUsize = AtomicUsize::new(0);
+
+// The periodic module needs the alloc feature and therefore rustc 1.61.
+#[clippy::msrv = "1.61"]
+#[distributed_slice(PERIODIC_TASKS)]
+static PURGE_SESSIONS: PeriodicTask = PeriodicTask {
+    name: "purge-sessions",
+    interval: Duration::from_secs(10),
+    job: Job::Blocking(|| {
+        PURGES.fetch_add(1, Ordering::SeqCst);
+    }),
+};
+
+#[clippy::msrv = "1.61"]
+#[distributed_slice(PERIODIC_TASKS)]
+static REFRESH_TOKENS: PeriodicTask = PeriodicTask {
+    name: "refresh-tokens",
+    interval: Duration::from_secs(25),
+    job: Job::Async(|| Box::pin(async {})),
+};
+
+fn names(tasks: Vec<&PeriodicTask>) -> Vec<&'static str> {
+    let mut names: Vec<_> = tasks.iter().map(|task| task.name).collect();
+    names.sort();
+    names
+}
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn test_schedule() {
+    duplicate::check(&PERIODIC_TASKS, |task| task.name).unwrap();
+
+    let mut schedule = Schedule::new(&PERIODIC_TASKS, secs(0));
+    assert_eq!(schedule.next_deadline(), Some(secs(10)));
+    assert!(schedule.due(secs(9)).is_empty());
+    assert_eq!(names(schedule.due(secs(10))), ["purge-sessions"]);
+    assert_eq!(schedule.next_deadline(), Some(secs(20)));
+
+    // Missed several periods of purge-sessions; returned once, realigned.
+    assert_eq!(
+        names(schedule.due(secs(47))),
+        ["purge-sessions", "refresh-tokens"],
+    );
+    assert_eq!(schedule.next_deadline(), Some(secs(50)));
+
+    for task in schedule.due(secs(50)) {
+        if let Job::Blocking(run) = task.job {
+            run();
+        }
+    }
+    assert_eq!(PURGES.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_zero_interval() {
+    let tasks = [PeriodicTask {
+        name: "busy",
+        interval: secs(0),
+        job: Job::Blocking(|| {}),
+    }];
+    let mut schedule = Schedule::new(&tasks, secs(5));
+    assert_eq!(schedule.next_deadline(), Some(secs(5)));
+    assert_eq!(names(schedule.due(secs(5))), ["busy"]);
+    assert_eq!(names(schedule.due(secs(5))), ["busy"]);
+    assert_eq!(names(schedule.due(secs(6))), ["busy"]);
+}
+
+#[test]
+fn test_long_stall() {
+    let tasks = [PeriodicTask {
+        name: "tick",
+        interval: secs(10),
+        job: Job::Blocking(|| {}),
+    }];
+    let mut schedule = Schedule::new(&tasks, secs(0));
+    let now = secs(1_000_000_000_000_007) + Duration::from_millis(500);
+    assert_eq!(names(schedule.due(now)), ["tick"]);
+    assert_eq!(schedule.next_deadline(), Some(secs(1_000_000_000_000_010)));
+}
+
+#[test]
+fn test_never_due() {
+    let tasks = [
+        PeriodicTask {
+            name: "never",
+            interval: Duration::MAX,
+            job: Job::Blocking(|| {}),
+        },
+        PeriodicTask {
+            name: "rare",
+            interval: Duration::MAX - secs(10),
+            job: Job::Blocking(|| {}),
+        },
+    ];
+    let mut schedule = Schedule::new(&tasks, secs(5));
+    assert_eq!(schedule.next_deadline(), Some(Duration::MAX - secs(5)));
+    assert!(schedule.due(secs(1_000)).is_empty());
+
+    // Due once, after which the next run would overflow.
+    assert_eq!(names(schedule.due(Duration::MAX)), ["rare"]);
+    assert_eq!(schedule.next_deadline(), None);
+    assert!(schedule.due(Duration::MAX).is_empty());
+}
@@ -0,0 +1,74 @@
+use linkme::distributed_slice;
+use linkme::plugin::{self, Plugin, Version};
+
+#[distributed_slice]
+static PLUGINS: [Plugin] = [..];
+
+#[distributed_slice(PLUGINS)]
+static S3: Plugin = Plugin {
+    name: "s3",
+    version: Version::new(1, 4, 0),
+    provides: &["blob-storage"],
+    requires: &["http-client"],
+};
+
+#[distributed_slice(PLUGINS)]
+static HYPER: Plugin = Plugin {
+    name: "hyper",
+    version: Version::new(0, 14, 2),
+    provides: &["http-client", "http-server"],
+    requires: &[],
+};
+
+#[test]
+fn test_query() {
+    plugin::validate(&PLUGINS).unwrap();
+
+    let hyper = PLUGINS
+        .iter()
+        .find(|plugin| plugin.name == "hyper")
+        .unwrap();
+    assert_eq!(hyper.version.to_string(), "0.14.2");
+    assert!(hyper.version < Version::new(1, 0, 0));
+
+    assert_eq!(
+        plugin::provider(&PLUGINS, "http-server").unwrap().name,
+        "hyper"
+    );
+    assert!(plugin::provider(&PLUGINS, "queue").is_none());
+}
+
+#[test]
+fn test_invalid() {
+    static DUPLICATE_CAPABILITY: [Plugin; 2] = [
+        Plugin {
+            name: "s3",
+            version: Version::new(1, 0, 0),
+            provides: &["blob-storage"],
+            requires: &[],
+        },
+        Plugin {
+            name: "gcs",
+            version: Version::new(1, 0, 0),
+            provides: &["blob-storage"],
+            requires: &[],
+        },
+    ];
+    let err = plugin::validate(&DUPLICATE_CAPABILITY).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "capability \"blob-storage\" is provided by both \"s3\" and \"gcs\"",
+    );
+
+    static MISSING_CAPABILITY: [Plugin; 1] = [Plugin {
+        name: "s3",
+        version: Version::new(1, 0, 0),
+        provides: &[],
+        requires: &["http-client"],
+    }];
+    let err = plugin::validate(&MISSING_CAPABILITY).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "plugin \"s3\" requires capability \"http-client\" which no plugin provides",
+    );
+}
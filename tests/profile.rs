@@ -0,0 +1,40 @@
+use linkme::distributed_slice;
+use linkme::profile::{self, ScopeEvent, ScopeKind, HOOKS};
+use std::cell::RefCell;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<(ScopeKind, &'static str)>> = RefCell::new(Vec::new());
+}
+
+#[distributed_slice(HOOKS)]
+fn record(event: &ScopeEvent) {
+    assert!(event.file.ends_with("profile.rs"));
+    EVENTS.with(|events| events.borrow_mut().push((event.kind, event.name)));
+}
+
+fn outer() {
+    linkme::profile_scope!("outer");
+    inner();
+}
+
+fn inner() {
+    linkme::profile_scope!("inner");
+}
+
+#[test]
+fn test_scopes() {
+    assert!(profile::is_enabled());
+
+    outer();
+
+    let events = EVENTS.with(|events| events.borrow_mut().split_off(0));
+    assert_eq!(
+        events,
+        [
+            (ScopeKind::Enter, "outer"),
+            (ScopeKind::Enter, "inner"),
+            (ScopeKind::Exit, "inner"),
+            (ScopeKind::Exit, "outer"),
+        ],
+    );
+}
@@ -0,0 +1,65 @@
+use linkme::distributed_slice;
+use linkme::registry::Registry;
+
+pub struct Job {
+    pub name: &'static str,
+    pub weight: u32,
+}
+
+fn total_weight<R: Registry<Job> + ?Sized>(jobs: &R) -> u32 {
+    jobs.iter().map(|job| job.weight).sum()
+}
+
+#[distributed_slice]
+static JOBS: [Job] = [..];
+
+#[distributed_slice(JOBS)]
+static COMPACT: Job = Job {
+    name: "compact",
+    weight: 3,
+};
+
+#[distributed_slice(JOBS)]
+static VACUUM: Job = Job {
+    name: "vacuum",
+    weight: 4,
+};
+
+#[test]
+fn test_distributed_slice() {
+    assert_eq!(Registry::len(&JOBS), 2);
+    assert!(Registry::get(&JOBS, 2).is_none());
+    assert_eq!(total_weight(&JOBS), 7);
+}
+
+#[test]
+fn test_plain_slice() {
+    let jobs: &[Job] = &[Job {
+        name: "fake",
+        weight: 10,
+    }];
+    assert_eq!(total_weight(jobs), 10);
+    assert_eq!(total_weight(&jobs), 10);
+    assert_eq!(jobs.iter().len(), 1);
+    assert_eq!(Registry::get(jobs, 0).unwrap().name, "fake");
+
+    let empty: &[Job] = &[];
+    assert!(Registry::is_empty(empty));
+    assert_eq!(total_weight(empty), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_runtime_slice() {
+    use linkme::runtime::RuntimeSlice;
+
+    static WITH_ADDED: RuntimeSlice<Job> = RuntimeSlice::new(&JOBS);
+
+    WITH_ADDED.push(Job {
+        name: "reindex",
+        weight: 5,
+    });
+    assert_eq!(Registry::len(&WITH_ADDED), 3);
+    assert_eq!(Registry::get(&WITH_ADDED, 2).unwrap().name, "reindex");
+    assert_eq!(total_weight(&WITH_ADDED), 12);
+}
@@ -0,0 +1,80 @@
+use linkme::rtic::{self, Monotonic, Task};
+use linkme::{distributed_slice, duplicate};
+
+#[derive(Default)]
+struct Board {
+    log: Vec<&'static str>,
+}
+
+#[distributed_slice]
+static MONOTONICS: [Monotonic<Board>] = [..];
+
+#[distributed_slice]
+static TASKS: [Task<Board>] = [..];
+
+#[distributed_slice(MONOTONICS)]
+static SYSTICK: Monotonic<Board> = Monotonic {
+    name: "systick",
+    start: |board| board.log.push("systick"),
+};
+
+#[distributed_slice(MONOTONICS)]
+static RTC: Monotonic<Board> = Monotonic {
+    name: "rtc",
+    start: |board| board.log.push("rtc"),
+};
+
+#[distributed_slice(TASKS)]
+static BLINK: Task<Board> = Task {
+    name: "blink",
+    priority: 1,
+    run: |board| board.log.push("blink"),
+};
+
+#[distributed_slice(TASKS)]
+static SENSOR: Task<Board> = Task {
+    name: "sensor",
+    priority: 2,
+    run: |board| board.log.push("sensor"),
+};
+
+#[distributed_slice(TASKS)]
+static HEARTBEAT: Task<Board> = Task {
+    name: "heartbeat",
+    priority: 1,
+    run: |board| board.log.push("heartbeat"),
+};
+
+#[test]
+fn test_contributions() {
+    duplicate::check(&TASKS, |task| task.name).unwrap();
+    assert!(rtic::undispatched(&TASKS, 2).is_none());
+
+    let mut board = Board::default();
+    rtic::start_monotonics(&MONOTONICS, &mut board);
+    assert_eq!(board.log, ["rtc", "systick"]);
+
+    board.log.clear();
+    rtic::run_at(&TASKS, 1, &mut board);
+    assert_eq!(board.log, ["blink", "heartbeat"]);
+
+    board.log.clear();
+    rtic::run_at(&TASKS, 2, &mut board);
+    assert_eq!(board.log, ["sensor"]);
+
+    assert!(rtic::uses_priority(&TASKS, 2));
+    assert!(!rtic::uses_priority(&TASKS, 3));
+}
+
+#[test]
+fn test_undispatched() {
+    let task = rtic::undispatched(&TASKS, 1).unwrap();
+    assert_eq!(task.name, "sensor");
+
+    let tasks: [Task<()>; 1] = [Task {
+        name: "idle",
+        priority: 0,
+        run: |()| {},
+    }];
+    assert_eq!(rtic::undispatched(&tasks, 1).unwrap().name, "idle");
+}
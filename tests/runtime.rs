@@ -0,0 +1,90 @@
+#![cfg(feature = "alloc")]
+#![allow(clippy::incompatible_msrv)]
+
+use linkme::distributed_slice;
+use linkme::runtime::RuntimeSlice;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+#[distributed_slice]
+static LINKED: [u32] = [..];
+
+static NUMBERS: RuntimeSlice<u32> = RuntimeSlice::new(&LINKED);
+
+#[distributed_slice(LINKED)]
+static ZERO: u32 = 0;
+
+#[test]
+fn test_snapshot() {
+    let before = NUMBERS.snapshot();
+    assert_eq!(before.len(), 1);
+
+    let pushed = NUMBERS.push(1);
+    assert_eq!(*pushed, 1);
+    assert_eq!(before.iter().copied().collect::<Vec<_>>(), [0]);
+
+    let after = NUMBERS.snapshot();
+    assert_eq!(after.to_vec(), [&0, &1]);
+    assert_eq!(after.get(1), Some(&1));
+    assert_eq!(after.get(2), None);
+    assert!(std::ptr::eq(after.get(1).unwrap(), pushed));
+}
+
+#[distributed_slice]
+static NONE: [u64] = [..];
+
+static CONCURRENT: RuntimeSlice<u64> = RuntimeSlice::new(&NONE);
+
+#[test]
+fn test_concurrent() {
+    const WRITERS: u64 = 4;
+    const PER_WRITER: u64 = 1000;
+
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    let snapshot = CONCURRENT.snapshot();
+                    // Every entry in a snapshot is fully written.
+                    assert_eq!(snapshot.iter().count(), snapshot.len());
+                    assert!(snapshot.iter().all(|&n| n < WRITERS * PER_WRITER));
+                }
+            });
+        }
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|w| {
+                scope.spawn(move || {
+                    for n in 0..PER_WRITER {
+                        CONCURRENT.push(w * PER_WRITER + n);
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+
+    let mut all: Vec<u64> = CONCURRENT.iter().copied().collect();
+    all.sort_unstable();
+    assert_eq!(all, (0..WRITERS * PER_WRITER).collect::<Vec<_>>());
+}
+
+#[distributed_slice]
+static NO_RCS: [Rc<()>] = [..];
+
+#[test]
+fn test_drop() {
+    let rc = Rc::new(());
+    {
+        let slice = RuntimeSlice::new(&NO_RCS);
+        for _ in 0..10 {
+            slice.push(Rc::clone(&rc));
+        }
+        assert_eq!(Rc::strong_count(&rc), 11);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
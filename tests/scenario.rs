@@ -0,0 +1,47 @@
+use linkme::scenario::{self, Scenario};
+use linkme::{distributed_slice, duplicate};
+
+struct World {
+    log: Vec<String>,
+    time: u64,
+}
+
+#[distributed_slice]
+static SCENARIOS: [Scenario<World>] = [..];
+
+#[distributed_slice(SCENARIOS)]
+static RAIN: Scenario<World> = Scenario {
+    name: "rain",
+    description: "Steady rainfall",
+    setup: |world| world.log.push("clouds".to_owned()),
+    tick: |world| world.time += 1,
+    teardown: |world| world.log.push(format!("dried after {}", world.time)),
+};
+
+#[distributed_slice(SCENARIOS)]
+static DROUGHT: Scenario<World> = Scenario {
+    name: "drought",
+    description: "No rain at all",
+    setup: |_world| {},
+    tick: |_world| {},
+    teardown: |_world| {},
+};
+
+#[test]
+fn test_menu() {
+    duplicate::check(&SCENARIOS, |scenario| scenario.name).unwrap();
+    let names: Vec<_> = scenario::menu(&SCENARIOS)
+        .map(|scenario| scenario.name)
+        .collect();
+    assert_eq!(names, ["drought", "rain"]);
+}
+
+#[test]
+fn test_run() {
+    let mut world = World {
+        log: Vec::new(),
+        time: 0,
+    };
+    scenario::run(&RAIN, &mut world, 3);
+    assert_eq!(world.log, ["clouds", "dried after 3"]);
+}
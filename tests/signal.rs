@@ -0,0 +1,81 @@
+#![cfg(any(
+    all(
+        target_os = "linux",
+        any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            target_arch = "arm",
+            target_arch = "aarch64",
+            target_arch = "riscv64",
+        ),
+    ),
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "illumos",
+))]
+
+use linkme::distributed_slice;
+use linkme::signal::{self, SignalHandler, HANDLERS, SIGHUP};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RELOADS: AtomicUsize = AtomicUsize::new(0);
+static FLUSHES: AtomicUsize = AtomicUsize::new(0);
+
+#[distributed_slice(HANDLERS)]
+static RELOAD: SignalHandler = SignalHandler {
+    signal: SIGHUP,
+    handler: |_| {
+        RELOADS.fetch_add(1, Ordering::SeqCst);
+    },
+};
+
+#[distributed_slice(HANDLERS)]
+static FLUSH: SignalHandler = SignalHandler {
+    signal: SIGHUP,
+    handler: |signum| {
+        assert_eq!(signum, SIGHUP);
+        FLUSHES.fetch_add(1, Ordering::SeqCst);
+    },
+};
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    mask: [u8; 128],
+    flags: i32,
+    restorer: usize,
+}
+
+extern "C" {
+    fn raise(signum: i32) -> i32;
+    #[cfg(target_os = "linux")]
+    fn sigaction(signum: i32, act: *const SigAction, oldact: *mut SigAction) -> i32;
+}
+
+#[test]
+fn test_dispatch() {
+    signal::install().unwrap();
+
+    assert_eq!(unsafe { raise(SIGHUP) }, 0);
+    assert_eq!(unsafe { raise(SIGHUP) }, 0);
+
+    assert_eq!(RELOADS.load(Ordering::SeqCst), 2);
+    assert_eq!(FLUSHES.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_restart() {
+    const SA_RESTART: i32 = 0x1000_0000;
+
+    signal::install().unwrap();
+
+    let mut action: SigAction = unsafe { std::mem::zeroed() };
+    assert_eq!(
+        unsafe { sigaction(SIGHUP, std::ptr::null(), &mut action) },
+        0
+    );
+    assert_ne!(action.handler, 0);
+    assert_ne!(action.flags & SA_RESTART, 0);
+}
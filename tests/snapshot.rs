@@ -0,0 +1,100 @@
+#![cfg(feature = "std")]
+
+use linkme::distributed_slice;
+use linkme::snapshot::{self, Mode, Outcome, Settings, SnapshotCase};
+use std::fs;
+use std::path::PathBuf;
+
+#[distributed_slice]
+static SNAPSHOTS: [SnapshotCase] = [..];
+
+#[distributed_slice(SNAPSHOTS)]
+static GREETING: SnapshotCase = SnapshotCase {
+    name: "greeting",
+    input: || "hello world\n\n".to_owned(),
+    settings: Settings::DEFAULT,
+};
+
+#[distributed_slice(SNAPSHOTS)]
+static NUMBERS: SnapshotCase = SnapshotCase {
+    name: "numbers",
+    input: || format!("{:?}", [1, 2, 3]),
+    settings: Settings {
+        extension: "txt",
+        trim: false,
+    },
+};
+
+#[distributed_slice(SNAPSHOTS)]
+static DOTTED: SnapshotCase = SnapshotCase {
+    name: "parser.v2",
+    input: || "ok".to_owned(),
+    settings: Settings::DEFAULT,
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("linkme-snapshot-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_update_then_compare() {
+    let dir = temp_dir("update");
+
+    let report = snapshot::run(&SNAPSHOTS, &dir, Mode::Compare);
+    assert!(!report.is_ok());
+    assert_eq!(
+        report.cases[0],
+        (
+            "greeting",
+            Outcome::Missing {
+                actual: "hello world\n".to_owned(),
+            },
+        ),
+    );
+
+    let report = snapshot::run(&SNAPSHOTS, &dir, Mode::Update);
+    assert!(report.is_ok());
+    assert_eq!(report.cases[0], ("greeting", Outcome::Written));
+    assert_eq!(
+        fs::read_to_string(dir.join("numbers.txt")).unwrap(),
+        "[1, 2, 3]"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("parser.v2.snap")).unwrap(),
+        "ok\n"
+    );
+
+    let report = snapshot::run(&SNAPSHOTS, &dir, Mode::Compare);
+    report.assert_ok();
+    assert_eq!(report.cases[1], ("numbers", Outcome::Matched));
+
+    fs::write(dir.join("greeting.snap"), "goodbye\n").unwrap();
+    let report = snapshot::run(&SNAPSHOTS, &dir, Mode::Compare);
+    assert_eq!(
+        report.cases[0],
+        (
+            "greeting",
+            Outcome::Mismatch {
+                expected: "goodbye\n".to_owned(),
+                actual: "hello world\n".to_owned(),
+            },
+        ),
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_panicked() {
+    let dir = temp_dir("panicked");
+    let cases = [SnapshotCase {
+        name: "broken",
+        input: || panic!("input failed"),
+        settings: Settings::DEFAULT,
+    }];
+    let report = snapshot::run(&cases, &dir, Mode::Update);
+    assert_eq!(report.cases, [("broken", Outcome::Panicked)]);
+    assert_eq!(report.to_string(), "broken: panicked\n");
+}
@@ -0,0 +1,67 @@
+#![cfg(not(no_const_control_flow))]
+
+use linkme::distributed_slice;
+use linkme::stable_id;
+
+pub struct Codec {
+    pub name: &'static str,
+}
+
+fn name(codec: &Codec) -> &'static str {
+    codec.name
+}
+
+#[distributed_slice]
+static CODECS: [Codec] = [..];
+
+#[distributed_slice(CODECS)]
+static ZSTD: Codec = Codec { name: "zstd" };
+
+#[distributed_slice(CODECS)]
+static GZIP: Codec = Codec { name: "gzip" };
+
+#[distributed_slice(CODECS)]
+static LZ4: Codec = Codec { name: "lz4" };
+
+#[test]
+fn test_hash() {
+    // Reference values of 32-bit FNV-1a, which must never change.
+    const EMPTY: u32 = stable_id::hash("");
+    assert_eq!(EMPTY, 0x811c_9dc5);
+    assert_eq!(stable_id::hash("a"), 0xe40c_292c);
+    assert_eq!(stable_id::hash("foobar"), 0xbf9c_f968);
+
+    stable_id::validate(&CODECS, name).unwrap();
+    let id = stable_id::hash("gzip");
+    assert_eq!(stable_id::by_hash(&CODECS, name, id).unwrap().name, "gzip");
+    assert!(stable_id::by_hash(&CODECS, name, stable_id::hash("brotli")).is_none());
+}
+
+#[test]
+fn test_index() {
+    assert_eq!(stable_id::index_of(&CODECS, name, "gzip"), Some(0));
+    assert_eq!(stable_id::index_of(&CODECS, name, "lz4"), Some(1));
+    assert_eq!(stable_id::index_of(&CODECS, name, "zstd"), Some(2));
+    assert_eq!(stable_id::index_of(&CODECS, name, "brotli"), None);
+
+    assert_eq!(stable_id::by_index(&CODECS, name, 2).unwrap().name, "zstd");
+    assert!(stable_id::by_index(&CODECS, name, 3).is_none());
+}
+
+#[test]
+fn test_collision() {
+    let codecs = [Codec { name: "snappy" }, Codec { name: "snappy" }];
+    let err = stable_id::validate(&codecs, name).unwrap_err();
+    assert_eq!(err.to_string(), "name \"snappy\" is registered twice");
+
+    // Known FNV-1a 32-bit collision.
+    let codecs = [Codec { name: "costarring" }, Codec { name: "liquid" }];
+    let err = stable_id::validate(&codecs, name).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "names \"costarring\" and \"liquid\" both hash to {:#010x}",
+            err.id,
+        ),
+    );
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "alloc")]
+
+use linkme::distributed_slice;
+use linkme::startup::{self, BoxFuture, Startup};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+// The startup module needs the alloc feature and therefore rustc 1.61, so
+// the items below are checked against that rather than the crate's MSRV.
+
+#[clippy::msrv = "1.61"]
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    let waker = unsafe { Waker::from_raw(clone(std::ptr::null())) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+// Returns Pending a given number of times before completing.
+struct YieldFor(usize);
+
+#[clippy::msrv = "1.61"]
+impl Future for YieldFor {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.0 == 0 {
+            return Poll::Ready(());
+        }
+        self.0 -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+static MAX_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct Ctx {
+    fail: &'static str,
+    log: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[clippy::msrv = "1.61"]
+fn routine(name: &'static str, ctx: Ctx) -> BoxFuture<Result<(), String>> {
+    Box::pin(async move {
+        let now = IN_FLIGHT.fetch_add(1, Ordering::SeqCst) + 1;
+        MAX_IN_FLIGHT.fetch_max(now, Ordering::SeqCst);
+        YieldFor(2).await;
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+        if ctx.fail == name {
+            return Err(format!("{} is broken", name));
+        }
+        ctx.log.lock().unwrap().push(name);
+        Ok(())
+    })
+}
+
+#[distributed_slice]
+static STARTUP: [Startup<Ctx, String>] = [..];
+
+#[distributed_slice(STARTUP)]
+static CACHE: Startup<Ctx, String> = Startup {
+    name: "cache",
+    run: |ctx| routine("cache", ctx),
+};
+
+#[distributed_slice(STARTUP)]
+static MIGRATE: Startup<Ctx, String> = Startup {
+    name: "migrate",
+    run: |ctx| routine("migrate", ctx),
+};
+
+#[distributed_slice(STARTUP)]
+static DISCOVERY: Startup<Ctx, String> = Startup {
+    name: "discovery",
+    run: |ctx| routine("discovery", ctx),
+};
+
+// The tests share the counters above, so run every scenario from one test.
+#[test]
+fn test_run_all() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let ok = Ctx {
+        fail: "",
+        log: log.clone(),
+    };
+
+    block_on(startup::run_all(&STARTUP, ok.clone(), 1)).unwrap();
+    assert_eq!(*log.lock().unwrap(), ["cache", "discovery", "migrate"]);
+    assert_eq!(MAX_IN_FLIGHT.load(Ordering::SeqCst), 1);
+
+    log.lock().unwrap().clear();
+    block_on(startup::run_all(&STARTUP, ok.clone(), 2)).unwrap();
+    assert_eq!(log.lock().unwrap().len(), 3);
+    assert_eq!(MAX_IN_FLIGHT.load(Ordering::SeqCst), 2);
+
+    log.lock().unwrap().clear();
+    block_on(startup::run_all(&STARTUP, ok, 0)).unwrap();
+    assert_eq!(*log.lock().unwrap(), ["cache", "discovery", "migrate"]);
+
+    log.lock().unwrap().clear();
+    IN_FLIGHT.store(0, Ordering::SeqCst);
+    let failing = Ctx {
+        fail: "discovery",
+        log: log.clone(),
+    };
+    let err = block_on(startup::run_all(&STARTUP, failing, 1)).unwrap_err();
+    assert_eq!(err.name, "discovery");
+    assert_eq!(err.error, "discovery is broken");
+    assert_eq!(err.to_string(), "startup routine \"discovery\" failed");
+    assert_eq!(*log.lock().unwrap(), ["cache"]);
+}
@@ -0,0 +1,110 @@
+use linkme::distributed_slice;
+use linkme::state_machine::{self, Transition};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum State {
+    Idle,
+    Running,
+    Paused,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Event {
+    Start,
+    Pause,
+    Stop,
+}
+
+#[derive(Default)]
+pub struct Job {
+    log: Vec<&'static str>,
+}
+
+#[distributed_slice]
+static JOB: [Transition<State, Event, Job>] = [..];
+
+#[distributed_slice(JOB)]
+static START: Transition<State, Event, Job> = Transition {
+    from: State::Idle,
+    event: Event::Start,
+    action: |job| job.log.push("start"),
+    to: State::Running,
+};
+
+#[distributed_slice(JOB)]
+static PAUSE: Transition<State, Event, Job> = Transition {
+    from: State::Running,
+    event: Event::Pause,
+    action: |job| job.log.push("pause"),
+    to: State::Paused,
+};
+
+#[distributed_slice(JOB)]
+static RESUME: Transition<State, Event, Job> = Transition {
+    from: State::Paused,
+    event: Event::Start,
+    action: |job| job.log.push("resume"),
+    to: State::Running,
+};
+
+#[distributed_slice(JOB)]
+static STOP: Transition<State, Event, Job> = Transition {
+    from: State::Running,
+    event: Event::Stop,
+    action: |job| job.log.push("stop"),
+    to: State::Idle,
+};
+
+#[test]
+fn test_fire() {
+    let mut state = State::Idle;
+    let mut job = Job::default();
+    for event in &[Event::Start, Event::Pause, Event::Start, Event::Stop] {
+        assert!(state_machine::fire(&JOB, &mut state, event, &mut job));
+    }
+    assert_eq!(state, State::Idle);
+    assert_eq!(job.log, ["start", "pause", "resume", "stop"]);
+
+    assert!(!state_machine::fire(
+        &JOB,
+        &mut state,
+        &Event::Pause,
+        &mut job
+    ));
+    assert_eq!(state, State::Idle);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_validate() {
+    state_machine::validate(&JOB, &State::Idle).unwrap();
+
+    let unreachable = [Transition {
+        from: State::Paused,
+        event: Event::Stop,
+        action: |_: &mut ()| {},
+        to: State::Idle,
+    }];
+    let err = state_machine::validate(&unreachable, &State::Idle).unwrap_err();
+    assert_eq!(err.to_string(), "state Paused is unreachable");
+
+    let conflicting = [
+        Transition {
+            from: State::Idle,
+            event: Event::Start,
+            action: |_: &mut ()| {},
+            to: State::Running,
+        },
+        Transition {
+            from: State::Idle,
+            event: Event::Start,
+            action: |_: &mut ()| {},
+            to: State::Paused,
+        },
+    ];
+    let err = state_machine::validate(&conflicting, &State::Idle).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "conflicting transitions from Idle on Start: to Running and to Paused",
+    );
+}
@@ -0,0 +1,64 @@
+// Slices first read by several threads at once. On wasm this needs shared
+// memory, as in wasm32-wasip1-threads or a build with
+// -Ctarget-feature=+atomics,+bulk-memory, and is skipped without it.
+
+use linkme::distributed_slice;
+use std::io::ErrorKind;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[distributed_slice]
+static NUMBERS: [u32] = [..];
+
+#[distributed_slice(NUMBERS)]
+static ONE: u32 = 1;
+
+#[distributed_slice(NUMBERS)]
+static TWO: u32 = 2;
+
+#[distributed_slice(NUMBERS)]
+static THREE: u32 = 3;
+
+#[distributed_slice]
+#[linkme(ordered)]
+static STAGES: [&'static str] = [..];
+
+#[distributed_slice(STAGES, order = 2)]
+static FLUSH: &str = "flush";
+
+#[distributed_slice(STAGES, order = -1)]
+static PARSE: &str = "parse";
+
+#[distributed_slice(STAGES)]
+static CHECK: &str = "check";
+
+#[test]
+fn test_concurrent_first_access() {
+    let barrier = Arc::new(Barrier::new(4));
+    let mut threads = Vec::new();
+    for _ in 0..4 {
+        let barrier = Arc::clone(&barrier);
+        let spawned = thread::Builder::new().spawn(move || {
+            barrier.wait();
+            let numbers = NUMBERS.static_slice();
+            let stages = STAGES.static_slice();
+            (numbers.as_ptr() as usize, stages.as_ptr() as usize)
+        });
+        match spawned {
+            Ok(thread) => threads.push(thread),
+            Err(ref err) if threads.is_empty() && err.kind() == ErrorKind::Unsupported => return,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    let addresses: Vec<_> = threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect();
+
+    // Every thread sees the same slices.
+    assert!(addresses.iter().all(|address| *address == addresses[0]));
+    let mut numbers = NUMBERS.to_vec();
+    numbers.sort_unstable();
+    assert_eq!(numbers, [1, 2, 3]);
+    assert_eq!(*STAGES, ["parse", "check", "flush"]);
+}
@@ -0,0 +1,32 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static CALLBACKS: [extern "C" fn()] = [..];
+
+#[distributed_slice(CALLBACKS)]
+fn rust_abi() {}
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static RUST: [fn()] = [..];
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static SYSTEM: [extern "system" fn()] = [..];
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static NOT_FN: [usize] = [..];
+
+type RustCallback = fn();
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static ALIASED: [RustCallback] = [..];
+
+#[distributed_slice]
+#[linkme(abi = "C")]
+pub static NOT_SLICE: extern "C" fn() = [..];
+
+fn main() {}
@@ -0,0 +1,25 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static NAMES: [&'static str] = [..];
+
+pub trait Named {
+    const NAME: &'static str;
+}
+
+pub struct Wrapper<T>(T);
+
+#[distributed_slice]
+impl<T> Named for Wrapper<T> {
+    #[distributed_slice(NAMES)]
+    const NAME: &'static str = "wrapper";
+}
+
+pub struct Plain;
+
+impl Named for Plain {
+    #[distributed_slice(NAMES)]
+    const NAME: &'static str = "plain";
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static SLICE: [i32] = [..];
+
+#[distributed_slice(SLICE)]
+#[linkme(export_c = "element")]
+static ELEMENT: i32 = 0;
+
+fn main() {}
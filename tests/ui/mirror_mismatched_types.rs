@@ -0,0 +1,10 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static UPSTREAM: [u32] = [..];
+
+#[distributed_slice]
+#[linkme(mirror = UPSTREAM)]
+pub static MIRROR: [u64] = [..];
+
+fn main() {}
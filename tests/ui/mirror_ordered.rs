@@ -0,0 +1,11 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+#[linkme(ordered)]
+pub static UPSTREAM: [u32] = [..];
+
+#[distributed_slice]
+#[linkme(mirror = UPSTREAM)]
+pub static MIRROR: [u32] = [..];
+
+fn main() {}
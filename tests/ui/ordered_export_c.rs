@@ -0,0 +1,8 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+#[linkme(ordered)]
+#[linkme(export_c = "ordered_registry")]
+pub static REGISTRY: [u32] = [..];
+
+fn main() {}
@@ -0,0 +1,16 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static SLICE: [&'static str] = [..];
+
+#[distributed_slice(SLICE, order = 1)]
+static ELEMENT: &str = "element";
+
+#[distributed_slice]
+#[linkme(ordered)]
+pub static ORDERED: [&'static str] = [..];
+
+#[distributed_slice(ORDERED, 1)]
+static POSITIONAL: &str = "positional";
+
+fn main() {}
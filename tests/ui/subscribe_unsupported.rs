@@ -0,0 +1,27 @@
+#[linkme::event]
+pub struct Event;
+
+pub struct NotAnEvent;
+
+#[linkme::event]
+pub struct Generic<T>(T);
+
+#[linkme::event]
+fn not_a_type() {}
+
+#[linkme::subscribe(Event)]
+async fn async_subscriber(_: &Event) {}
+
+#[linkme::subscribe(Event)]
+unsafe fn unsafe_subscriber(_: &Event) {}
+
+#[linkme::subscribe(Event)]
+static NOT_A_FUNCTION: usize = 0;
+
+#[linkme::subscribe(Event)]
+fn wrong_event(_: &usize) {}
+
+#[linkme::subscribe(NotAnEvent)]
+fn undeclared_event(_: &NotAnEvent) {}
+
+fn main() {}